@@ -1,3 +1,6 @@
+use mr_kaffee_utils::solution::Solution;
+use std::{convert::Infallible, io, path::Path};
+
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/00";
 
@@ -26,11 +29,34 @@ pub fn parse_input() -> &'static str {
 
 // tag::star_1[]
 pub fn star_1<T: AsRef<InputType>>(input: T) -> Sol1Type {
-    println!("{}", input.as_ref());
+    // stderr, so that machine readable output on stdout stays intact
+    eprintln!("{}", input.as_ref());
     0
 }
 // end::star_1[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = &'a str;
+    type Answer1 = Sol1Type;
+    type Answer2 = Infallible;
+
+    fn read_input(_path: Option<&Path>) -> io::Result<String> {
+        Ok(parse_input().to_string())
+    }
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -45,7 +71,7 @@ mod tests {
     #[test]
     pub fn test_star_1() {
         // the function star_1 can be called with a String ...
-        assert_eq!(0, star_1("owned".to_string()));
+        assert_eq!(0, star_1("owned"));
         // ... or with a &str argument
         assert_eq!(0, star_1("reference"));
     }
@@ -1,19 +1,19 @@
-use std::fs;
+use mr_kaffee_utils::inputs::{path_from_args, read_input};
 
 fn main() {
-    let input = fs::read_to_string("/workspace/inputs/input01.txt");
+    let input = read_input(1, path_from_args().as_deref());
     match input {
         Ok(input) => {
             let result_1 = calculate_result_part_1(&input);
             let result_2 = calculate_result_part_2(&input);
-            print!("Solution to part 1: {}\n", result_1);
-            print!("Solution to part 2: {}\n", result_2);
+            println!("Solution to part 1: {}", result_1);
+            println!("Solution to part 2: {}", result_2);
         },
         Err(error) => panic!("Problem opening the file: {:?}", error),
     };
 }
 
-fn calculate_result_part_1(input_str: &String) -> u32 {
+fn calculate_result_part_1(input_str: &str) -> u32 {
     let mut sum: u32 = 0;
 
     for line in input_str.split('\n') {
@@ -30,8 +30,8 @@ fn calculate_result_part_1(input_str: &String) -> u32 {
                 last = c.to_digit(10);
             }
         }
-        if first.is_some() && last.is_some() {
-            sum += first.unwrap() * 10 + last.unwrap();
+        if let (Some(first), Some(last)) = (first, last) {
+            sum += first * 10 + last;
         } else {
             panic!("Bad input");
         }
@@ -40,7 +40,7 @@ fn calculate_result_part_1(input_str: &String) -> u32 {
     sum
 }
 
-fn calculate_result_part_2(input: &String) -> u32 {
+fn calculate_result_part_2(input: &str) -> u32 {
     let mut sum = 0;
 
     let digits = [("zero", 0u32),
@@ -93,8 +93,8 @@ fn calculate_result_part_2(input: &String) -> u32 {
                 None => continue
             }
         }
-        if first.is_some() && last.is_some() {
-            sum += first.unwrap() * 10 + last.unwrap();
+        if let (Some(first), Some(last)) = (first, last) {
+            sum += first * 10 + last;
         } else {
             panic!("Bad input");
         }
@@ -109,37 +109,37 @@ mod tests {
 
     #[test]
     fn single_line_input() {
-        let result = calculate_result_part_1(&"aaa1aa2aa4aa\n".to_string());
+        let result = calculate_result_part_1("aaa1aa2aa4aa\n");
         assert_eq!(result, 14);
     }
 
     #[test]
     fn multiple_line_input() {
-        let result = calculate_result_part_1(&"10\naa10\n10aa\n".to_string());
+        let result = calculate_result_part_1("10\naa10\n10aa\n");
         assert_eq!(result, 30);
     }
 
     #[test]
     fn only_str_digits() {
-        let result = calculate_result_part_2(&"onetwothreeaaa\n".to_string());
+        let result = calculate_result_part_2("onetwothreeaaa\n");
         assert_eq!(result, 13);
     }
 
     #[test]
     fn str_digits_and_ascii_digits_mixed() {
-        let result = calculate_result_part_2(&"onsssze1nine17aaaone18fivesixzero0011five\n".to_string());
+        let result = calculate_result_part_2("onsssze1nine17aaaone18fivesixzero0011five\n");
         assert_eq!(result, 15);
     }
 
     #[test]
     fn only_one_ascii_digits_at_beginning() {
-        let result = calculate_result_part_2(&"1qmnxhfjsrq\n".to_string());
+        let result = calculate_result_part_2("1qmnxhfjsrq\n");
         assert_eq!(result, 11);
     }
 
     #[test]
     fn only_one_ascii_digits_at_end() {
-        let result = calculate_result_part_2(&"qmnxhfjsrq7\n".to_string());
+        let result = calculate_result_part_2("qmnxhfjsrq7\n");
         assert_eq!(result, 77);
     }
 }
@@ -1,4 +1,4 @@
-use std::fs::read_to_string;
+use mr_kaffee_utils::solution::Solution;
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/01";
@@ -9,7 +9,7 @@ pub type SolT = usize;
 
 // tag::parse[]
 pub fn parse_input() -> String {
-    read_to_string("../../../inputs/input01").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::parse[]
 
@@ -42,6 +42,28 @@ pub fn map_2(line: &str) -> Option<SolT> {
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = &'a str;
+    type Answer1 = SolT;
+    type Answer2 = SolT;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star(input, &map_1)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star(input, &map_2))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
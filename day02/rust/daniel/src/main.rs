@@ -1,20 +1,20 @@
-use std::fs;
+use mr_kaffee_utils::inputs::{path_from_args, read_input};
 use regex::Regex;
 use std::collections::HashMap;
 
 fn main() {
-    let input = match fs::read_to_string("/workspace/inputs/input02.txt") {
+    let input = match read_input(2, path_from_args().as_deref()) {
         Ok(input_str) => input_str,
         Err(error) => panic!("Problem opening input file: {:?}", error),
     };
 
     let result_1 = calculate_result_part_1(&input);
-    print!("Solution to part 1: {}\n", result_1);
+    println!("Solution to part 1: {}", result_1);
     let result_2 = calculate_result_part_2(&input);
-    print!("Solution to part 2: {}\n", result_2);
+    println!("Solution to part 2: {}", result_2);
 }
 
-fn calculate_result_part_1(input: &String) -> u32 {
+fn calculate_result_part_1(input: &str) -> u32 {
     let cubes = HashMap::from([
         ("red", 12),
         ("green", 13),
@@ -46,7 +46,7 @@ fn calculate_result_part_1(input: &String) -> u32 {
     sum_ids
 }
 
-fn calculate_result_part_2(input: &String) -> u32 {
+fn calculate_result_part_2(input: &str) -> u32 {
     let mut sum_power: u32 = 0;
     let re = Regex::new(r" (([0-9]+) ([a-z]+),?)+").unwrap();
 
@@ -68,7 +68,7 @@ fn calculate_result_part_2(input: &String) -> u32 {
         }
 
         let mut power = 1;
-        for (_, value) in &cubes {
+        for value in cubes.values() {
             power *= value;
         }
         sum_power += power;
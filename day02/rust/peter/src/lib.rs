@@ -1,5 +1,5 @@
 use input::PuzzleData;
-use std::fs::read_to_string;
+use mr_kaffee_utils::{parse::ParseError, solution::Solution};
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/02";
@@ -11,36 +11,50 @@ pub type SolT = usize;
 // tag::input[]
 pub mod input {
     use crate::SolT;
+    use mr_kaffee_utils::parse::{parse_token, ParseError};
+    use std::str::FromStr;
 
     #[derive(Debug)]
     pub struct PuzzleData(pub Vec<Vec<[SolT; 3]>>);
 
-    fn parse_draw(line: &str) -> [SolT; 3] {
-        line.split(", ").fold([0; 3], |[r, g, b], color| {
-            match color
+    fn parse_draw(input: &str, draw: &str) -> Result<[SolT; 3], ParseError> {
+        draw.split(", ").try_fold([0; 3], |[r, g, b], color| {
+            let (n, c) = color
                 .split_once(' ')
-                .map(|(n, c)| (n.parse::<SolT>().unwrap(), c))
-            {
-                Some((n, "red")) => [r + n, g, b],
-                Some((n, "green")) => [r, g + n, b],
-                Some((n, "blue")) => [r, g, b + n],
-                _ => panic!("Unexpected draw."),
+                .ok_or_else(|| ParseError::after(input, color, "' '"))?;
+            let n = parse_token::<SolT>(input, n, "number of cubes")?;
+            match c {
+                "red" => Ok([r + n, g, b]),
+                "green" => Ok([r, g + n, b]),
+                "blue" => Ok([r, g, b + n]),
+                _ => Err(ParseError::at(input, c, "'red', 'green' or 'blue'")),
             }
         })
     }
 
-    fn parse_game(line: &str) -> Vec<[SolT; 3]> {
+    fn parse_game(input: &str, line: &str) -> Result<Vec<[SolT; 3]>, ParseError> {
         line.split_once(": ")
-            .unwrap()
+            .ok_or_else(|| ParseError::after(input, line, "': '"))?
             .1
             .split("; ")
-            .map(parse_draw)
+            .map(|draw| parse_draw(input, draw))
             .collect()
     }
 
+    impl FromStr for PuzzleData {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.lines()
+                .map(|line| parse_game(s, line))
+                .collect::<Result<_, _>>()
+                .map(Self)
+        }
+    }
+
     impl<T: AsRef<str>> From<T> for PuzzleData {
         fn from(s: T) -> Self {
-            PuzzleData(s.as_ref().lines().map(parse_game).collect())
+            s.as_ref().parse().unwrap()
         }
     }
 }
@@ -48,7 +62,7 @@ pub mod input {
 
 // tag::parse[]
 pub fn parse_input() -> InputType {
-    read_to_string("../../../inputs/input02").unwrap().into()
+    Day::read_input(None).unwrap().into()
 }
 // end::parse[]
 
@@ -81,6 +95,32 @@ pub fn star_2(PuzzleData(games): &PuzzleData) -> SolT {
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData;
+    type Answer1 = SolT;
+    type Answer2 = SolT;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -106,6 +146,16 @@ Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
         assert_eq!(expected, games);
     }
 
+    #[test]
+    pub fn test_parse_error() {
+        assert_eq!(
+            Some(ParseError::new(2, 19, "'red', 'green' or 'blue'")),
+            "Game 1: 3 blue\nGame 2: 1 blue, 2 grey"
+                .parse::<PuzzleData>()
+                .err()
+        );
+    }
+
     #[test]
     pub fn test_star_1() {
         assert_eq!(8, star_1(&CONTENT.into()));
@@ -1,10 +1,11 @@
 use mr_kaffee_2023_02::*;
+use mr_kaffee_utils::{inputs::path_from_args, solution::Solution};
 use std::time::Instant;
 
 fn main() {
     let t = Instant::now();
 
-    let input = parse_input();
+    let input = input::PuzzleData::from(Day::read_input(path_from_args().as_deref()).unwrap());
 
     let t_1 = Instant::now();
     let sol_1 = star_1(&input);
@@ -1,6 +1,6 @@
 use input::PuzzleData;
 use mr_kaffee_utils::grids::Grid;
-use std::fs::read_to_string;
+use mr_kaffee_utils::{parse::ParseError, solution::Solution};
 use std::{
     collections::HashMap,
     iter::{once, successors},
@@ -15,19 +15,41 @@ pub type SolT = usize;
 
 // tag::input[]
 pub mod input {
-    use mr_kaffee_utils::grids::{Grid, MakeGrid};
+    use mr_kaffee_utils::{
+        grids::{Grid, MakeGrid},
+        parse::{check_grid, ParseError},
+    };
+    use std::str::FromStr;
 
     pub struct PuzzleData(pub Grid);
 
+    impl PuzzleData {
+        /// Parse the input, reporting anything that is not a rectangular grid of
+        /// digits, '.' and symbols as error
+        pub fn try_parse<T: AsRef<[u8]> + ?Sized>(s: &T) -> Result<Self, ParseError> {
+            let data = s.as_ref();
+            check_grid(data, |b| b.is_ascii_graphic(), "digit, '.' or symbol")?;
+            Ok(Self(data.make_grid(Some(b'.'))))
+        }
+    }
+
+    impl FromStr for PuzzleData {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Self::try_parse(s)
+        }
+    }
+
     impl<T: AsRef<[u8]>> From<T> for PuzzleData {
         fn from(value: T) -> Self {
-            PuzzleData(value.make_grid(Some(b'.')))
+            Self::try_parse(&value).unwrap()
         }
     }
 }
 
 pub fn parse_input() -> PuzzleData {
-    read_to_string("../../../inputs/input03").unwrap().into()
+    Day::read_input(None).unwrap().into()
 }
 // end::input[]
 
@@ -37,7 +59,7 @@ pub fn star_1(PuzzleData(grid): &PuzzleData) -> SolT {
         .filter(|(_, pos, len)| {
             run_around(grid.to_col_row(*pos), *len)
                 .map(|pos| grid[pos])
-                .any(|b: u8| b != b'.' && !(b'0'..=b'9').contains(&b))
+                .any(|b: u8| b != b'.' && !b.is_ascii_digit())
         })
         .map(|(value, _, _)| value)
         .sum()
@@ -86,11 +108,11 @@ pub fn numbers(grid: &Grid) -> impl Iterator<Item = (SolT, usize, usize)> + '_ {
 pub fn next_number(grid: &Grid, offset: usize) -> Option<(SolT, usize, usize)> {
     grid.data()[offset..]
         .iter()
-        .position(|b| (b'0'..=b'9').contains(b))
+        .position(|b: &u8| b.is_ascii_digit())
         .map(|pos| {
             grid.data()[offset + pos..]
                 .iter()
-                .take_while(|b| (b'0'..=b'9').contains(b))
+                .take_while(|b: &&u8| b.is_ascii_digit())
                 .fold((0, offset + pos, 0), |(val, pos, len), &b| {
                     (10 * val + (b - b'0') as SolT, pos, len + 1)
                 })
@@ -98,6 +120,32 @@ pub fn next_number(grid: &Grid, offset: usize) -> Option<(SolT, usize, usize)> {
 }
 // end::numbers[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData;
+    type Answer1 = SolT;
+    type Answer2 = SolT;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
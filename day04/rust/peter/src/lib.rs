@@ -1,5 +1,8 @@
+use mr_kaffee_utils::{
+    parse::{parse_token, ParseError},
+    solution::Solution,
+};
 use std::collections::HashSet;
-use std::fs::read_to_string;
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/04";
@@ -9,7 +12,26 @@ pub type SolT = usize;
 // end::prelude[]
 
 pub fn parse_input() -> String {
-    read_to_string("../../../inputs/input04").unwrap()
+    Day::read_input(None).unwrap()
+}
+
+/// Check that every line is a card `Card <id>: <numbers> | <numbers>`
+pub fn check_input(s: &str) -> Result<(), ParseError> {
+    for line in s.lines() {
+        let (_, tail) = line
+            .split_once(':')
+            .ok_or_else(|| ParseError::after(s, line, "':'"))?;
+        let (lhs, rhs) = tail
+            .split_once('|')
+            .ok_or_else(|| ParseError::after(s, line, "'|'"))?;
+        for value in lhs
+            .split_ascii_whitespace()
+            .chain(rhs.split_ascii_whitespace())
+        {
+            parse_token::<u32>(s, value, "number")?;
+        }
+    }
+    Ok(())
 }
 
 // tag::star_1[]
@@ -54,6 +76,33 @@ pub fn star_2(data: &str) -> SolT {
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = &'a str;
+    type Answer1 = SolT;
+    type Answer2 = SolT;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        check_input(input)?;
+        Ok(input)
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -84,5 +133,18 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
     pub fn test_star_2() {
         assert_eq!(30, star_2(CONTENT));
     }
+
+    #[test]
+    pub fn test_parse_error() {
+        assert_eq!(Ok(()), check_input(CONTENT));
+        assert_eq!(
+            Err(ParseError::new(2, 17, "'|'")),
+            check_input("Card 1: 41 | 83\nCard 2: 13 32 20")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 14, "number")),
+            check_input("Card 1: 41 | x3")
+        );
+    }
 }
 // end::tests[]
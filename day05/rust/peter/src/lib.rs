@@ -1,5 +1,10 @@
 use input::*;
-use std::{collections::HashMap, fs::read_to_string, iter::successors};
+use mr_kaffee_utils::{
+    intervals::{Interval, IntervalSet},
+    parse::ParseError,
+    solution::Solution,
+};
+use std::{collections::HashMap, iter::successors};
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/05";
@@ -12,7 +17,8 @@ type Map = (SolT, SolT, SolT);
 
 pub mod input {
     use crate::*;
-    use std::collections::HashMap;
+    use mr_kaffee_utils::parse::{parse_token, ParseError};
+    use std::{collections::HashMap, str::FromStr};
 
     #[derive(Debug)]
     pub struct PuzzleData {
@@ -20,51 +26,69 @@ pub mod input {
         pub maps: HashMap<String, (String, Vec<Map>)>,
     }
 
-    fn parse_ranges(ranges: &str) -> Map {
-        let mut numbers = ranges.split_ascii_whitespace().map(|r| r.parse().unwrap());
-        (
-            numbers.next().unwrap(),
-            numbers.next().unwrap(),
-            numbers.next().unwrap(),
-        )
+    fn parse_ranges(input: &str, line: &str) -> Result<Map, ParseError> {
+        let mut numbers = line
+            .split_ascii_whitespace()
+            .map(|n| parse_token(input, n, "number"));
+        let mut next = || {
+            numbers
+                .next()
+                .unwrap_or_else(|| Err(ParseError::after(input, line, "three numbers")))
+        };
+        Ok((next()?, next()?, next()?))
     }
 
-    fn parse_group(group: &str) -> (String, (String, Vec<Map>)) {
+    fn parse_group(input: &str, group: &str) -> Result<(String, (String, Vec<Map>)), ParseError> {
         let mut lines = group.lines();
 
-        let mut header = lines.next().unwrap().split(&['-', ' ']);
-        let (source, destination) = (
-            header.next().unwrap().to_string(),
-            header.nth(1).unwrap().to_string(),
-        );
+        let header = lines.next().unwrap_or(group);
+        let (source, destination) = header
+            .strip_suffix(" map:")
+            .and_then(|header| header.split_once("-to-"))
+            .ok_or_else(|| ParseError::at(input, header, "'<source>-to-<destination> map:'"))?;
+
+        Ok((
+            source.to_string(),
+            (
+                destination.to_string(),
+                lines
+                    .map(|line| parse_ranges(input, line))
+                    .collect::<Result<_, _>>()?,
+            ),
+        ))
+    }
 
-        (source, (destination, lines.map(parse_ranges).collect()))
+    impl FromStr for PuzzleData {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let mut groups = s.split("\n\n");
+            let seeds = groups
+                .next()
+                .and_then(|group| group.strip_prefix("seeds: "))
+                .ok_or_else(|| ParseError::at(s, s, "'seeds: '"))?;
+            Ok(Self {
+                seeds: seeds
+                    .split_ascii_whitespace()
+                    .map(|seed| parse_token(s, seed, "seed"))
+                    .collect::<Result<_, _>>()?,
+                maps: groups
+                    .map(|group| parse_group(s, group))
+                    .collect::<Result<_, _>>()?,
+            })
+        }
     }
 
     impl<T: AsRef<str>> From<T> for PuzzleData {
         fn from(s: T) -> Self {
-            let mut groups = s.as_ref().split("\n\n");
-            Self {
-                seeds: groups
-                    .next()
-                    .and_then(|group| group.strip_prefix("seeds: "))
-                    .and_then(|seeds| {
-                        seeds
-                            .split_ascii_whitespace()
-                            .map(|seed| seed.parse())
-                            .collect::<Result<Vec<_>, _>>()
-                            .ok()
-                    })
-                    .unwrap(),
-                maps: groups.map(parse_group).collect(),
-            }
+            s.as_ref().parse().unwrap()
         }
     }
 }
 // end::input[]
 
 pub fn parse_input() -> PuzzleData {
-    read_to_string("../../../inputs/input05").unwrap().into()
+    Day::read_input(None).unwrap().into()
 }
 
 // tag::star_1[]
@@ -109,36 +133,26 @@ pub fn star_1(data: &PuzzleData) -> SolT {
 // end::star_1[]
 
 // tag::star_2[]
-fn step_2(ranges: &[(SolT, SolT)], map: &[Map]) -> Vec<(SolT, SolT)> {
-    ranges.iter().fold(Vec::new(), |mut result, &rng| {
-        let mut rng = Some(rng);
-        while let Some((rng_0, rng_n)) = rng {
-            // find a map range that overlaps the current range
-            // return the transformed contained part and optionally
-            // a residual untransformed range
-            let (transformed, residual) = map
-                .iter()
-                .map(|&(dst_0, src_0, len)| (dst_0 - src_0, src_0, src_0 + len))
-                .find(|&(_, src_0, src_n)| {
-                    (src_0..src_n).contains(&rng_0) || (src_0..src_n).contains(&(rng_n - 1))
-                })
-                .map(|(dlt, src_0, src_n)| {
-                    match (
-                        (src_0..src_n).contains(&rng_0),
-                        (src_0..src_n).contains(&(rng_n - 1)),
-                    ) {
-                        (true, false) => ((rng_0 + dlt, src_n + dlt), Some((src_n, rng_n))),
-                        (false, true) => ((src_0 + dlt, rng_n + dlt), Some((rng_0, src_0))),
-                        _ => ((rng_0 + dlt, rng_n + dlt), None),
-                    }
-                })
-                .unwrap_or(((rng_0, rng_n), None));
-
-            result.push(transformed);
-            rng = residual;
-        }
-        result
-    })
+fn step_2(ranges: &[Interval<SolT>], map: &[Map]) -> Vec<Interval<SolT>> {
+    ranges
+        .iter()
+        .flat_map(|&rng| {
+            // transform the parts of the range covered by map ranges, keep the
+            // residual parts untransformed
+            let mut residual = IntervalSet::from(rng);
+            let mut transformed = Vec::new();
+            for &(dst_0, src_0, len) in map {
+                let src = Interval::from_len(src_0, len);
+                let covered = rng.intersect(&src);
+                if !covered.is_empty() {
+                    transformed.push(covered.shift(dst_0 - src_0));
+                    residual.remove(src);
+                }
+            }
+            transformed.extend(residual);
+            transformed
+        })
+        .collect()
 }
 
 pub fn star_2(data: &PuzzleData) -> SolT {
@@ -148,14 +162,40 @@ pub fn star_2(data: &PuzzleData) -> SolT {
             .iter()
             .step_by(2)
             .zip(data.seeds.iter().skip(1).step_by(2))
-            .map(|(&from, &len)| (from, from + len))
+            .map(|(&from, &len)| Interval::from_len(from, len))
             .collect::<Vec<_>>(),
         step_2,
-        |rng| rng.into_iter().map(|(start, _)| start).min(),
+        |rng| rng.into_iter().map(|rng| rng.start).min(),
     )
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData;
+    type Answer1 = SolT;
+    type Answer2 = SolT;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -196,6 +236,22 @@ humidity-to-location map:
 56 93 4
 "#;
 
+    #[test]
+    pub fn test_parse_error() {
+        assert_eq!(
+            Some(ParseError::new(5, 4, "number")),
+            "seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 5x 48"
+                .parse::<PuzzleData>()
+                .err()
+        );
+        assert_eq!(
+            Some(ParseError::new(4, 6, "three numbers")),
+            "seeds: 79 14\n\nseed-to-soil map:\n50 98\n"
+                .parse::<PuzzleData>()
+                .err()
+        );
+    }
+
     #[test]
     pub fn test_from() {
         let data = PuzzleData::from(CONTENT);
@@ -228,5 +284,22 @@ humidity-to-location map:
     pub fn test_star_2() {
         assert_eq!(46, star_2(&CONTENT.into()));
     }
+
+    #[test]
+    pub fn test_step_2() {
+        // map range strictly inside and overlapping both ends
+        let map = [(100, 5, 2), (200, 8, 4)];
+        let mut ranges = step_2(&[Interval::new(0, 10)], &map);
+        ranges.sort_unstable_by_key(|rng| rng.start);
+        assert_eq!(
+            vec![
+                Interval::new(0, 5),
+                Interval::new(7, 8),
+                Interval::new(100, 102),
+                Interval::new(200, 202)
+            ],
+            ranges
+        );
+    }
 }
 // end::tests[]
@@ -1,4 +1,8 @@
-use std::{fs::read_to_string, iter::successors};
+use mr_kaffee_utils::{
+    parse::{parse_token, ParseError},
+    solution::Solution,
+};
+use std::iter::successors;
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/06";
@@ -7,7 +11,44 @@ pub type SolT = i64;
 // end::prelude[]
 
 pub fn parse_input() -> String {
-    read_to_string("../../../inputs/input06").unwrap()
+    Day::read_input(None).unwrap()
+}
+
+/// Check that the input consists of a line with times and a line with
+/// distances, each made of a label and the same number of numbers
+pub fn check_input(s: &str) -> Result<(), ParseError> {
+    let mut lines = s.lines();
+    let mut counts = [0; 2];
+    for (count, expected) in counts
+        .iter_mut()
+        .zip(["line with times", "line with distances"])
+    {
+        let line = lines
+            .next()
+            .ok_or_else(|| ParseError::after(s, s, expected))?;
+        let mut tokens = line.split_ascii_whitespace();
+        tokens
+            .next()
+            .filter(|label| label.ends_with(':'))
+            .ok_or_else(|| ParseError::at(s, line, "label"))?;
+        for token in tokens {
+            parse_token::<SolT>(s, token, "number")?;
+            *count += 1;
+        }
+    }
+
+    if counts[0] != counts[1] {
+        let line = s.lines().nth(1).unwrap();
+        return Err(ParseError::after(
+            s,
+            line,
+            format!("{} distances", counts[0]),
+        ));
+    }
+    match lines.next() {
+        Some(line) => Err(ParseError::at(s, line, "end of input")),
+        None => Ok(()),
+    }
 }
 
 // tag::star_1[]
@@ -90,6 +131,33 @@ pub fn star_2(s: &str) -> SolT {
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = &'a str;
+    type Answer1 = SolT;
+    type Answer2 = SolT;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        check_input(input)?;
+        Ok(input)
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -101,12 +169,29 @@ Distance:  9  40  200
 
     #[test]
     pub fn test_star_1() {
-        assert_eq!(288, star_1(&CONTENT));
+        assert_eq!(288, star_1(CONTENT));
     }
 
     #[test]
     pub fn test_star_2() {
-        assert_eq!(71_503, star_2(&CONTENT));
+        assert_eq!(71_503, star_2(CONTENT));
+    }
+
+    #[test]
+    pub fn test_parse_error() {
+        assert_eq!(Ok(()), check_input(CONTENT));
+        assert_eq!(
+            Err(ParseError::new(2, 1, "line with distances")),
+            check_input("Time: 7 15\n")
+        );
+        assert_eq!(
+            Err(ParseError::new(2, 13, "number")),
+            check_input("Time: 7 15\nDistance: 9 x")
+        );
+        assert_eq!(
+            Err(ParseError::new(2, 12, "2 distances")),
+            check_input("Time: 7 15\nDistance: 9")
+        );
     }
 }
 // end::tests[]
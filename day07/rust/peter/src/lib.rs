@@ -1,5 +1,5 @@
 use input::*;
-use std::fs::read_to_string;
+use mr_kaffee_utils::{parse::ParseError, solution::Solution};
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/07";
@@ -10,38 +10,50 @@ pub type SolT = usize;
 // tag::input[]
 pub mod input {
     use crate::SolT;
+    use mr_kaffee_utils::parse::{parse_token, ParseError};
+    use std::str::FromStr;
 
     #[derive(Debug, PartialEq, Eq)]
     pub struct PuzzleData(pub Vec<([u8; 5], SolT)>);
 
+    fn parse_hand(input: &str, line: &str) -> Result<([u8; 5], SolT), ParseError> {
+        let (cards, bid) = line
+            .split_once(' ')
+            .ok_or_else(|| ParseError::after(input, line, "' '"))?;
+        if let Some(pos) = cards
+            .bytes()
+            .position(|card| !b"23456789TJQKA".contains(&card))
+        {
+            return Err(ParseError::at(input, &cards[pos..], "card label"));
+        }
+        let cards = cards
+            .as_bytes()
+            .try_into()
+            .map_err(|_| ParseError::at(input, cards, "five cards"))?;
+        Ok((cards, parse_token(input, bid, "bid")?))
+    }
+
+    impl FromStr for PuzzleData {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.lines()
+                .map(|line| parse_hand(s, line))
+                .collect::<Result<_, _>>()
+                .map(Self)
+        }
+    }
+
     impl<T: AsRef<str>> From<T> for PuzzleData {
         fn from(value: T) -> Self {
-            Self(
-                value
-                    .as_ref()
-                    .lines()
-                    .filter_map(|line| line.split_once(' '))
-                    .map(|(cards, bid)| {
-                        (
-                            cards.bytes().take(5).enumerate().fold(
-                                [0; 5],
-                                |mut cards, (pos, card)| {
-                                    cards[pos] = card;
-                                    cards
-                                },
-                            ),
-                            bid.parse().unwrap(),
-                        )
-                    })
-                    .collect(),
-            )
+            value.as_ref().parse().unwrap()
         }
     }
 }
 // end::input[]
 
 pub fn parse_input() -> PuzzleData {
-    read_to_string("../../../inputs/input07").unwrap().into()
+    Day::read_input(None).unwrap().into()
 }
 
 // tag::star_1[]
@@ -123,6 +135,32 @@ pub fn star_2(input: &PuzzleData) -> SolT {
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData;
+    type Answer1 = SolT;
+    type Answer2 = SolT;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -151,6 +189,18 @@ QQQJA 483
         println!("{data:?}");
     }
 
+    #[test]
+    pub fn test_parse_error() {
+        assert_eq!(
+            Some(ParseError::new(2, 3, "card label")),
+            "32T3K 765\nT5x5J 684".parse::<PuzzleData>().err()
+        );
+        assert_eq!(
+            Some(ParseError::new(2, 1, "five cards")),
+            "32T3K 765\nT55J 684".parse::<PuzzleData>().err()
+        );
+    }
+
     #[test]
     pub fn test_star_1() {
         assert_eq!(6440, star_1(&CONTENT.into()));
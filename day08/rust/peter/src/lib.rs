@@ -1,6 +1,8 @@
 use input::*;
-use mr_kaffee_utils::euclid::gcd;
-use std::{fs::read_to_string, iter::successors};
+use mr_kaffee_utils::euclid::crt;
+use mr_kaffee_utils::iterators::{find_cycle, CycleStates};
+use mr_kaffee_utils::{parse::ParseError, solution::Solution};
+use std::iter::successors;
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/08";
@@ -9,12 +11,13 @@ pub type SolT = usize;
 pub type InputT<'a> = PuzzleData<'a>;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input08").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
 // tag::input[]
 pub mod input {
+    use mr_kaffee_utils::parse::ParseError;
     use std::collections::{hash_map::Entry, HashMap};
 
     #[derive(Debug, PartialEq, Eq)]
@@ -24,42 +27,56 @@ pub mod input {
         pub HashMap<&'a str, usize>,
     );
 
-    impl<'a, T> From<&'a T> for PuzzleData<'a>
-    where
-        T: AsRef<str> + 'a + ?Sized,
-    {
-        fn from(s: &'a T) -> Self {
-            let mut lines = s.as_ref().lines();
-
-            let dirs = lines.next().unwrap().as_bytes();
-            let (map, indices) = lines
-                .skip(1)
-                .filter_map(|line| line.split_once(" = "))
-                .map(|(key, values)| {
-                    (
-                        key,
+    impl<'a> PuzzleData<'a> {
+        /// Parse the input, reporting malformed directions or nodes as error
+        pub fn try_parse<T>(s: &'a T) -> Result<Self, ParseError>
+        where
+            T: AsRef<str> + 'a + ?Sized,
+        {
+            let s = s.as_ref();
+            let mut lines = s.lines();
+
+            let dirs = lines.next().unwrap_or(s);
+            if let Some(pos) = dirs.bytes().position(|b| b != b'L' && b != b'R') {
+                return Err(ParseError::at(s, &dirs[pos..], "'L' or 'R'"));
+            } else if dirs.is_empty() {
+                return Err(ParseError::at(s, dirs, "'L' or 'R'"));
+            }
+
+            let mut map = Vec::<(&str, Option<(usize, usize)>)>::new();
+            let mut indices = HashMap::<&str, usize>::new();
+            for line in lines.skip(1) {
+                let (src, (left, right)) = line
+                    .split_once(" = ")
+                    .and_then(|(key, values)| {
                         values
                             .strip_prefix('(')
                             .and_then(|values| values.strip_suffix(')'))
                             .and_then(|values| values.split_once(", "))
-                            .unwrap(),
-                    )
-                })
-                .fold(
-                    (
-                        Vec::<(&str, Option<(usize, usize)>)>::new(),
-                        HashMap::<&str, usize>::new(),
-                    ),
-                    |(mut map, mut indices), (src, (left, right))| {
-                        let idx_src = find_or_insert(&mut map, &mut indices, src);
-                        let idx_left = find_or_insert(&mut map, &mut indices, left);
-                        let idx_right = find_or_insert(&mut map, &mut indices, right);
-                        map[idx_src].1 = Some((idx_left, idx_right));
-                        (map, indices)
-                    },
-                );
+                            .map(|values| (key, values))
+                    })
+                    .ok_or_else(|| ParseError::at(s, line, "'<node> = (<left>, <right>)'"))?;
+                let idx_src = find_or_insert(&mut map, &mut indices, src);
+                let idx_left = find_or_insert(&mut map, &mut indices, left);
+                let idx_right = find_or_insert(&mut map, &mut indices, right);
+                map[idx_src].1 = Some((idx_left, idx_right));
+            }
 
-            Self(dirs, map, indices)
+            // labels are stored as first seen, so errors point to the first reference
+            if let Some((label, _)) = map.iter().find(|(_, succ)| succ.is_none()) {
+                return Err(ParseError::at(s, label, "node with definition"));
+            }
+
+            Ok(Self(dirs.as_bytes(), map, indices))
+        }
+    }
+
+    impl<'a, T> From<&'a T> for PuzzleData<'a>
+    where
+        T: AsRef<str> + 'a + ?Sized,
+    {
+        fn from(s: &'a T) -> Self {
+            Self::try_parse(s).unwrap()
         }
     }
 
@@ -102,28 +119,132 @@ pub fn star_1(PuzzleData(dirs, map, indices): &PuzzleData) -> SolT {
 // end::star_1[]
 
 // tag::star_2[]
+/// The steps at which a walker is on a node ending with `Z`
+///
+/// Hits before the walker enters its cycle are listed in `tail`, hits in the
+/// cycle's first period in `hits`. They repeat every `period` steps.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Walk {
+    pub prefix: usize,
+    pub period: usize,
+    pub tail: Vec<usize>,
+    pub hits: Vec<usize>,
+}
+
+impl Walk {
+    /// Analyse the walk from `node`, which is a cycle in the space of nodes
+    /// and indices into the directions
+    pub fn new(dirs: &[u8], map: &[(&str, Option<(usize, usize)>)], node: usize) -> Self {
+        let CycleStates { cycle, states } = find_cycle((0, node), |&(k, node)| {
+            let (left, right) = map[node].1.expect("node without successors");
+            match dirs[k] {
+                b'L' => ((k + 1) % dirs.len(), left),
+                _ => ((k + 1) % dirs.len(), right),
+            }
+        });
+
+        let (tail, hits) = states
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, node))| map[*node].0.ends_with('Z'))
+            .map(|(step, _)| step)
+            .partition(|&step| step < cycle.prefix);
+
+        Self {
+            prefix: cycle.prefix,
+            period: cycle.period,
+            tail,
+            hits,
+        }
+    }
+
+    pub fn is_hit(&self, step: usize) -> bool {
+        if step < self.prefix {
+            self.tail.contains(&step)
+        } else {
+            let step = self.prefix + (step - self.prefix) % self.period;
+            self.hits.contains(&step)
+        }
+    }
+}
+
+/// The first step at which all walkers are on a hit, `None` if there is no
+/// such step or no walkers at all
+pub fn first_common_hit(walks: &[Walk]) -> Option<usize> {
+    let prefix = walks.iter().map(|walk| walk.prefix).max()?;
+
+    // before all walkers are in their cycles, check explicitly
+    if let Some(step) = (0..prefix).find(|&step| walks.iter().all(|walk| walk.is_hit(step))) {
+        return Some(step);
+    }
+
+    // afterwards, combine the hits of all walkers with the CRT
+    let solutions = walks.iter().fold(vec![(0, 1)], |solutions, walk| {
+        let mut solutions = solutions
+            .iter()
+            .flat_map(|&congruence| {
+                walk.hits
+                    .iter()
+                    .filter_map(move |&hit| crt([congruence, (hit as _, walk.period as _)]))
+            })
+            .collect::<Vec<_>>();
+        solutions.sort_unstable();
+        solutions.dedup();
+        solutions
+    });
+
+    // smallest solutions not before prefix
+    let prefix = prefix as i64;
+    solutions
+        .into_iter()
+        .map(|(x, m)| {
+            if x >= prefix {
+                x
+            } else {
+                x + (prefix - x + m - 1) / m * m
+            }
+        })
+        .min()
+        .map(|step| step as _)
+}
+
 pub fn star_2(PuzzleData(dirs, map, _): &PuzzleData) -> SolT {
-    map.iter()
+    let walks = map
+        .iter()
         .enumerate()
         .filter(|(_, (key, _))| key.ends_with('A'))
-        .map(|(pos, _)| {
-            let mut it = map_iter(dirs, map, pos)
-                .step_by(dirs.len()) // only find result that used all dirs
-                .filter(|(_, node)| map[*node].0.ends_with('Z'));
-            let (steps_0, node_0) = it.next().unwrap();
-            if cfg!(feature = "check-periodicity") {
-                // the solution assumes periodicity, so let's check it
-                let (steps_1, node_1) = it.next().unwrap();
-                if steps_1 != 2 * steps_0 || node_1 != node_0 {
-                    panic!("Periodicity assumption not satisfied!");
-                }
-            }
-            steps_0
-        })
-        .fold(1, |result, steps| result * steps / gcd(result, steps))
+        .map(|(pos, _)| Walk::new(dirs, map, pos))
+        .collect::<Vec<_>>();
+    first_common_hit(&walks).expect("No solution")
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData<'a>;
+    type Answer1 = SolT;
+    type Answer2 = SolT;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        PuzzleData::try_parse(input)
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -156,6 +277,22 @@ ZZZ = (ZZZ, ZZZ)
         );
     }
 
+    #[test]
+    pub fn test_parse_error() {
+        assert_eq!(
+            Some(ParseError::new(1, 3, "'L' or 'R'")),
+            PuzzleData::try_parse("LRX\n\nAAA = (AAA, AAA)").err()
+        );
+        assert_eq!(
+            Some(ParseError::new(3, 1, "'<node> = (<left>, <right>)'")),
+            PuzzleData::try_parse("LR\n\nAAA = AAA, AAA").err()
+        );
+        assert_eq!(
+            Some(ParseError::new(3, 13, "node with definition")),
+            PuzzleData::try_parse("LR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, BBB)").err()
+        );
+    }
+
     #[test]
     pub fn test_star_1() {
         assert_eq!(6, star_1(&CONTENT.into()));
@@ -178,6 +315,67 @@ XXX = (XXX, XXX)
         assert_eq!(6, star_2(&CONTENT_2.into()));
     }
 
+    /// Walkers with hits before their cycles and cycles not starting at zero
+    const CONTENT_3: &str = r#"L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11C, 11C)
+11C = (12Z, 12Z)
+12Z = (11B, 11B)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22C, 22C)
+22C = (22D, 22D)
+22D = (22B, 22B)
+"#;
+
+    const CONTENT_4: &str = r#"L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11C, 11C)
+11C = (12Z, 12Z)
+12Z = (11B, 11B)
+33A = (33Z, 33Z)
+33Z = (33B, 33B)
+33B = (33B, 33B)
+"#;
+
+    #[test]
+    pub fn test_walk() {
+        let PuzzleData(dirs, map, indices) = CONTENT_3.into();
+        let walk = Walk::new(dirs, &map, indices["11A"]);
+        assert_eq!(
+            Walk {
+                prefix: 2,
+                period: 3,
+                tail: vec![1],
+                hits: vec![4]
+            },
+            walk
+        );
+        assert_eq!(
+            vec![1, 4, 7, 10],
+            (0..12)
+                .filter(|&step| walk.is_hit(step))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    pub fn test_star_2_general() {
+        assert_eq!(10, star_2(&CONTENT_3.into()));
+        assert_eq!(1, star_2(&CONTENT_4.into()));
+
+        let PuzzleData(dirs, map, indices) = CONTENT_4.into();
+        let walks = [
+            Walk::new(dirs, &map, indices["33A"]),
+            Walk::new(dirs, &map, indices["11Z"]),
+        ];
+        assert_eq!(None, first_common_hit(&walks));
+    }
+
     pub fn do_understand(data: &str, n: usize) {
         let PuzzleData(dirs, map, _) = data.into();
         for index in map
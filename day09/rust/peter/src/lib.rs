@@ -1,5 +1,5 @@
 use input::*;
-use std::fs::read_to_string;
+use mr_kaffee_utils::{parse::ParseError, solution::Solution};
 use std::iter::successors;
 
 // tag::prelude[]
@@ -9,33 +9,40 @@ pub type SolT = i64;
 pub type InputT = PuzzleData;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input09").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
 // tag::input[]
 pub mod input {
     use crate::SolT;
+    use mr_kaffee_utils::parse::{parse_token, ParseError};
+    use std::str::FromStr;
 
     #[derive(Debug, PartialEq, Eq)]
     pub struct PuzzleData(pub Vec<Vec<SolT>>);
 
+    impl FromStr for PuzzleData {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.lines()
+                .map(|line| {
+                    line.split_ascii_whitespace()
+                        .map(|value| parse_token(s, value, "number"))
+                        .collect::<Result<_, _>>()
+                })
+                .collect::<Result<_, _>>()
+                .map(Self)
+        }
+    }
+
     impl<T> From<T> for PuzzleData
     where
         T: AsRef<str>,
     {
         fn from(s: T) -> Self {
-            Self(
-                s.as_ref()
-                    .lines()
-                    .map(|line| {
-                        line.split_ascii_whitespace()
-                            .map(str::parse)
-                            .collect::<Result<_, _>>()
-                    })
-                    .collect::<Result<_, _>>()
-                    .unwrap(),
-            )
+            s.as_ref().parse().unwrap()
         }
     }
 }
@@ -120,6 +127,32 @@ pub fn star_2(PuzzleData(values): &PuzzleData) -> SolT {
 
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData;
+    type Answer1 = SolT;
+    type Answer2 = SolT;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -1,6 +1,6 @@
 use input::*;
 use mr_kaffee_utils::grids::Grid;
-use std::fs::read_to_string;
+use mr_kaffee_utils::{parse::ParseError, solution::Solution};
 use std::iter::successors;
 
 // tag::prelude[]
@@ -10,23 +10,45 @@ pub type SolT = usize;
 pub type InputT = PuzzleData;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input10").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
 // tag::input[]
 pub mod input {
-    use mr_kaffee_utils::grids::{Grid, MakeGrid};
+    use mr_kaffee_utils::{
+        grids::{Grid, MakeGrid},
+        parse::{check_grid, ParseError},
+    };
+    use std::str::FromStr;
 
     #[derive(Debug)]
     pub struct PuzzleData(pub Grid);
 
+    impl PuzzleData {
+        /// Parse the input, reporting anything that is not a rectangular grid of
+        /// the symbols '|-LJ7F.S' as error
+        pub fn try_parse<T: AsRef<[u8]> + ?Sized>(s: &T) -> Result<Self, ParseError> {
+            let data = s.as_ref();
+            check_grid(data, |b| b"|-LJ7F.S".contains(&b), "one of '|-LJ7F.S'")?;
+            Ok(Self(data.make_grid(Some(b'.'))))
+        }
+    }
+
+    impl FromStr for PuzzleData {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Self::try_parse(s)
+        }
+    }
+
     impl<T> From<T> for PuzzleData
     where
         T: AsRef<[u8]>,
     {
         fn from(s: T) -> Self {
-            Self(s.make_grid(Some(b'.')))
+            Self::try_parse(&s).unwrap()
         }
     }
 }
@@ -55,11 +77,13 @@ fn deduce_pipe(grid: &Grid, (col, row): (usize, usize)) -> u8 {
     }
 }
 
-pub fn find_loop(grid: &Grid) -> (SolT, Vec<bool>, u8) {
+/// Walk the loop starting at 'S', yielding the index of every element of the
+/// loop in order together with the pipe hidden below 'S'
+pub fn walk_loop(grid: &Grid) -> (impl Iterator<Item = usize> + '_, u8) {
     let start = grid.data().iter().position(|&b| b == b'S').unwrap();
     let start_pipe = deduce_pipe(grid, grid.to_col_row(start));
 
-    let (len, pipe_loop) = successors(Some((0, start)), |&(prev_idx, cur_idx)| {
+    let it = successors(Some((0, start)), move |&(prev_idx, cur_idx)| {
         let cur = match grid[cur_idx] {
             b'S' => start_pipe,
             b => b,
@@ -83,13 +107,17 @@ pub fn find_loop(grid: &Grid) -> (SolT, Vec<bool>, u8) {
             None
         }
     })
-    .fold(
-        (0, vec![false; grid.len()]),
-        |(len, mut pipe_loop), (_, idx)| {
-            pipe_loop[idx] = true;
-            (len + 1, pipe_loop)
-        },
-    );
+    .map(|(_, idx)| idx);
+
+    (it, start_pipe)
+}
+
+pub fn find_loop(grid: &Grid) -> (SolT, Vec<bool>, u8) {
+    let (it, start_pipe) = walk_loop(grid);
+    let (len, pipe_loop) = it.fold((0, vec![false; grid.len()]), |(len, mut pipe_loop), idx| {
+        pipe_loop[idx] = true;
+        (len + 1, pipe_loop)
+    });
 
     (len >> 1, pipe_loop, start_pipe)
 }
@@ -132,7 +160,7 @@ pub fn star_2(PuzzleData(grid): &PuzzleData) -> SolT {
         .count()
 }
 
-#[cfg(not(feature = "point-by-point"))]
+#[cfg(all(feature = "line-by-line", not(feature = "point-by-point")))]
 pub fn star_2(PuzzleData(grid): &PuzzleData) -> SolT {
     let (_, pipe_loop, start_pipe) = find_loop(grid);
 
@@ -158,8 +186,46 @@ pub fn star_2(PuzzleData(grid): &PuzzleData) -> SolT {
         })
         .sum()
 }
+
+#[cfg(not(any(feature = "point-by-point", feature = "line-by-line")))]
+pub fn star_2(PuzzleData(grid): &PuzzleData) -> SolT {
+    use mr_kaffee_utils::polygon::Polygon;
+
+    let (it, _) = walk_loop(grid);
+    let polygon = Polygon::new(it.map(|idx| {
+        let (col, row) = grid.to_col_row(idx);
+        (col as isize, row as isize)
+    }));
+    polygon.interior_points() as SolT
+}
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData;
+    type Answer1 = SolT;
+    type Answer2 = SolT;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -39,7 +39,7 @@ pub fn make_universe(w: usize, h: usize, r: usize) -> String {
     let mut rng = rand::thread_rng();
     indices.shuffle(&mut rng);
 
-    for &k in indices[0..(w * h + r - 1) / r].into_iter() {
+    for &k in indices[0..(w * h).div_ceil(r)].iter() {
         grid[k + k / w] = '#';
     }
 
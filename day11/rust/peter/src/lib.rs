@@ -1,5 +1,5 @@
 use input::*;
-use std::fs::read_to_string;
+use mr_kaffee_utils::{parse::ParseError, solution::Solution};
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/11";
@@ -8,12 +8,15 @@ pub type SolT = usize;
 pub type InputT = PuzzleData;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input11").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
 // tag::input[]
 pub mod input {
+    use mr_kaffee_utils::parse::{check_grid, ParseError};
+    use std::str::FromStr;
+
     #[derive(Debug, PartialEq, Eq, Default)]
     pub struct PuzzleData {
         pub galaxies: Vec<(usize, usize)>,
@@ -21,13 +24,12 @@ pub mod input {
         pub count_in_rows: Vec<usize>,
     }
 
-    impl<T> From<T> for PuzzleData
-    where
-        T: AsRef<str>,
-    {
-        fn from(s: T) -> Self {
-            s.as_ref()
-                .lines()
+    impl FromStr for PuzzleData {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            check_grid(s.as_bytes(), |b| b == b'#' || b == b'.', "'#' or '.'")?;
+            Ok(s.lines()
                 .enumerate()
                 .fold(PuzzleData::default(), |mut data, (row, line)| {
                     data.count_in_rows.push(0);
@@ -42,7 +44,16 @@ pub mod input {
                             data
                         },
                     )
-                })
+                }))
+        }
+    }
+
+    impl<T> From<T> for PuzzleData
+    where
+        T: AsRef<str>,
+    {
+        fn from(s: T) -> Self {
+            s.as_ref().parse().unwrap()
         }
     }
 }
@@ -90,6 +101,32 @@ pub fn star_2(data: &PuzzleData) -> SolT {
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData;
+    type Answer1 = SolT;
+    type Answer2 = SolT;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
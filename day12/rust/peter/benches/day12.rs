@@ -5,6 +5,11 @@ pub fn sol_benchmark(c: &mut Criterion) {
     let data = InputT::from(read_input());
     c.bench_function("star_1", |b| b.iter(|| star_1(black_box(&data))));
     c.bench_function("star_2", |b| b.iter(|| star_2(black_box(&data))));
+    for caching in [Caching::PerLine, Caching::Shared] {
+        c.bench_function(&format!("star_2 {:?}", caching), |b| {
+            b.iter(|| solve(black_box(&data), 5, caching))
+        });
+    }
 }
 
 criterion_group!(benches, sol_benchmark);
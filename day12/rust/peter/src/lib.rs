@@ -1,5 +1,9 @@
-use std::fs::read_to_string;
-use std::{collections::HashMap, iter::once};
+use mr_kaffee_utils::{
+    memo::{Memo, MemoStats},
+    parse::{parse_token, ParseError},
+    solution::Solution,
+};
+use std::iter::once;
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/12";
@@ -8,10 +12,27 @@ pub type SolT = usize;
 pub type InputT = String;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input12").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
+/// Check that every line is a row of springs made of `'.'`, `'#'` and `'?'`
+/// followed by comma separated group sizes
+pub fn check_input(s: &str) -> Result<(), ParseError> {
+    for line in s.lines() {
+        let (data, groups) = line
+            .split_once(' ')
+            .ok_or_else(|| ParseError::after(s, line, "' '"))?;
+        if let Some(pos) = data.bytes().position(|b| !b".#?".contains(&b)) {
+            return Err(ParseError::at(s, &data[pos..], "one of '.#?'"));
+        }
+        for group in groups.split(',') {
+            parse_token::<SolT>(s, group, "group size")?;
+        }
+    }
+    Ok(())
+}
+
 // tag::star_1[]
 pub fn parse_line(line: &str) -> (&[u8], Vec<SolT>) {
     line.split_once(' ')
@@ -28,15 +49,14 @@ pub fn parse_line(line: &str) -> (&[u8], Vec<SolT>) {
         .unwrap()
 }
 
-type Cache<'a> = HashMap<(&'a [u8], &'a [SolT]), SolT>;
+type Cache<'a> = Memo<(&'a [u8], &'a [SolT]), SolT>;
 
-fn check<'a>(data: &'a [u8], groups: &'a [SolT], cache: &mut Option<Cache<'a>>) -> SolT {
-    if let Some(&result) = cache.as_ref().and_then(|cache| cache.get(&(data, groups))) {
-        // return cached result
-        return result;
-    }
+fn check<'a>(data: &'a [u8], groups: &'a [SolT], cache: &mut Cache<'a>) -> SolT {
+    cache.memoize((data, groups), |cache| check_uncached(data, groups, cache))
+}
 
-    let result = if groups.is_empty() {
+fn check_uncached<'a>(data: &'a [u8], groups: &'a [SolT], cache: &mut Cache<'a>) -> SolT {
+    if groups.is_empty() {
         if data.iter().all(|&d| d != b'#') {
             1
         } else {
@@ -72,19 +92,13 @@ fn check<'a>(data: &'a [u8], groups: &'a [SolT], cache: &mut Option<Cache<'a>>)
             }
         }
         result
-    };
-
-    // cache and return result
-    cache
-        .as_mut()
-        .map(|cache| cache.insert((data, groups), result));
-    result
+    }
 }
 
 pub fn star_1(data: &str) -> SolT {
     data.lines()
         .map(parse_line)
-        .map(|(data, groups)| check(data, &groups, &mut None))
+        .map(|(data, groups)| check(data, &groups, &mut Cache::disabled()))
         .sum()
 }
 // end::star_1[]
@@ -92,14 +106,27 @@ pub fn star_1(data: &str) -> SolT {
 // tag::star_2[]
 const UNFOLDS: usize = 5;
 
-#[cfg(not(feature = "shared-cache"))]
-const CACHE_CAPACITY: usize = 1 << 10;
+/// How partial results are cached
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Caching {
+    /// No caching at all
+    Disabled,
+    /// A fresh cache for every line
+    PerLine,
+    /// One cache shared across all lines
+    Shared,
+}
 
-#[cfg(feature = "shared-cache")]
-const CACHE_CAPACITY: usize = 1 << 16;
+impl Caching {
+    pub const ALL: [Self; 3] = [Self::Disabled, Self::PerLine, Self::Shared];
 
-fn make_cache<'a>() -> Option<Cache<'a>> {
-    Some(Cache::with_capacity(CACHE_CAPACITY))
+    fn make_cache<'a>(self) -> Cache<'a> {
+        match self {
+            Self::Disabled => Cache::disabled(),
+            Self::PerLine => Cache::with_capacity(1 << 10),
+            Self::Shared => Cache::with_capacity(1 << 16),
+        }
+    }
 }
 
 pub fn data_iter(data: &str, unfolds: usize) -> impl Iterator<Item = (Vec<u8>, Vec<SolT>)> + '_ {
@@ -122,23 +149,55 @@ pub fn data_iter(data: &str, unfolds: usize) -> impl Iterator<Item = (Vec<u8>, V
     })
 }
 
-#[cfg(not(feature = "shared-cache"))]
-pub fn star_2(data: &str) -> SolT {
-    data_iter(data, UNFOLDS)
-        .map(|(data, groups)| check(&data, &groups, &mut make_cache()))
-        .sum()
+/// Sum of arrangements over all lines unfolded `unfolds` times using the
+/// given caching strategy, together with the cache statistics
+pub fn solve(data: &str, unfolds: usize, caching: Caching) -> (SolT, MemoStats) {
+    let data = data_iter(data, unfolds).collect::<Vec<_>>();
+    let mut cache = caching.make_cache();
+    let sum = data
+        .iter()
+        .map(|(data, groups)| {
+            if caching == Caching::PerLine {
+                cache.clear();
+            }
+            check(data, groups, &mut cache)
+        })
+        .sum();
+    (sum, cache.stats())
 }
 
-#[cfg(feature = "shared-cache")]
 pub fn star_2(data: &str) -> SolT {
-    let mut cache = make_cache();
-    let data = data_iter(data, UNFOLDS).collect::<Vec<_>>();
-    data.iter()
-        .map(|(data, groups)| check(&data, &groups, &mut cache))
-        .sum()
+    solve(data, UNFOLDS, Caching::PerLine).0
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = &'a str;
+    type Answer1 = SolT;
+    type Answer2 = SolT;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        check_input(input)?;
+        Ok(input)
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -154,34 +213,45 @@ mod tests {
 
     #[test]
     pub fn test_check() {
-        assert_eq!(1, check(".###.##.#...".as_bytes(), &[3, 2, 1], &mut None));
         assert_eq!(
-            0,
+            1,
             check(
                 ".###.##.#...".as_bytes(),
-                &[3, 2, 2],
-                &mut Some(Cache::default())
+                &[3, 2, 1],
+                &mut Cache::disabled()
             )
         );
         assert_eq!(
             0,
-            check(".###.##.#...".as_bytes(), &[3, 2, 1, 1], &mut None)
+            check(".###.##.#...".as_bytes(), &[3, 2, 2], &mut Cache::new())
         );
         assert_eq!(
-            1,
+            0,
             check(
-                ".###.##....#".as_bytes(),
-                &[3, 2, 1],
-                &mut Some(Cache::default())
+                ".###.##.#...".as_bytes(),
+                &[3, 2, 1, 1],
+                &mut Cache::disabled()
             )
         );
+        assert_eq!(
+            1,
+            check(".###.##....#".as_bytes(), &[3, 2, 1], &mut Cache::new())
+        );
 
         const EXP: &[SolT] = &[1, 4, 1, 1, 4, 10];
         for (k, (line, &exp)) in CONTENT.lines().zip(EXP.iter()).enumerate() {
             let (data, groups) = parse_line(line);
             assert_eq!(
                 exp,
-                check(data, &groups, &mut (k & 1 == 0).then_some(Cache::default())),
+                check(
+                    data,
+                    &groups,
+                    &mut if k & 1 == 0 {
+                        Cache::new()
+                    } else {
+                        Cache::disabled()
+                    }
+                ),
                 "{}",
                 line
             );
@@ -197,5 +267,37 @@ mod tests {
     pub fn test_star_2() {
         assert_eq!(525_152, star_2(CONTENT));
     }
+
+    #[test]
+    pub fn test_caching() {
+        let results = Caching::ALL.map(|caching| solve(CONTENT, UNFOLDS, caching));
+        for (sum, _) in results {
+            assert_eq!(525_152, sum);
+        }
+
+        let [(_, disabled), (_, per_line), (_, shared)] = results;
+        assert_eq!(0, disabled.hits);
+        assert_eq!(0, disabled.len);
+        assert!(per_line.hits > 0);
+        assert!(per_line.misses < disabled.misses);
+        // the shared cache is never cleared, so it holds at least as much as
+        // the per-line cache for its last line
+        assert!(shared.len >= per_line.len);
+        assert!(shared.misses <= per_line.misses);
+    }
+
+    #[test]
+    pub fn test_parse_error() {
+        assert_eq!(Ok(()), check_input(CONTENT));
+        assert_eq!(
+            Err(ParseError::new(2, 3, "one of '.#?'")),
+            check_input("#.# 1,1\n.?x 1")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 7, "group size")),
+            check_input("#.# 1,,1")
+        );
+        assert_eq!(Err(ParseError::new(1, 4, "' '")), check_input("#.#"));
+    }
 }
 // end::tests[]
@@ -1,10 +1,11 @@
 use mr_kaffee_2023_12::*;
+use mr_kaffee_utils::{inputs::path_from_args, solution::Solution};
 use std::time::Instant;
 
 fn main() {
     let t = Instant::now();
 
-    let input = read_input();
+    let input = Day::read_input(path_from_args().as_deref()).unwrap();
     let data = InputT::from(&input);
 
     let t_1 = Instant::now();
@@ -1,5 +1,9 @@
 use input::*;
-use std::fs::read_to_string;
+use mr_kaffee_utils::{
+    grids::{GridRef, View},
+    parse::ParseError,
+    solution::Solution,
+};
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/13";
@@ -8,77 +12,74 @@ pub type SolT = usize;
 pub type InputT<'a> = PuzzleData<'a>;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input13").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
 // tag::input[]
 pub mod input {
-    use crate::SolT;
+    use mr_kaffee_utils::{grids::GridRef, parse::ParseError};
 
     #[derive(Debug)]
-    pub struct PuzzleData<'a>(pub Vec<(&'a [u8], SolT)>);
+    pub struct PuzzleData<'a>(pub Vec<GridRef<'a>>);
+
+    impl<'a> PuzzleData<'a> {
+        /// Parse the input, reporting anything that is not a sequence of
+        /// rectangular grids of '#' and '.' separated by blank lines as error
+        pub fn try_parse<T>(s: &'a T) -> Result<Self, ParseError>
+        where
+            T: AsRef<str> + 'a + ?Sized,
+        {
+            let s = s.as_ref();
+            s.split("\n\n")
+                .map(|pattern| {
+                    GridRef::from_bytes(
+                        pattern.as_bytes(),
+                        |b| b == b'#' || b == b'.',
+                        "'#' or '.'",
+                    )
+                    .map_err(|err| err.within(s, pattern))
+                })
+                .collect::<Result<_, _>>()
+                .map(Self)
+        }
+    }
 
     impl<'a, T> From<&'a T> for PuzzleData<'a>
     where
         T: AsRef<str> + 'a + ?Sized,
     {
         fn from(s: &'a T) -> Self {
-            Self(
-                s.as_ref()
-                    .split("\n\n")
-                    .map(|pattern| {
-                        (
-                            pattern.as_bytes(),
-                            pattern
-                                .bytes()
-                                .position(|b| b == b'\n')
-                                .unwrap_or(pattern.len()),
-                        )
-                    })
-                    .collect(),
-            )
+            Self::try_parse(s).unwrap()
         }
     }
 }
 // end::input[]
 
 // tag::star_1[]
-pub enum ToIdx {
-    ColRow(SolT),
-    RowCol(SolT),
-}
-
-impl ToIdx {
-    pub fn idx(&self, x: SolT, y: SolT) -> SolT {
-        match self {
-            Self::ColRow(w) => x + y * (w + 1),
-            Self::RowCol(w) => y + x * (w + 1),
-        }
-    }
-}
-
-pub fn find_line(pattern: &[u8], d1: SolT, d2: SolT, idx: ToIdx) -> Option<SolT> {
+/// Find a vertical line of reflection, i.e., a line between two columns, in
+/// the pattern as seen through the view
+pub fn find_line(pattern: &View<u8, &[u8]>) -> Option<SolT> {
+    let (d1, d2) = (pattern.width(), pattern.height());
     (0..d1 - 1).find(move |&line| {
         (0..d2).all(|y| {
             (0..=line)
                 .rev()
                 .zip(line + 1..d1)
-                .all(|(x_a, x_b)| pattern[idx.idx(x_a, y)] == pattern[idx.idx(x_b, y)])
+                .all(|(x_a, x_b)| pattern[(x_a, y)] == pattern[(x_b, y)])
         })
     })
 }
 
-pub fn star<F>(data: &[(&[u8], SolT)], f: F) -> SolT
+pub fn star<F>(data: &[GridRef], f: F) -> SolT
 where
-    F: Fn(&[u8], SolT, SolT, ToIdx) -> Option<SolT>,
+    F: Fn(&View<u8, &[u8]>) -> Option<SolT>,
 {
     data.iter()
-        .filter_map(|&(pattern, w)| {
-            let h = (pattern.len() + 1) / (w + 1);
-            f(pattern, w, h, ToIdx::ColRow(w))
+        .filter_map(|pattern| {
+            f(&pattern.view())
                 .map(|line| line + 1)
-                .or_else(|| f(pattern, h, w, ToIdx::RowCol(w)).map(|line| (line + 1) * 100))
+                .or_else(|| f(&pattern.view().transpose()).map(|line| (line + 1) * 100))
         })
         .sum()
 }
@@ -89,7 +90,8 @@ pub fn star_1(PuzzleData(data): &PuzzleData) -> SolT {
 // end::star_1[]
 
 // tag::star_2[]
-pub fn find_line_with_smudge(pattern: &[u8], d1: SolT, d2: SolT, idx: ToIdx) -> Option<SolT> {
+pub fn find_line_with_smudge(pattern: &View<u8, &[u8]>) -> Option<SolT> {
+    let (d1, d2) = (pattern.width(), pattern.height());
     (0..d1 - 1).find(move |&line| {
         let mut sum = 0;
         for v in (0..d2).map(|y| {
@@ -97,7 +99,7 @@ pub fn find_line_with_smudge(pattern: &[u8], d1: SolT, d2: SolT, idx: ToIdx) ->
             for v in (0..=line)
                 .rev()
                 .zip(line + 1..d1)
-                .map(|(x_a, x_b)| pattern[idx.idx(x_a, y)] == pattern[idx.idx(x_b, y)])
+                .map(|(x_a, x_b)| pattern[(x_a, y)] == pattern[(x_b, y)])
             {
                 sum += if v { 0 } else { 1 };
                 if sum > 1 {
@@ -120,6 +122,32 @@ pub fn star_2(PuzzleData(data): &PuzzleData) -> SolT {
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData<'a>;
+    type Answer1 = SolT;
+    type Answer2 = SolT;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        PuzzleData::try_parse(input)
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -146,23 +174,26 @@ mod tests {
     pub fn test_from() {
         let PuzzleData(data) = PuzzleData::from(CONTENT);
         assert_eq!(2, data.len());
-        assert_eq!(9, data[0].1);
-        assert_eq!(9, data[1].1);
+        assert_eq!(9, data[0].width());
+        assert_eq!(9, data[1].width());
         println!("{data:?}");
     }
 
+    #[test]
+    pub fn test_parse_error() {
+        assert_eq!(
+            Some(ParseError::new(5, 2, "'#' or '.'")),
+            PuzzleData::try_parse("#.\n..\n\n##\n#x").err()
+        );
+    }
+
     #[test]
     pub fn test_find_line_vertical() {
         let PuzzleData(data) = PuzzleData::from(CONTENT);
         assert_eq!(
             vec![Some(4), None],
             data.iter()
-                .map(|&(pattern, w)| find_line(
-                    pattern,
-                    w,
-                    (pattern.len() + 1) / (w + 1),
-                    ToIdx::ColRow(w)
-                ))
+                .map(|pattern| find_line(&pattern.view()))
                 .collect::<Vec<_>>()
         )
     }
@@ -173,12 +204,7 @@ mod tests {
         assert_eq!(
             vec![None, Some(3)],
             data.iter()
-                .map(|&(pattern, w)| find_line(
-                    pattern,
-                    (pattern.len() + 1) / (w + 1),
-                    w,
-                    ToIdx::RowCol(w)
-                ))
+                .map(|pattern| find_line(&pattern.view().transpose()))
                 .collect::<Vec<_>>()
         );
     }
@@ -1,5 +1,10 @@
 use input::*;
-use std::fs::read_to_string;
+use mr_kaffee_utils::{
+    grids::{Grid, GridRef, View},
+    iterators::find_cycle,
+    parse::ParseError,
+    solution::Solution,
+};
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/14";
@@ -7,53 +12,72 @@ pub const IDENTIFIER: &str = "2023/14";
 pub type InputT<'a> = PuzzleData<'a>;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input14").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
 // tag::input[]
 pub mod input {
+    use mr_kaffee_utils::{grids::GridRef, parse::ParseError};
+
     #[derive(Debug)]
-    pub struct PuzzleData<'a>(pub &'a [u8], pub usize, pub usize);
+    pub struct PuzzleData<'a>(pub GridRef<'a>);
+
+    impl<'a> PuzzleData<'a> {
+        /// Parse the input, reporting anything that is not a rectangular grid of
+        /// 'O', '#' or '.' as error
+        pub fn try_parse<T>(s: &'a T) -> Result<Self, ParseError>
+        where
+            T: AsRef<[u8]> + 'a + ?Sized,
+        {
+            GridRef::from_bytes(
+                s.as_ref(),
+                |b| matches!(b, b'O' | b'#' | b'.'),
+                "'O', '#' or '.'",
+            )
+            .map(Self)
+        }
+    }
 
     impl<'a, T> From<&'a T> for PuzzleData<'a>
     where
         T: AsRef<[u8]> + 'a + ?Sized,
     {
         fn from(s: &'a T) -> Self {
-            let data = s.as_ref();
-            let w = data.iter().position(|&b| b == b'\n').unwrap_or(data.len());
-            let h = (data.len() + 1) / (w + 1);
-            Self(data, w, h)
+            Self::try_parse(s).unwrap()
         }
     }
 }
 // end::input[]
 
 // tag::star_1[]
-pub fn star_1(&PuzzleData(data, w, h): &PuzzleData) -> usize {
-    (0..w)
-        .map(|col| {
-            (0..h).fold((0, 0), |(load, free), row| {
-                match data[col + row * (w + 1)] {
+pub fn star_1(PuzzleData(grid): &PuzzleData) -> usize {
+    let h = grid.height();
+    grid.columns()
+        .map(|column| {
+            column
+                .enumerate()
+                .fold((0, 0), |(load, free), (row, b)| match b {
                     b'#' => (load, row + 1),
                     b'O' => (load + h - free, free + 1),
                     _ => (load, free),
-                }
-            })
+                })
         })
         .fold(0, |sum, (load, _)| sum + load)
 }
 // end::star_1[]
 
 // tag::star_2[]
-pub fn tilt<F: Fn(usize, usize) -> usize>(data: &mut [u8], d1: usize, d2: usize, idx: F) -> usize {
+/// Tilt the view so that all rounded rocks roll to its top, return the load
+/// on the top
+pub fn tilt(view: &mut View<u8, &mut [u8]>) -> usize {
+    let (d1, d2) = (view.width(), view.height());
     (0..d1)
         .map(|x| {
-            (0..d2).fold((0, 0), |(load, free), y| match data[idx(x, y)] {
+            (0..d2).fold((0, 0), |(load, free), y| match view[(x, y)] {
                 b'#' => (load, y + 1),
                 b'O' => {
-                    data.swap(idx(x, free), idx(x, y));
+                    view.swap((x, free), (x, y));
                     (load + d2 - free, free + 1)
                 }
                 _ => (load, free),
@@ -62,50 +86,102 @@ pub fn tilt<F: Fn(usize, usize) -> usize>(data: &mut [u8], d1: usize, d2: usize,
         .fold(0, |sum, (load, _)| sum + load)
 }
 
-pub fn cycle(data: &mut [u8], w: usize, h: usize) -> [usize; 4] {
+/// Tilt north, west, south and east
+pub fn cycle(grid: &mut Grid) -> [usize; 4] {
     [
-        tilt(data, w, h, |col, row| col + row * (w + 1)),
-        tilt(data, h, w, |row, col| col + row * (w + 1)),
-        tilt(data, w, h, |col, row_inv| col + (h - row_inv - 1) * (w + 1)),
-        tilt(data, h, w, |row, col_inv| (w - col_inv - 1) + row * (w + 1)),
+        tilt(&mut grid.view_mut()),
+        tilt(&mut grid.view_mut().rotate_cw()),
+        tilt(&mut grid.view_mut().rotate_180()),
+        tilt(&mut grid.view_mut().rotate_ccw()),
     ]
 }
 
-pub fn star_2(&PuzzleData(data, w, h): &PuzzleData) -> usize {
-    let mut data = data.to_owned();
-
-    // cycle until repetition is found
-    let r = (0..)
-        .scan(Vec::new(), |list, n_1| {
-            let loads = cycle(&mut data, w, h);
-            let v = list
-                .iter()
-                .position(|prev| prev == &loads)
-                .map(|n_0| ((1_000_000_000) - (n_1 + 1)) % (n_1 - n_0));
-            list.push(loads);
-            Some(v)
-        })
-        .flatten()
-        .next()
-        .unwrap();
+/// The grid after `n` spin cycles
+///
+/// Repetitions are detected on the full rock configuration, so the result is
+/// exact.
+pub fn spin(grid: &GridRef, n: usize) -> Grid {
+    find_cycle(grid.view().to_grid(), |grid| {
+        let mut grid = grid.clone();
+        cycle(&mut grid);
+        grid
+    })
+    .nth(n)
+    .clone()
+}
+
+/// The load on the north support beams (without tilting to north)
+pub fn north_load(grid: &Grid) -> usize {
+    let h = grid.height();
+    grid.iter()
+        .filter(|&(_, &b)| b == b'O')
+        .map(|((_, row), _)| h - row)
+        .sum()
+}
+
+/// The load on the north support beams after `n` spin cycles, assuming that
+/// the rock configuration repeats when the loads returned by [`cycle`] do
+///
+/// This saves hashing grids but may give wrong results if different
+/// configurations produce identical loads. It is only used for star 2 with
+/// feature `load-shortcut`.
+pub fn spin_load_shortcut(grid: &GridRef, n: usize) -> usize {
+    let mut grid = grid.view().to_grid();
 
-    // execute residual cycles
-    for _ in 0..r {
-        cycle(&mut data, w, h);
+    // cycle until repetition is found or n cycles are done
+    let mut list = Vec::new();
+    for n_1 in 0..n {
+        let loads = cycle(&mut grid);
+        if let Some(n_0) = list.iter().position(|prev| prev == &loads) {
+            // execute residual cycles
+            for _ in 0..(n - (n_1 + 1)) % (n_1 - n_0) {
+                cycle(&mut grid);
+            }
+            break;
+        }
+        list.push(loads);
     }
 
-    // determine load on north (without tilting to north)
-    (0..w)
-        .map(|col| {
-            (0..h)
-                .filter(|&row| data[col + row * (w + 1)] == b'O')
-                .map(|row| h - row)
-                .sum::<usize>()
-        })
-        .sum()
+    north_load(&grid)
+}
+
+#[cfg(not(feature = "load-shortcut"))]
+pub fn star_2(PuzzleData(grid): &PuzzleData) -> usize {
+    north_load(&spin(grid, 1_000_000_000))
+}
+
+#[cfg(feature = "load-shortcut")]
+pub fn star_2(PuzzleData(grid): &PuzzleData) -> usize {
+    spin_load_shortcut(grid, 1_000_000_000)
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData<'a>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        PuzzleData::try_parse(input)
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -161,21 +237,21 @@ O.#..O.#.#
 
     #[test]
     pub fn test_from() {
-        let PuzzleData(_, w, h) = PuzzleData::from(CONTENT);
-        assert_eq!(10, w);
-        assert_eq!(10, h);
+        let PuzzleData(grid) = PuzzleData::from(CONTENT);
+        assert_eq!(10, grid.width());
+        assert_eq!(10, grid.height());
     }
 
     #[test]
     pub fn test_cycle() {
-        let PuzzleData(data, w, h) = PuzzleData::from(CONTENT);
-        let mut data = data.to_owned();
-        cycle(&mut data, w, h);
-        assert_eq!(String::from_utf8_lossy(&data), CONTENT_1);
-        cycle(&mut data, w, h);
-        assert_eq!(String::from_utf8_lossy(&data), CONTENT_2);
-        cycle(&mut data, w, h);
-        assert_eq!(String::from_utf8_lossy(&data), CONTENT_3);
+        let PuzzleData(grid) = PuzzleData::from(CONTENT);
+        let mut grid = grid.view().to_grid();
+        cycle(&mut grid);
+        assert_eq!(grid.to_string(), CONTENT_1);
+        cycle(&mut grid);
+        assert_eq!(grid.to_string(), CONTENT_2);
+        cycle(&mut grid);
+        assert_eq!(grid.to_string(), CONTENT_3);
     }
 
     #[test]
@@ -185,9 +261,19 @@ O.#..O.#.#
 
     #[test]
     pub fn test_tilt_north() {
-        let PuzzleData(data, w, h) = PuzzleData::from(CONTENT);
-        let mut data = data.to_owned();
-        assert_eq!(136, tilt(&mut data, w, h, |col, row| col + row * (w + 1)));
+        let PuzzleData(grid) = PuzzleData::from(CONTENT);
+        let mut grid = grid.view().to_grid();
+        assert_eq!(136, tilt(&mut grid.view_mut()));
+    }
+
+    #[test]
+    pub fn test_spin() {
+        let PuzzleData(grid) = PuzzleData::from(CONTENT);
+        assert_eq!(grid.view().to_grid(), spin(&grid, 0));
+        assert_eq!(CONTENT_3, spin(&grid, 3).to_string());
+        for n in [0, 1, 3, 100, 1_000, 1_000_000_000] {
+            assert_eq!(north_load(&spin(&grid, n)), spin_load_shortcut(&grid, n));
+        }
     }
 
     #[test]
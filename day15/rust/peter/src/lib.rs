@@ -1,4 +1,7 @@
-use std::fs::read_to_string;
+use mr_kaffee_utils::{
+    parse::{parse_token, ParseError},
+    solution::Solution,
+};
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/15";
@@ -6,10 +9,23 @@ pub const IDENTIFIER: &str = "2023/15";
 pub type InputT<'a> = &'a str;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input15").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
+/// Check that the input is a comma separated list of steps `<label>=<value>`
+/// or `<label>-`
+pub fn check_input(s: &str) -> Result<(), ParseError> {
+    for step in s.split(',').map(str::trim) {
+        match step.split_once('=') {
+            Some((_, value)) => _ = parse_token::<usize>(s, value, "focal length")?,
+            None if step.ends_with('-') => (),
+            None => return Err(ParseError::after(s, step, "'=' or '-'")),
+        }
+    }
+    Ok(())
+}
+
 // tag::star_1[]
 pub fn hash(item: &str) -> usize {
     item.bytes()
@@ -60,6 +76,33 @@ pub fn star_2(data: &str) -> usize {
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = &'a str;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        check_input(input)?;
+        Ok(input)
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -76,5 +119,18 @@ mod tests {
     pub fn test_star_2() {
         assert_eq!(145, star_2(CONTENT));
     }
+
+    #[test]
+    pub fn test_parse_error() {
+        assert_eq!(Ok(()), check_input(CONTENT));
+        assert_eq!(
+            Err(ParseError::new(1, 9, "focal length")),
+            check_input("rn=1,cm=x")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 8, "'=' or '-'")),
+            check_input("rn=1,cm\n")
+        );
+    }
 }
 // end::tests[]
@@ -1,14 +1,15 @@
 use mr_kaffee_2023_15::*;
+use mr_kaffee_utils::{inputs::path_from_args, solution::Solution};
 use std::time::Instant;
 
 fn main() {
     let t = Instant::now();
 
-    let input = read_input();
+    let input = Day::read_input(path_from_args().as_deref()).unwrap();
     let data = InputT::from(&input);
 
     let t_1 = Instant::now();
-    let sol_1 = star_1(&data);
+    let sol_1 = star_1(data);
     println!(
         "Solved {}, star 1 in {:?}: {}",
         IDENTIFIER,
@@ -17,7 +18,7 @@ fn main() {
     );
 
     let t_2 = Instant::now();
-    let sol_2 = star_2(&data);
+    let sol_2 = star_2(data);
     println!(
         "Solved {}, star 2 in {:?}: {}",
         IDENTIFIER,
@@ -1,5 +1,6 @@
 use clap::Parser;
-use mr_kaffee_2023_16::{input::PuzzleData, read_input, simulate_beam, EAST, NORTH, SOUTH, WEST};
+use mr_kaffee_2023_16::{input::PuzzleData, read_input, simulate_beam};
+use mr_kaffee_utils::directions::Direction;
 use std::{error::Error, fs::File, iter::once};
 
 #[derive(Parser, Debug)]
@@ -28,17 +29,17 @@ struct Cli {
     #[arg(short, long, default_value_t = 0)]
     tile: usize,
 
-    /// initial direction
-    #[arg(short, long, default_value_t = EAST, value_parser = clap::value_parser!(u8).range(0..4))]
-    dir: u8,
+    /// initial direction, one of '>^<v', 'ENWS' or 'RULD'
+    #[arg(short, long, default_value_t = Direction::East)]
+    dir: Direction,
 }
 
 pub fn main() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
 
-    let input = (!cli.example).then(|| read_input());
-    let PuzzleData(data, w, h) =
-        PuzzleData::from(input.as_ref().map(String::as_str).unwrap_or(EXAMPLE));
+    let input = (!cli.example).then(read_input);
+    let PuzzleData(grid) = PuzzleData::from(input.as_deref().unwrap_or(EXAMPLE));
+    let (data, w, h) = (grid.data(), grid.width(), grid.height());
 
     let check = |tile, dim| {
         assert!(tile < dim, "Dim is {} but tile is {}", dim, tile);
@@ -50,20 +51,19 @@ pub fn main() -> Result<(), Box<dyn Error>> {
     let fps = cli.fps;
     let step_size = cli.step_size;
     let start = match cli.dir {
-        EAST => ((0, check(cli.tile, h)), EAST),
-        NORTH => ((check(cli.tile, w), h - 1), NORTH),
-        WEST => ((w - 1, h - 1 - check(cli.tile, h)), WEST),
-        SOUTH => ((w - 1 - check(cli.tile, w), 0), SOUTH),
-        _ => unreachable!(),
+        Direction::East => ((0, check(cli.tile, h)), Direction::East),
+        Direction::North => ((check(cli.tile, w), h - 1), Direction::North),
+        Direction::West => ((w - 1, h - 1 - check(cli.tile, h)), Direction::West),
+        Direction::South => ((w - 1 - check(cli.tile, w), 0), Direction::South),
     };
 
     let images = once(get_image_data(data, None, (w, h), scale))
         .chain(
             (step_size..)
                 .step_by(step_size)
-                .map(|steps| (steps, simulate_beam(data, (w, h), start, steps)))
+                .map(|steps| (steps, simulate_beam(&grid, start, steps)))
                 .take_while(|(req_steps, (_, act_steps))| req_steps == act_steps)
-                .map(|(_, (beam, _))| get_image_data(data, Some(&beam), (w, h), scale)),
+                .map(|(_, (beam, _))| get_image_data(data, Some(beam.data()), (w, h), scale)),
         )
         .collect::<Vec<_>>();
 
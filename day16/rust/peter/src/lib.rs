@@ -1,5 +1,10 @@
 use input::*;
-use std::fs::read_to_string;
+use mr_kaffee_utils::{
+    directions::Direction,
+    grids::{Grid, GridRef},
+    parse::ParseError,
+    solution::Solution,
+};
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/16";
@@ -7,38 +12,45 @@ pub const IDENTIFIER: &str = "2023/16";
 pub type InputT<'a> = PuzzleData<'a>;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input16").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
 // tag::input[]
 pub mod input {
+    use mr_kaffee_utils::{grids::GridRef, parse::ParseError};
+
     #[derive(Debug)]
-    pub struct PuzzleData<'a>(pub &'a [u8], pub usize, pub usize);
+    pub struct PuzzleData<'a>(pub GridRef<'a>);
+
+    impl<'a> PuzzleData<'a> {
+        /// Parse the input, reporting anything that is not a rectangular grid of
+        /// '.', '/', '\', '|' or '-' as error
+        pub fn try_parse<T>(s: &'a T) -> Result<Self, ParseError>
+        where
+            T: AsRef<[u8]> + 'a + ?Sized,
+        {
+            GridRef::from_bytes(
+                s.as_ref(),
+                |b| matches!(b, b'.' | b'/' | b'\\' | b'|' | b'-'),
+                "'.', '/', '\\', '|' or '-'",
+            )
+            .map(Self)
+        }
+    }
 
     impl<'a, T> From<&'a T> for PuzzleData<'a>
     where
         T: AsRef<[u8]> + 'a + ?Sized,
     {
         fn from(s: &'a T) -> Self {
-            let data = s.as_ref();
-            let w = data.iter().position(|&b| b == b'\n').unwrap_or(data.len());
-            let h = (data.len() + 1) / (w + 1);
-            Self(data, w, h)
+            Self::try_parse(s).unwrap()
         }
     }
 }
 // end::input[]
 
 // tag::star_1[]
-pub const EAST: u8 = 0;
-pub const NORTH: u8 = 1;
-pub const WEST: u8 = 2;
-pub const SOUTH: u8 = 3;
-
-pub const EAST_WEST: u8 = 0;
-pub const NORTH_SOUTH: u8 = 1;
-
 #[cfg(feature = "bfs")]
 struct Queue<T>(std::collections::VecDeque<T>);
 
@@ -76,14 +88,13 @@ impl<T> Queue<T> {
 }
 
 pub fn simulate_beam(
-    data: &[u8],
-    (w, h): (usize, usize),
-    ((col, row), heading): ((usize, usize), u8),
+    grid: &GridRef,
+    ((col, row), heading): ((usize, usize), Direction),
     steps: usize,
-) -> (Vec<u8>, usize) {
+) -> (Grid, usize) {
     let mut queue = Queue::from([(1, (col, row), heading)]);
-    let mut seen = vec![0u8; w * h];
-    seen[col + row * w] |= 1 << heading;
+    let mut seen = Grid::filled(0u8, grid.width(), grid.height());
+    seen[(col, row)] |= 1 << heading.index();
 
     let mut max_steps = 0;
     while let Some((cur_steps, (col, row), heading)) = queue.pop() {
@@ -92,26 +103,21 @@ pub fn simulate_beam(
             continue;
         }
 
-        let deltas: &[u8] = match (data[col + row * (w + 1)], heading & 1) {
-            (b'.', _) | (b'-', EAST_WEST) | (b'|', NORTH_SOUTH) => &[0], // pass-through
-            (b'-', _) | (b'|', _) => &[1, 3],                            // split, turn left & right
-            (b'/', EAST_WEST) | (b'\\', NORTH_SOUTH) => &[1],            // turn left
-            (b'\\', EAST_WEST) | (b'/', NORTH_SOUTH) => &[3],            // turn right
+        let headings: &[Direction] = match (grid[(col, row)], heading.is_horizontal()) {
+            (b'.', _) | (b'-', true) | (b'|', false) => &[heading], // pass-through
+            (b'-', _) | (b'|', _) => &[heading.turn_left(), heading.turn_right()], // split
+            (b'/', true) | (b'\\', false) => &[heading.turn_left()],
+            (b'\\', true) | (b'/', false) => &[heading.turn_right()],
             _ => panic!(),
         };
 
-        for delta in deltas {
-            let heading = (heading + delta) & 3;
-            let (col, row) = match heading {
-                EAST => (col + 1, row),
-                NORTH => (col, row.wrapping_sub(1)),
-                WEST => (col.wrapping_sub(1), row),
-                SOUTH => (col, row + 1),
-                _ => panic!(),
+        for &heading in headings {
+            let Some(pos) = grid.step((col, row), heading) else {
+                continue;
             };
-            if col < w && row < h && (seen[col + row * w] & (1 << heading)) == 0 {
-                seen[col + row * w] |= 1 << heading;
-                queue.push((cur_steps + 1, (col, row), heading));
+            if (seen[pos] & (1 << heading.index())) == 0 {
+                seen[pos] |= 1 << heading.index();
+                queue.push((cur_steps + 1, pos, heading));
             }
         }
     }
@@ -119,32 +125,60 @@ pub fn simulate_beam(
     (seen, max_steps)
 }
 
-pub fn count_energized(data: &[u8], dims: (usize, usize), beam: ((usize, usize), u8)) -> usize {
-    simulate_beam(data, dims, beam, usize::MAX)
+pub fn count_energized(grid: &GridRef, beam: ((usize, usize), Direction)) -> usize {
+    simulate_beam(grid, beam, usize::MAX)
         .0
-        .into_iter()
-        .filter(|&v| v > 0)
+        .data()
+        .iter()
+        .filter(|&&v| v > 0)
         .count()
 }
 
-pub fn star_1(&PuzzleData(data, w, h): &PuzzleData) -> usize {
-    count_energized(data, (w, h), ((0, 0), EAST))
+pub fn star_1(PuzzleData(grid): &PuzzleData) -> usize {
+    count_energized(grid, ((0, 0), Direction::East))
 }
 // end::star_1[]
 
 // tag::star_2[]
-pub fn star_2(&PuzzleData(data, w, h): &PuzzleData) -> usize {
+pub fn star_2(PuzzleData(grid): &PuzzleData) -> usize {
+    let (w, h) = (grid.width(), grid.height());
     (0..w)
-        .map(|col| ((col, 0), SOUTH))
-        .chain((0..h).map(|row| ((w - 1, row), WEST)))
-        .chain((0..w).map(|col_inv| ((w - col_inv - 1, h - 1), NORTH)))
-        .chain((0..h).map(|row_inv| ((0, h - row_inv - 1), EAST)))
-        .map(|start| count_energized(data, (w, h), start))
+        .map(|col| ((col, 0), Direction::South))
+        .chain((0..h).map(|row| ((w - 1, row), Direction::West)))
+        .chain((0..w).map(|col_inv| ((w - col_inv - 1, h - 1), Direction::North)))
+        .chain((0..h).map(|row_inv| ((0, h - row_inv - 1), Direction::East)))
+        .map(|start| count_energized(grid, start))
         .max()
         .unwrap()
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData<'a>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        PuzzleData::try_parse(input)
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -164,10 +198,10 @@ mod tests {
 
     #[test]
     pub fn test_from() {
-        let PuzzleData(data, w, h) = PuzzleData::from(CONTENT);
-        assert_eq!(10, w);
-        assert_eq!(10, h);
-        assert_eq!(110, data.len());
+        let PuzzleData(grid) = PuzzleData::from(CONTENT);
+        assert_eq!(10, grid.width());
+        assert_eq!(10, grid.height());
+        assert_eq!(110, grid.data().len());
     }
 
     #[test]
@@ -1,296 +1,248 @@
 use input::*;
-#[cfg(feature = "plot")]
-use std::collections::HashMap;
-use std::fs::read_to_string;
+use mr_kaffee_utils::{
+    directions::Direction, grids::GridRef, parse::ParseError, search, solution::Solution,
+};
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/17";
 
 pub type SolT = usize;
-pub type SolST = isize;
 pub type InputT<'a> = PuzzleData<'a>;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input17").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
 // tag::input[]
 pub mod input {
+    use mr_kaffee_utils::{grids::GridRef, parse::ParseError};
+
     #[derive(Debug)]
-    pub struct PuzzleData<'a>(pub &'a [u8], pub usize, pub usize);
+    pub struct PuzzleData<'a>(pub GridRef<'a>);
+
+    impl<'a> PuzzleData<'a> {
+        /// Parse the input, reporting anything that is not a rectangular grid of
+        /// digits from 1 to 9 as error
+        pub fn try_parse<T>(s: &'a T) -> Result<Self, ParseError>
+        where
+            T: AsRef<[u8]> + 'a + ?Sized,
+        {
+            GridRef::from_bytes(
+                s.as_ref(),
+                |b| matches!(b, b'1'..=b'9'),
+                "a digit from 1 to 9",
+            )
+            .map(Self)
+        }
+    }
 
     impl<'a, T> From<&'a T> for PuzzleData<'a>
     where
         T: AsRef<[u8]> + 'a + ?Sized,
     {
         fn from(s: &'a T) -> Self {
-            let data = s.as_ref();
-            let w = data.iter().position(|b| b == &b'\n').unwrap_or(data.len());
-            let h = (data.len() + 1) / (w + 1);
-            Self(data, w, h)
+            Self::try_parse(s).unwrap()
         }
     }
 }
 // end::input[]
 
-const D: [(SolST, SolST); 4] = [(1, 0), (0, -1), (-1, 0), (0, 1)];
-
 // tag::loss_bounds[]
 #[cfg(not(feature = "no-heuristic"))]
-fn loss_bounds(grid: &[u8], w: usize, h: usize) -> Vec<SolT> {
-    use std::collections::BinaryHeap;
-
-    let target = (w - 1, h - 1);
-    let mut bounds = vec![SolT::MAX; w * h];
-    bounds[w * h - 1] = 0;
-    // queue is a max heap! Need to inverse cost.
-    let mut queue =
-        BinaryHeap::from([(SolT::MAX - (grid[(w + 1) * h - 2] - b'0') as SolT, target)]);
-    while let Some((cost_0, (c0, r0))) = queue.pop() {
-        for (c1, r1) in D
-            .map(|(dc, dr)| (c0.wrapping_add_signed(dc), r0.wrapping_add_signed(dr)))
-            .into_iter()
-            .filter(|&(c1, r1)| c1 < w && r1 < h)
-        {
-            // settle value; this is safe because the cost adder to reach a node
-            // is the same no matter from which neighbor we reach the node
-            let r = &mut bounds[c1 + r1 * w];
-            if *r == SolT::MAX {
-                *r = SolT::MAX - cost_0;
-                queue.push((cost_0 - (grid[c1 + r1 * (w + 1)] - b'0') as SolT, (c1, r1)))
-            }
-        }
+fn loss_bounds(grid: &GridRef) -> mr_kaffee_utils::grids::Grid<SolT> {
+    use mr_kaffee_utils::grids::Grid;
+
+    // search backwards from the target: moving from a block to its neighbour
+    // costs the heat loss of the block left, which is entered going forwards
+    let (w, h) = (grid.width(), grid.height());
+    let costs = search::dijkstra_costs(
+        [((w - 1, h - 1), 0)],
+        |&pos| {
+            let loss = (grid[pos] - b'0') as SolT;
+            grid.neighbours_4(pos).map(move |adj| (adj, loss))
+        },
+        SolT::MAX,
+    );
+
+    let mut bounds = Grid::filled(SolT::MAX, w, h);
+    for (pos, cost) in costs {
+        bounds[pos] = cost;
     }
     bounds
 }
 
 #[cfg(not(feature = "no-heuristic"))]
-fn loss_bounds_heuristic(grid: &[u8], w: usize, h: usize) -> impl Fn((usize, usize)) -> SolT {
-    let bounds = loss_bounds(grid, w, h);
-    move |(col, row)| bounds[col + row * w]
+fn loss_bounds_heuristic(grid: &GridRef) -> impl Fn((usize, usize)) -> SolT {
+    let bounds = loss_bounds(grid);
+    move |pos| bounds[pos]
 }
 
 #[cfg(feature = "no-heuristic")]
-fn loss_bounds_heuristic(_: &[u8], _: usize, _: usize) -> impl Fn((usize, usize)) -> SolT {
+fn loss_bounds_heuristic(_: &GridRef) -> impl Fn((usize, usize)) -> SolT {
     |_| 0
 }
 // end::loss_bounds[]
 
-#[cfg(feature = "settle-early")]
-type NodeT = ((usize, usize), (u8, u8));
+#[cfg(feature = "heading-and-steps")]
+type NodeT = ((usize, usize), (Direction, u8));
 
-#[cfg(all(feature = "plot", feature = "settle-early"))]
-pub fn to_string(
-    w: usize,
-    h: usize,
-    ((c, r), (hd, s)): NodeT,
-    parents: &HashMap<NodeT, NodeT>,
-) -> String {
-    use std::iter::successors;
+#[cfg(not(feature = "heading-and-steps"))]
+type NodeT = ((usize, usize), u8);
 
+#[cfg(feature = "plot")]
+pub fn to_string(w: usize, h: usize, expanded: &[NodeT], path: &[NodeT]) -> String {
     let mut data = vec!['.'; (w + 1) * h];
     (0..h).for_each(|r| data[w + r * (w + 1)] = '\n');
 
-    parents.keys().for_each(|((c, r), _)| {
+    expanded.iter().for_each(|((c, r), _)| {
         data[c + r * (w + 1)] = '+';
     });
 
-    successors(Some((((c, r), (hd, s)), ((c, r), (hd, s)))), |(_, n1)| {
-        parents.get(n1).map(|n2| (*n1, *n2))
-    })
-    .flat_map(|(((c1, r1), _), ((c2, r2), _))| {
-        (c1.min(c2)..=c1.max(c2)).flat_map(move |c| (r1.min(r2)..=r1.max(r2)).map(move |r| (c, r)))
-    })
-    .for_each(|(c, r)| data[c + r * (w + 1)] = '#');
+    path.windows(2)
+        .flat_map(|w| {
+            let (((c1, r1), _), ((c2, r2), _)) = (w[0], w[1]);
+            (c1.min(c2)..=c1.max(c2))
+                .flat_map(move |c| (r1.min(r2)..=r1.max(r2)).map(move |r| (c, r)))
+        })
+        .for_each(|(c, r)| data[c + r * (w + 1)] = '#');
 
     data.iter().collect()
 }
 
-// tag::settle-early[]
-#[cfg(feature = "settle-early")]
-pub fn optimize(grid: &[u8], w: usize, h: usize, s_max: u8, s_min: u8) -> SolT {
-    use std::collections::{BinaryHeap, HashSet};
+// tag::heading-and-steps[]
+#[cfg(feature = "heading-and-steps")]
+pub fn optimize(grid: &GridRef, s_max: u8, s_min: u8) -> SolT {
     use std::iter::successors;
 
-    let heuristic = loss_bounds_heuristic(grid, w, h);
-
-    let start_cost = heuristic((0, 0));
-    let starts: [(usize, NodeT); 2] = [
-        (start_cost, ((0, 0), (0, s_max))),
-        (start_cost, ((0, 0), (3, s_max))),
-    ];
-
+    let (w, h) = (grid.width(), grid.height());
+    let heuristic = loss_bounds_heuristic(grid);
     let target_pos = (w - 1, h - 1);
 
-    let mut queue = BinaryHeap::new();
-    let mut seen = HashSet::new();
-    for (cost, node) in starts.into_iter() {
-        queue.push((SolT::MAX - cost, node));
-        seen.insert(node);
-    }
-
     #[cfg(feature = "plot")]
-    let mut parents = HashMap::new();
+    let mut expanded = Vec::new();
 
-    while let Some((cost_0, ((c0, r0), (hd0, s0)))) = queue.pop() {
-        if (c0, r0) == target_pos {
-            #[cfg(feature = "plot")]
-            println!("{}", to_string(w, h, ((c0, r0), (hd0, s0)), &parents));
-            return SolT::MAX - cost_0;
-        }
+    let adjacents = |&((c0, r0), (hd0, s0)): &NodeT| {
+        #[cfg(feature = "plot")]
+        expanded.push(((c0, r0), (hd0, s0)));
 
-        let dhs: &[u8] = if s0 < s_max { &[0, 1, 3] } else { &[1, 3] };
-        for (cost_1, node_1) in dhs
-            .iter()
-            .map(move |&dh| ((hd0 + dh) & 3, if dh == 0 { s0 } else { 0 }))
-            .filter_map(|(hd1, s1)| {
+        // keep heading only while below s_max
+        [(hd0, s0), (hd0.turn_left(), 0), (hd0.turn_right(), 0)]
+            .into_iter()
+            .skip((s0 >= s_max) as usize)
+            .filter_map(move |(hd1, s1)| {
                 // move one step or enough to complete s_min, whatever is more
                 let to_go = 1.max(s_min - s1.min(s_min));
-                let (dc, dr) = D[hd1 as usize];
-                successors(Some((0, c0, r0)), |(weight, c, r)| {
+                successors(Some((0, c0, r0)), |&(weight, c, r)| {
                     // return Some - summing weights - while in bounds
-                    let (c, r) = (c.wrapping_add_signed(dc), r.wrapping_add_signed(dr));
-                    (c < w && r < h)
-                        .then(|| (weight + (grid[c + r * (w + 1)] - b'0') as SolT, c, r))
+                    grid.step((c, r), hd1)
+                        .map(|(c, r)| (weight + (grid[(c, r)] - b'0') as SolT, c, r))
                 })
                 .nth(to_go as _)
-                .map(|(weight, c1, r1)| {
-                    (
-                        cost_0 - weight + heuristic((c0, r0)) - heuristic((c1, r1)),
-                        ((c1, r1), (hd1, s1 + to_go)),
-                    )
-                })
+                .map(|(weight, c1, r1)| (((c1, r1), (hd1, s1 + to_go)), weight))
             })
-        {
-            if seen.insert(node_1) {
-                queue.push((cost_1, node_1));
-                #[cfg(feature = "plot")]
-                parents.insert(node_1, ((c0, r0), (hd0, s0)));
-            }
-        }
-    }
-
-    panic!("No solution found.")
-}
-// end::settle-early[]
-
-#[cfg(not(feature = "settle-early"))]
-type NodeT = ((usize, usize), u8);
-
-#[cfg(all(feature = "plot", not(feature = "settle-early")))]
-pub fn to_string(
-    w: usize,
-    h: usize,
-    ((c, r), hd): NodeT,
-    settled: &[u8],
-    parents: &HashMap<NodeT, NodeT>,
-) -> String {
-    use std::iter::successors;
-
-    let mut data = vec!['.'; (w + 1) * h];
-    (0..h).for_each(|r| data[w + r * (w + 1)] = '\n');
+    };
+
+    let (loss, _path) = search::astar(
+        [
+            ((0, 0), (Direction::East, s_max)),
+            ((0, 0), (Direction::South, s_max)),
+        ],
+        adjacents,
+        |&(pos, _)| heuristic(pos),
+        |&(pos, _)| pos == target_pos,
+    )
+    .expect("No solution found.");
 
-    parents.keys().for_each(|((c, r), _)| {
-        data[c + r * (w + 1)] = '-';
-    });
-
-    (0..settled.len())
-        .filter(|&p| settled[p] > 0)
-        .for_each(|p| data[p % w + (p / w) * (w + 1)] = '+');
-
-    successors(Some((((c, r), hd), ((c, r), hd))), |(_, n1)| {
-        parents.get(n1).map(|n2| (*n1, *n2))
-    })
-    .flat_map(|(((c1, r1), _), ((c2, r2), _))| {
-        (c1.min(c2)..=c1.max(c2)).flat_map(move |c| (r1.min(r2)..=r1.max(r2)).map(move |r| (c, r)))
-    })
-    .for_each(|(c, r)| data[c + r * (w + 1)] = '#');
+    #[cfg(feature = "plot")]
+    println!("{}", to_string(w, h, &expanded, &_path));
 
-    data.iter().collect()
+    loss
 }
+// end::heading-and-steps[]
 
 // tag::greedy-expand[]
-#[cfg(not(feature = "settle-early"))]
-pub fn optimize(grid: &[u8], w: usize, h: usize, s_max: u8, s_min: u8) -> SolT {
-    use std::collections::BinaryHeap;
+#[cfg(not(feature = "heading-and-steps"))]
+pub fn optimize(grid: &GridRef, s_max: u8, s_min: u8) -> SolT {
     use std::iter::successors;
 
-    let heuristic = &loss_bounds_heuristic(grid, w, h);
-
-    let start_cost = SolT::MAX - heuristic((0, 0));
-    let starts: [(usize, NodeT); 2] = [(start_cost, ((0, 0), 0)), (start_cost, ((0, 0), 1))];
-
+    let (w, h) = (grid.width(), grid.height());
+    let heuristic = loss_bounds_heuristic(grid);
     let target_pos = (w - 1, h - 1);
 
-    let mut queue = BinaryHeap::new();
-    let mut settled = vec![0u8; w * h];
-    let mut costs = vec![0; w * h * 2];
-    for (cost, ((c, r), o)) in starts.into_iter() {
-        queue.push((cost, ((c, r), o)));
-        costs[c + r * w + (o as usize) * w * h] = cost;
-    }
-
     #[cfg(feature = "plot")]
-    let mut parents = HashMap::new();
+    let mut expanded = Vec::new();
 
-    while let Some((cost_0, ((c0, r0), o0))) = queue.pop() {
-        if (c0, r0) == target_pos {
-            #[cfg(feature = "plot")]
-            println!("{}", to_string(w, h, ((c0, r0), o0), &settled, &parents));
-            return SolT::MAX - cost_0;
-        }
-
-        if settled[c0 + r0 * w] & (1 << o0) == 0 {
-            settled[c0 + r0 * w] |= 1 << o0;
-        } else {
-            continue;
-        }
+    // orientation 0 is reached moving east or west, 1 moving north or south
+    let adjacents = |&((c0, r0), o0): &NodeT| {
+        #[cfg(feature = "plot")]
+        expanded.push(((c0, r0), o0));
 
-        for (cost_1, ((c1, r1), o1)) in [1, 3]
-            .iter()
-            .map(move |&dh| D[(o0 as usize + dh) & 3])
-            .flat_map(|(dc, dr)| {
-                successors(Some((0, c0, r0)), move |(weight, c, r)| {
-                    let (c, r) = (c.wrapping_add_signed(dc), r.wrapping_add_signed(dr));
-                    (c < w && r < h)
-                        .then(|| (weight + (grid[c + r * (w + 1)] - b'0') as SolT, c, r))
+        let hd0 = Direction::from_index(o0 as usize);
+        [hd0.turn_left(), hd0.turn_right()]
+            .into_iter()
+            .flat_map(move |hd1| {
+                successors(Some((0, c0, r0)), move |&(weight, c, r)| {
+                    grid.step((c, r), hd1)
+                        .map(|(c, r)| (weight + (grid[(c, r)] - b'0') as SolT, c, r))
                 })
                 .take(s_max as usize + 1)
                 .skip(1.max(s_min as _))
-                .map(move |(weight, c1, r1)| {
-                    (
-                        cost_0 - weight + heuristic((c0, r0)) - heuristic((c1, r1)),
-                        ((c1, r1), !o0 & 1),
-                    )
-                })
+                .map(move |(weight, c1, r1)| (((c1, r1), !o0 & 1), weight))
             })
-            .filter(|(_, ((r1, c1), o1))| settled[r1 + c1 * w] & (1 << o1) == 0)
-        {
-            let cost_1_prev = &mut costs[c1 + r1 * w + (o1 as usize) * w * h];
-            if cost_1 > *cost_1_prev {
-                *cost_1_prev = cost_1;
-                queue.push((cost_1, ((c1, r1), o1)));
-                #[cfg(feature = "plot")]
-                parents.insert(((c1, r1), o1), ((c0, r0), o0));
-            }
-        }
-    }
+    };
+
+    let (loss, _path) = search::astar(
+        [((0, 0), 0), ((0, 0), 1)],
+        adjacents,
+        |&(pos, _)| heuristic(pos),
+        |&(pos, _)| pos == target_pos,
+    )
+    .expect("No solution found.");
+
+    #[cfg(feature = "plot")]
+    println!("{}", to_string(w, h, &expanded, &_path));
 
-    panic!("No solution found.")
+    loss
 }
 // end::greedy-expand[]
 
 // tag::solution[]
-pub fn star_1(&PuzzleData(grid, w, h): &PuzzleData) -> SolT {
-    optimize(grid, w, h, 3, 0)
+pub fn star_1(PuzzleData(grid): &PuzzleData) -> SolT {
+    optimize(grid, 3, 0)
 }
 
-pub fn star_2(&PuzzleData(grid, w, h): &PuzzleData) -> SolT {
-    optimize(grid, w, h, 10, 4)
+pub fn star_2(PuzzleData(grid): &PuzzleData) -> SolT {
+    optimize(grid, 10, 4)
 }
 // end::solution[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData<'a>;
+    type Answer1 = SolT;
+    type Answer2 = SolT;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        PuzzleData::try_parse(input)
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -320,10 +272,10 @@ mod tests {
 
     #[test]
     pub fn test_from() {
-        let PuzzleData(grid, w, h) = PuzzleData::from(CONTENT);
-        assert_eq!(13, w);
-        assert_eq!(13, h);
-        assert_eq!(13 * 14, grid.len());
+        let PuzzleData(grid) = PuzzleData::from(CONTENT);
+        assert_eq!(13, grid.width());
+        assert_eq!(13, grid.height());
+        assert_eq!(13 * 14, grid.data().len());
     }
 
     #[test]
@@ -339,12 +291,13 @@ mod tests {
 
     #[cfg(not(feature = "no-heuristic"))]
     #[test]
+    #[allow(clippy::zero_prefixed_literal)]
     pub fn test_loss_bounds() {
-        let PuzzleData(grid, w, h) = CONTENT_2.into();
-        let bounds = loss_bounds(grid, w, h);
-        for row in 0..h {
-            for col in 0..w {
-                print!("{:>3}", bounds[col + row * w]);
+        let PuzzleData(grid) = CONTENT_2.into();
+        let bounds = loss_bounds(&grid);
+        for row in bounds.rows() {
+            for bound in row {
+                print!("{:>3}", bound);
             }
             println!();
         }
@@ -358,7 +311,7 @@ mod tests {
         ]
         .concat();
 
-        assert_eq!(exp, bounds);
+        assert_eq!(exp, bounds.data());
     }
 }
 // end::tests[]
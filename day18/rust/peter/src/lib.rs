@@ -1,4 +1,9 @@
-use std::fs::read_to_string;
+use mr_kaffee_utils::{
+    directions::Direction,
+    parse::{parse_token, ParseError},
+    polygon::Polygon,
+    solution::Solution,
+};
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/18";
@@ -7,43 +12,55 @@ pub type SolT = isize;
 pub type InputT<'a> = &'a str;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input18").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
+/// Check that every line is a dig step `<direction> <distance> (#<color>)`,
+/// where the last hex digit of the color is a direction from 0 to 3
+pub fn check_input(s: &str) -> Result<(), ParseError> {
+    for line in s.lines() {
+        let mut parts = line.split_ascii_whitespace();
+        let mut next = |expected| {
+            parts
+                .next()
+                .ok_or_else(|| ParseError::after(s, line, expected))
+        };
+
+        let dir = next("direction")?;
+        dir.parse::<Direction>().map_err(|err| err.within(s, dir))?;
+        let distance = next("distance")?;
+        parse_token::<SolT>(s, distance, "distance")?;
+        let code = next("color code")?;
+        let hex = code
+            .strip_prefix("(#")
+            .and_then(|code| code.strip_suffix(')'))
+            .filter(|hex| hex.len() == 6 && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| ParseError::at(s, code, "'(#<six hex digits>)'"))?;
+        if !(b'0'..=b'3').contains(&hex.as_bytes()[5]) {
+            return Err(ParseError::at(s, &hex[5..], "direction digit from 0 to 3"));
+        }
+
+        if let Some(part) = parts.next() {
+            return Err(ParseError::at(s, part, "end of line"));
+        }
+    }
+    Ok(())
+}
+
 // tag::star_1[]
 pub fn shoelace<F>(data: F) -> SolT
 where
-    F: Iterator<Item = (u8, SolT)>,
+    F: Iterator<Item = (Direction, SolT)>,
 {
-    let (_, s, p) = data.fold(((0, 0), 0, 0), |((x0, y0), s, p), (d, len)| {
-        let (x1, y1) = match d {
-            0 => (x0 + len, y0),
-            1 => (x0, y0 + len),
-            2 => (x0 - len, y0),
-            3 => (x0, y0 - len),
-            _ => unreachable!(),
-        };
-        (
-            (x1, y1),
-            s + (y0 + y1) * (x0 - x1),
-            p + x0.max(x1) - x0.min(x1) + y0.max(y1) - y0.min(y1),
-        )
-    });
-    s / 2 + p / 2 + 1
+    Polygon::from_steps((0, 0), data).lattice_points()
 }
 
 pub fn star_1(data: &&str) -> SolT {
     shoelace(data.lines().map(|line| {
         let mut parts = line.split_ascii_whitespace();
         (
-            match parts.next().unwrap() {
-                "R" => 0,
-                "D" => 1,
-                "L" => 2,
-                "U" => 3,
-                d => panic!("Illegal direction: {}", d),
-            },
+            parts.next().unwrap().parse().unwrap(),
             parts.next().unwrap().parse().unwrap(),
         )
     }))
@@ -52,16 +69,44 @@ pub fn star_1(data: &&str) -> SolT {
 
 // tag::star_2[]
 pub fn star_2(data: &&str) -> SolT {
+    use Direction::*;
     shoelace(data.lines().map(|line| {
         line.split_ascii_whitespace()
             .nth(2)
             .and_then(|code| SolT::from_str_radix(&code[2..code.len() - 1], 16).ok())
-            .map(|code| ((code & 0xf) as _, code >> 4))
+            .map(|code| ([East, South, West, North][(code & 0xf) as usize], code >> 4))
             .unwrap()
     }))
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = &'a str;
+    type Answer1 = SolT;
+    type Answer2 = SolT;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        check_input(input)?;
+        Ok(input)
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -92,5 +137,23 @@ U 2 (#7a21e3)
     pub fn test_star_2() {
         assert_eq!(952_408_144_115, star_2(&CONTENT));
     }
+
+    #[test]
+    pub fn test_parse_error() {
+        assert_eq!(Ok(()), check_input(CONTENT));
+        assert_eq!(
+            Err(ParseError::new(2, 1, "one of '>^<v', 'ENWS' or 'RULD'")),
+            check_input("R 6 (#70c710)\nX 5 (#0dc571)")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 6, "'(#<six hex digits>)'")),
+            check_input("R 6  #70c710")
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 12, "direction digit from 0 to 3")),
+            check_input("R 6 (#70c714)")
+        );
+        assert_eq!(Err(ParseError::new(1, 4, "color code")), check_input("R 6"));
+    }
 }
 // end::tests[]
@@ -1,5 +1,10 @@
 use input::*;
-use std::{collections::HashMap, fs::read_to_string, iter::successors};
+use mr_kaffee_utils::{
+    intervals::{Cuboid, Interval},
+    parse::ParseError,
+    solution::Solution,
+};
+use std::{cmp::Ordering, collections::HashMap, iter::successors};
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/19";
@@ -8,12 +13,13 @@ pub type SolT = usize;
 pub type InputT<'a> = PuzzleData<'a>;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input19").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
 // tag::input[]
 pub mod input {
+    use mr_kaffee_utils::parse::{parse_token, ParseError};
     use std::{cmp::Ordering, collections::HashMap};
 
     pub type ValT = usize;
@@ -23,70 +29,93 @@ pub mod input {
     #[derive(Debug)]
     pub struct PuzzleData<'a>(pub HashMap<&'a str, Vec<RuleT<'a>>>, pub Vec<PartT>);
 
-    fn parse_rule(rule: &str) -> RuleT {
-        rule.split_once(':')
-            .map(|(cond, target)| {
-                (
-                    Some((
-                        match cond.as_bytes()[0] {
-                            b'x' => 0,
-                            b'm' => 1,
-                            b'a' => 2,
-                            b's' => 3,
-                            b => panic!("Bad symbol: '{}'", b as char),
-                        },
-                        match cond.as_bytes()[1] {
-                            b'>' => Ordering::Greater,
-                            b'<' => Ordering::Less,
-                            b => panic!("Bad symbol: '{}'", b as char),
-                        },
-                        cond[2..].parse().unwrap(),
-                    )),
-                    target,
-                )
-            })
-            .unwrap_or((None, rule))
+    fn parse_rule<'a>(input: &str, rule: &'a str) -> Result<RuleT<'a>, ParseError> {
+        match rule.split_once(':') {
+            None => Ok((None, rule)),
+            Some((cond, target)) => {
+                let category = match cond.bytes().next() {
+                    Some(b'x') => 0,
+                    Some(b'm') => 1,
+                    Some(b'a') => 2,
+                    Some(b's') => 3,
+                    _ => return Err(ParseError::at(input, cond, "'x', 'm', 'a' or 's'")),
+                };
+                let ordering = match cond.as_bytes().get(1) {
+                    Some(b'>') => Ordering::Greater,
+                    Some(b'<') => Ordering::Less,
+                    _ => return Err(ParseError::at(input, &cond[1..], "'<' or '>'")),
+                };
+                let value = parse_token(input, &cond[2..], "number")?;
+                Ok((Some((category, ordering, value)), target))
+            }
+        }
     }
 
-    fn parse_workflows(workflow: &str) -> HashMap<&str, Vec<RuleT>> {
-        workflow
+    fn parse_workflows<'a>(
+        input: &str,
+        workflows: &'a str,
+    ) -> Result<HashMap<&'a str, Vec<RuleT<'a>>>, ParseError> {
+        workflows
             .lines()
             .map(|line| {
-                line.split_once('{')
+                let (label, rules) = line
+                    .split_once('{')
                     .and_then(|(label, rules)| rules.strip_suffix('}').map(|rules| (label, rules)))
-                    .unwrap()
+                    .ok_or_else(|| ParseError::at(input, line, "'<label>{<rules>}'"))?;
+                Ok((
+                    label,
+                    rules
+                        .split(',')
+                        .map(|rule| parse_rule(input, rule))
+                        .collect::<Result<_, _>>()?,
+                ))
             })
-            .map(|(label, rules)| (label, rules.split(',').map(parse_rule).collect()))
             .collect()
     }
 
-    fn parse_parts(parts: &str) -> Vec<PartT> {
+    fn parse_parts(input: &str, parts: &str) -> Result<Vec<PartT>, ParseError> {
         parts
             .lines()
             .map(|part| {
                 let mut values = part
-                    .trim_matches::<&[char]>(&['{', '}'])
+                    .strip_prefix('{')
+                    .and_then(|values| values.strip_suffix('}'))
+                    .ok_or_else(|| ParseError::at(input, part, "'{<ratings>}'"))?
                     .split(',')
-                    .map(|n| n[2..].parse().unwrap());
-                [
-                    values.next().unwrap(),
-                    values.next().unwrap(),
-                    values.next().unwrap(),
-                    values.next().unwrap(),
-                ]
+                    .map(|value| match value.get(2..) {
+                        Some(n) => parse_token(input, n, "rating"),
+                        None => Err(ParseError::at(input, value, "'<category>=<rating>'")),
+                    });
+                let mut next = || {
+                    values
+                        .next()
+                        .unwrap_or_else(|| Err(ParseError::after(input, part, "four ratings")))
+                };
+                Ok([next()?, next()?, next()?, next()?])
             })
             .collect()
     }
 
+    impl<'a> PuzzleData<'a> {
+        /// Parse the input, reporting malformed workflows or parts as error
+        pub fn try_parse<T>(s: &'a T) -> Result<Self, ParseError>
+        where
+            T: AsRef<str> + 'a + ?Sized,
+        {
+            let s = s.as_ref();
+            let (workflows, parts) = s
+                .split_once("\n\n")
+                .ok_or_else(|| ParseError::after(s, s, "blank line"))?;
+            Ok(Self(parse_workflows(s, workflows)?, parse_parts(s, parts)?))
+        }
+    }
+
     impl<'a, T> From<&'a T> for PuzzleData<'a>
     where
         T: AsRef<str> + 'a + ?Sized,
     {
         fn from(s: &'a T) -> Self {
-            s.as_ref()
-                .split_once("\n\n")
-                .map(|(workflows, parts)| Self(parse_workflows(workflows), parse_parts(parts)))
-                .unwrap()
+            Self::try_parse(s).unwrap()
         }
     }
 }
@@ -120,64 +149,70 @@ pub fn star_1(PuzzleData(workflows, parts): &PuzzleData) -> SolT {
 pub fn star_2_rec(
     workflows: &HashMap<&str, Vec<RuleT>>,
     target: &str,
-    mut v_min: PartT,
-    mut v_max: PartT,
+    mut parts: Cuboid<ValT, 4>,
 ) -> SolT {
     match target {
-        "A" => {
-            return (0..4)
-                .map(|idx| (v_max[idx] - v_min[idx]) as SolT)
-                .product()
-        }
+        "A" => return parts.volume(),
         "R" => return 0,
         _ => (),
     }
 
     let mut count = 0;
     for (rule, next) in workflows.get(target).unwrap() {
-        match rule
-            .map(|(idx, ord, val)| {
-                (
-                    idx,
-                    val,
-                    v_min[idx].cmp(&val) == ord,
-                    (v_max[idx] - 1).cmp(&val) == ord,
-                )
-            })
-            .unwrap_or((0, 0, true, true))
-        {
-            (_, _, false, false) => (), // rule does not match at all -> check next rule
-            (_, _, true, true) => {
-                // rule fully matches -> recurse and stop checking further rules here
-                return count + star_2_rec(workflows, next, v_min, v_max);
-            }
-            (idx, val, true, false) => {
-                // rule matches for values_min_match[idx]..val -> recurse
-                let (v_min_rec, mut v_max_rec) = (v_min, v_max);
-                v_max_rec[idx] = val;
-                count += star_2_rec(workflows, next, v_min_rec, v_max_rec);
-                // rule does not match for val..values_max[idx] -> check next rule
-                v_min[idx] = val;
-            }
-            (idx, val, false, true) => {
-                // rule matches for val + 1..values_max_match[idx] -> recurse
-                let (mut v_min_rec, v_max_rec) = (v_min, v_max);
-                v_min_rec[idx] = val + 1;
-                count += star_2_rec(workflows, next, v_min_rec, v_max_rec);
-                // rule does not match for values_min[idx]..val + 1 -> check next rule
-                v_max[idx] = val + 1;
+        // split in parts which match the rule and parts which do not
+        let (matching, residual) = match *rule {
+            None => return count + star_2_rec(workflows, next, parts),
+            Some((idx, Ordering::Less, val)) => parts.split(idx, val),
+            Some((idx, _, val)) => {
+                let (residual, matching) = parts.split(idx, val + 1);
+                (matching, residual)
             }
+        };
+
+        // recurse for matching parts, check next rule for residual parts
+        if !matching.is_empty() {
+            count += star_2_rec(workflows, next, matching);
+        }
+        if residual.is_empty() {
+            return count;
         }
+        parts = residual;
     }
 
     unreachable!("Eventually everything shall match")
 }
 
 pub fn star_2(PuzzleData(workflows, _): &PuzzleData) -> SolT {
-    star_2_rec(workflows, "in", [1; 4], [4001; 4])
+    star_2_rec(workflows, "in", Cuboid([Interval::new(1, 4001); 4]))
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData<'a>;
+    type Answer1 = SolT;
+    type Answer2 = SolT;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        PuzzleData::try_parse(input)
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -208,6 +243,18 @@ hdj{m>838:A,pv}
         println!("{data:?}");
     }
 
+    #[test]
+    pub fn test_parse_error() {
+        assert_eq!(
+            Some(ParseError::new(1, 5, "'<' or '>'")),
+            PuzzleData::try_parse("in{s=1351:px,qqz}\n\n{x=787,m=2655,a=1222,s=2876}").err()
+        );
+        assert_eq!(
+            Some(ParseError::new(3, 22, "four ratings")),
+            PuzzleData::try_parse("in{s<1351:px,qqz}\n\n{x=787,m=2655,a=1222}").err()
+        );
+    }
+
     #[test]
     pub fn test_star_1() {
         assert_eq!(19_114, star_1(&CONTENT.into()));
@@ -1,5 +1,6 @@
 use input::*;
-use std::{collections::VecDeque, fs::read_to_string};
+use mr_kaffee_utils::{parse::ParseError, solution::Solution};
+use std::collections::VecDeque;
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/20";
@@ -7,12 +8,15 @@ pub const IDENTIFIER: &str = "2023/20";
 pub type InputT = PuzzleData;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input20").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
 // tag::input[]
 pub mod input {
+    use mr_kaffee_utils::parse::ParseError;
+    use std::str::FromStr;
+
     #[derive(Debug, PartialEq, Eq)]
     pub struct PuzzleData {
         pub bc: usize,
@@ -29,21 +33,22 @@ pub mod input {
         id
     }
 
-    impl<T> From<&T> for PuzzleData
-    where
-        T: AsRef<str> + ?Sized,
-    {
-        fn from(s: &T) -> Self {
+    impl FromStr for PuzzleData {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
             let mut nodes = Vec::new();
             let mut targets = Vec::new();
             let mut types = Vec::new();
-            for line in s.as_ref().lines() {
-                let (source, targets_part) = line.split_once(" -> ").unwrap();
-                let (source_type, source) = match source.as_bytes()[0] {
-                    b'%' => (b'%', &source[1..]),
-                    b'&' => (b'&', &source[1..]),
-                    b if b.is_ascii_alphabetic() => (0, source),
-                    _ => panic!(),
+            for line in s.lines() {
+                let (source, targets_part) = line
+                    .split_once(" -> ")
+                    .ok_or_else(|| ParseError::after(s, line, "' -> '"))?;
+                let (source_type, source) = match source.as_bytes().first() {
+                    Some(b'%') => (b'%', &source[1..]),
+                    Some(b'&') => (b'&', &source[1..]),
+                    Some(b) if b.is_ascii_alphabetic() => (0, source),
+                    _ => return Err(ParseError::at(s, source, "'%', '&' or module name")),
                 };
 
                 let source_id = get_id(&mut nodes, source);
@@ -64,15 +69,24 @@ pub mod input {
             let bc = nodes
                 .iter()
                 .position(|&name| name == "broadcaster")
-                .unwrap();
+                .ok_or_else(|| ParseError::after(s, s, "'broadcaster' module"))?;
             let rx = nodes.iter().position(|&name| name == "rx");
 
-            Self {
+            Ok(Self {
                 bc,
                 rx,
                 targets,
                 types,
-            }
+            })
+        }
+    }
+
+    impl<T> From<&T> for PuzzleData
+    where
+        T: AsRef<str> + ?Sized,
+    {
+        fn from(s: &T) -> Self {
+            s.as_ref().parse().unwrap()
         }
     }
 }
@@ -107,8 +121,8 @@ pub fn init(targets: &[Vec<usize>], types: &[u8]) -> Vec<Mem> {
         })
         .collect::<Vec<_>>();
 
-    for k in 0..types.len() {
-        for &target in &targets[k] {
+    for (k, targets) in targets.iter().enumerate().take(types.len()) {
+        for &target in targets {
             if let Mem::Conjunction(ref mut vec) = &mut memories[target] {
                 vec.push((k, false));
             }
@@ -122,7 +136,7 @@ pub fn init(targets: &[Vec<usize>], types: &[u8]) -> Vec<Mem> {
 // tag::press_button[]
 pub fn press_button<F>(memories: &mut [Mem], bc: usize, targets: &[Vec<usize>], mut callback: F)
 where
-    F: FnMut(usize, usize, bool) -> (),
+    F: FnMut(usize, usize, bool),
 {
     let mut queue = VecDeque::new();
 
@@ -159,7 +173,7 @@ where
 struct SendCounter(usize, usize);
 
 impl SendCounter {
-    fn callback(&mut self) -> impl FnMut(usize, usize, bool) -> () + '_ {
+    fn callback(&mut self) -> impl FnMut(usize, usize, bool) + '_ {
         |_, _, value| self.count(value)
     }
 
@@ -211,10 +225,10 @@ pub fn find_sources(targets: &[Vec<usize>], target: usize) -> Vec<usize> {
 
 impl Sources {
     fn create(targets: &[Vec<usize>], rx: usize) -> Self {
-        let sources = find_sources(&targets, rx);
+        let sources = find_sources(targets, rx);
         assert_eq!(1, sources.len());
         let source = sources[0];
-        let source_sources = find_sources(&targets, source);
+        let source_sources = find_sources(targets, source);
         let values = vec![None; source_sources.len()];
 
         Self {
@@ -225,7 +239,7 @@ impl Sources {
         }
     }
 
-    fn callback(&mut self) -> impl FnMut(usize, usize, bool) -> () + '_ {
+    fn callback(&mut self) -> impl FnMut(usize, usize, bool) + '_ {
         |target, source, value| {
             if target == self.source && value {
                 let id = self
@@ -261,17 +275,43 @@ pub fn star_2(
         types,
     }: &PuzzleData,
 ) -> usize {
-    let mut memories = init(&targets, &types);
+    let mut memories = init(targets, types);
 
-    let mut sources = Sources::create(&targets, rx.unwrap());
+    let mut sources = Sources::create(targets, rx.unwrap());
     while !sources.done() {
         sources.button();
-        press_button(&mut memories, *bc, &targets, sources.callback());
+        press_button(&mut memories, *bc, targets, sources.callback());
     }
     sources.get()
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -316,6 +356,18 @@ mod tests {
         assert_eq!(exp, data);
     }
 
+    #[test]
+    pub fn test_parse_error() {
+        assert_eq!(
+            Some(ParseError::new(2, 1, "'%', '&' or module name")),
+            "broadcaster -> a\n#a -> b".parse::<PuzzleData>().err()
+        );
+        assert_eq!(
+            Some(ParseError::new(2, 8, "'broadcaster' module")),
+            "%a -> b\n%b -> a".parse::<PuzzleData>().err()
+        );
+    }
+
     #[test]
     pub fn test_star_1() {
         assert_eq!(32_000_000, star_1(&CONTENT_1.into()));
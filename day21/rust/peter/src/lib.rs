@@ -1,6 +1,5 @@
-use std::{
-    collections::{BinaryHeap, VecDeque},
-    fs::read_to_string,
+use mr_kaffee_utils::{
+    directions::Direction, grids::GridRef, parse::ParseError, search, solution::Solution,
 };
 
 // tag::prelude[]
@@ -9,25 +8,17 @@ pub const IDENTIFIER: &str = "2023/21";
 pub type InputT<'a> = Grid<'a>;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input21").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
 // tag::solution[]
 pub type Steps = u32;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-pub enum Heading {
-    East = 0,
-    North = 1,
-    West = 2,
-    South = 3,
-}
-
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Boundary {
     offset: usize,
-    heading: Heading,
+    heading: Direction,
     data: Vec<Steps>,
 }
 
@@ -36,16 +27,16 @@ impl Boundary {
         data: &[Steps],
         w: usize,
         h: usize,
-        heading: Heading,
+        heading: Direction,
         offset: usize,
     ) -> Self {
         let mut data: Vec<_> = match heading {
-            Heading::East => (0..h)
+            Direction::East => (0..h)
                 .map(|row_inv| data[w - 1 + (h - 1 - row_inv) * w])
                 .collect(),
-            Heading::North => (0..w).map(|col_inv| data[w - 1 - col_inv]).collect(),
-            Heading::West => (0..h).map(|row| data[row * w]).collect(),
-            Heading::South => (0..w).map(|col| data[col + (h - 1) * w]).collect(),
+            Direction::North => (0..w).map(|col_inv| data[w - 1 - col_inv]).collect(),
+            Direction::West => (0..h).map(|row| data[row * w]).collect(),
+            Direction::South => (0..w).map(|col| data[col + (h - 1) * w]).collect(),
         };
 
         let min_cost = *data.iter().min().unwrap();
@@ -69,9 +60,7 @@ impl Boundary {
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct Grid<'a> {
-    pub(crate) data: &'a [u8],
-    pub(crate) w: usize,
-    pub(crate) h: usize,
+    pub(crate) tiles: GridRef<'a>,
 }
 
 mod grid {
@@ -97,29 +86,39 @@ mod grid {
     }
 }
 
+impl<'a> Grid<'a> {
+    /// Parse the input, reporting anything that is not a rectangular grid of
+    /// '.', '#' or 'S' as error
+    pub fn try_parse<T>(value: &'a T) -> Result<Self, ParseError>
+    where
+        T: AsRef<[u8]> + 'a + ?Sized,
+    {
+        let tiles = GridRef::from_bytes(
+            value.as_ref(),
+            |b| matches!(b, b'.' | b'#' | b'S'),
+            "'.', '#' or 'S'",
+        )?;
+        Ok(Self { tiles })
+    }
+}
+
 impl<'a, T> From<&'a T> for Grid<'a>
 where
     T: AsRef<[u8]> + 'a + ?Sized,
 {
     fn from(value: &'a T) -> Self {
-        let data = value.as_ref();
-        let w = data.iter().position(|&b| b == b'\n').unwrap_or(data.len());
-        let h = (data.len() + 1) / (w + 1);
-        Self { data, w, h }
+        Self::try_parse(value).unwrap()
     }
 }
 
 impl Grid<'_> {
     pub fn get_start(&self) -> (usize, usize) {
-        self.data
-            .iter()
-            .position(|&b| b == b'S')
-            .map(|p| (p % (self.w + 1), p / (self.w + 1)))
-            .unwrap()
+        self.tiles.position(|&b| b == b'S').unwrap()
     }
 
     pub fn reachable_in_steps(&self, steps: usize) -> usize {
-        assert!(self.w == self.h);
+        let w = self.tiles.width();
+        assert!(w == self.tiles.height());
 
         let (mut total_count, boundaries) = self.center_tile_boundaries(steps);
 
@@ -138,9 +137,9 @@ impl Grid<'_> {
             let (_, costs) = self.next_boundary(&boundary, usize::MAX);
             let cost_counts = grid::cost_counts(&costs);
 
-            // fits if offset + (s - 1) * self.w + max_steps <= steps
-            // (s - 1) * self.w <= (steps - offset - max_steps)
-            let n = (steps + self.w).saturating_sub(offset + cost_counts.len() - 1) / self.w;
+            // fits if offset + (s - 1) * w + max_steps <= steps
+            // (s - 1) * w <= (steps - offset - max_steps)
+            let n = (steps + w).saturating_sub(offset + cost_counts.len() - 1) / w;
 
             // short cut as long as max_step fits
             if n > 0 {
@@ -150,7 +149,7 @@ impl Grid<'_> {
                 // sum of even numbers from 1 to n
                 let sum_even = 2 * (n / 2 + 1) * (n / 2) / 2;
                 // sum of odd numbers from 1 to n
-                let sum_odd = 2 * ((n + 1) / 2 + 1) * ((n + 1) / 2) / 2 - (n + 1) / 2;
+                let sum_odd = 2 * (n.div_ceil(2) + 1) * n.div_ceil(2) / 2 - n.div_ceil(2);
 
                 total_count += if (steps - offset) & 1 == 0 {
                     sum_odd * cost_counts_even + sum_even * cost_counts_odd
@@ -161,7 +160,7 @@ impl Grid<'_> {
 
             // do the counting for the remaining tiles
             for s in n + 1.. {
-                let off = offset + (s - 1) * self.w;
+                let off = offset + (s - 1) * w;
                 let count = grid::count_reachable(&costs, off, steps);
                 total_count += s * count;
 
@@ -195,23 +194,23 @@ impl Grid<'_> {
 
             let cost_counts = grid::cost_counts(&costs);
 
-            // fits if offset + (s - 1) * self.w + max_steps <= steps
-            // (s - 1) * self.w <= (steps - offset - max_steps)
-            let n = (steps + self.w).saturating_sub(offset + cost_counts.len() - 1) / self.w;
+            // fits if offset + (s - 1) * w + max_steps <= steps
+            // (s - 1) * w <= (steps - offset - max_steps)
+            let n = (steps + w).saturating_sub(offset + cost_counts.len() - 1) / w;
 
             if n > 0 {
                 let cost_counts_even = cost_counts.iter().step_by(2).skip(1).sum::<usize>();
                 let cost_counts_odd = cost_counts.iter().skip(1).step_by(2).sum::<usize>();
 
                 total_count += if (steps - offset) & 1 == 0 {
-                    (n + 1) / 2 * cost_counts_even + (n / 2) * cost_counts_odd
+                    n.div_ceil(2) * cost_counts_even + (n / 2) * cost_counts_odd
                 } else {
-                    (n + 1) / 2 * cost_counts_odd + (n / 2) * cost_counts_even
+                    n.div_ceil(2) * cost_counts_odd + (n / 2) * cost_counts_even
                 };
             }
 
             for s in n + 1.. {
-                let count = grid::count_reachable(&costs, offset + (s - 1) * self.w, steps);
+                let count = grid::count_reachable(&costs, offset + (s - 1) * w, steps);
                 total_count += count;
                 if count == 0 {
                     break;
@@ -227,83 +226,62 @@ impl Grid<'_> {
         boundary: &Boundary,
         steps: usize,
     ) -> (Option<Boundary>, Vec<Steps>) {
+        let (w, h) = (self.tiles.width(), self.tiles.height());
         let (off_c, fac_c, off_r, fac_r) = match boundary.heading {
-            Heading::East => (0, 0, self.h - 1, -1),
-            Heading::North => (self.w - 1, -1, self.h - 1, 0),
-            Heading::West => (self.w - 1, 0, 0, 1),
-            Heading::South => (0, 1, 0, 0),
+            Direction::East => (0, 0, h - 1, -1),
+            Direction::North => (w - 1, -1, h - 1, 0),
+            Direction::West => (w - 1, 0, 0, 1),
+            Direction::South => (0, 1, 0, 0),
         };
-        let (mut queue, mut costs) = boundary
+        let starts = boundary
             .data
             .iter()
             .enumerate()
             .map(|(v, &d)| {
                 (
-                    d.saturating_add(1),
                     (
                         off_c.wrapping_add_signed(fac_c * v as isize),
                         off_r.wrapping_add_signed(fac_r * v as isize),
                     ),
+                    d.saturating_add(1),
                 )
             })
-            .fold(
-                (BinaryHeap::new(), vec![Steps::MAX; self.w * self.h]),
-                |(mut heap, mut costs), (cost, (col, row))| {
-                    heap.push((!cost, (col, row)));
-                    costs[col + row * self.w] = cost;
-                    (heap, costs)
-                },
-            );
-
-        while let Some((cost, (col, row))) = queue.pop() {
-            if boundary.offset == usize::MAX || boundary.offset + !cost as usize >= steps {
-                break;
-            }
-
-            // add adjacents
-            for (col, row) in [(1, 0), (0, -1), (-1, 0), (0, 1)]
-                .into_iter()
-                .map(|(dc, dr)| (col.wrapping_add_signed(dc), row.wrapping_add_signed(dr)))
-                .filter(|&(col, row)| {
-                    (col < self.w && row < self.h) && self.data[col + row * (self.w + 1)] != b'#'
-                })
-            {
-                if costs[col + row * self.w] == Steps::MAX {
-                    costs[col + row * self.w] = !cost + 1;
-                    queue.push((cost - 1, (col, row)));
-                }
-            }
+            .collect::<Vec<_>>();
+
+        // plots beyond the total number of steps are not searched, the entry
+        // plots on the boundary keep their costs anyways
+        let max_cost = steps.saturating_sub(boundary.offset).min(Steps::MAX as _) as _;
+        let mut costs = vec![Steps::MAX; w * h];
+        for &((col, row), cost) in &starts {
+            costs[col + row * w] = cost;
+        }
+        for ((col, row), cost) in search::dijkstra_costs(
+            starts,
+            |&pos| self.adjacents(pos).map(|adj| (adj, 1)),
+            max_cost,
+        ) {
+            costs[col + row * w] = cost;
         }
 
-        let next = Boundary::from_matrix(&costs, self.w, self.h, boundary.heading, boundary.offset);
+        let next = Boundary::from_matrix(&costs, w, h, boundary.heading, boundary.offset);
 
         ((next.offset != usize::MAX).then_some(next), costs)
     }
 
+    /// The garden plots adjacent to `pos` on the tile
+    fn adjacents(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.tiles
+            .neighbours_4(pos)
+            .filter(|&adj| self.tiles[adj] != b'#')
+    }
+
     pub fn center_tile_costs(&self, steps: Steps) -> (Steps, Vec<Steps>) {
+        let (w, h) = (self.tiles.width(), self.tiles.height());
         let start = self.get_start();
-        let mut costs = vec![Steps::MAX; self.w * self.h];
-        let mut queue = VecDeque::new();
-        costs[start.0 + start.1 * self.w] = 0;
-        queue.push_back((0, start));
-
-        while let Some((cost, (col, row))) = queue.pop_front() {
-            if cost >= steps {
-                break;
-            }
-
-            for (col, row) in [(1, 0), (0, -1), (-1, 0), (0, 1)]
-                .into_iter()
-                .map(|(dc, dr)| (col.wrapping_add_signed(dc), row.wrapping_add_signed(dr)))
-                .filter(|&(col, row)| {
-                    (col < self.w && row < self.h) && self.data[col + row * (self.w + 1)] != b'#'
-                })
-            {
-                if costs[col + row * self.w] == Steps::MAX {
-                    costs[col + row * self.w] = cost + 1;
-                    queue.push_back((cost + 1, (col, row)));
-                }
-            }
+        let mut costs = vec![Steps::MAX; w * h];
+        for ((col, row), cost) in search::bfs_steps([start], |&pos| self.adjacents(pos), steps as _)
+        {
+            costs[col + row * w] = cost as _;
         }
 
         let count = costs
@@ -314,15 +292,16 @@ impl Grid<'_> {
     }
 
     pub fn center_tile_boundaries(&self, steps: usize) -> (usize, [Boundary; 4]) {
+        let (w, h) = (self.tiles.width(), self.tiles.height());
         let (count, costs) = self.center_tile_costs(steps.min(Steps::MAX as _) as _);
 
         (
             count as _,
             [
-                Boundary::from_matrix(&costs, self.w, self.h, Heading::East, 0),
-                Boundary::from_matrix(&costs, self.w, self.h, Heading::North, 0),
-                Boundary::from_matrix(&costs, self.w, self.h, Heading::West, 0),
-                Boundary::from_matrix(&costs, self.w, self.h, Heading::South, 0),
+                Boundary::from_matrix(&costs, w, h, Direction::East, 0),
+                Boundary::from_matrix(&costs, w, h, Direction::North, 0),
+                Boundary::from_matrix(&costs, w, h, Direction::West, 0),
+                Boundary::from_matrix(&costs, w, h, Direction::South, 0),
             ],
         )
     }
@@ -337,6 +316,32 @@ pub fn star_2(grid: &Grid) -> usize {
     grid.reachable_in_steps(26_501_365)
 }
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = Grid<'a>;
+    type Answer1 = Steps;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Grid::try_parse(input)
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -371,10 +376,15 @@ mod tests {
         // 3 3 0
         let data = [2, 1, 1, 2, 99, 0, 3, 3, 0];
 
-        for heading in [Heading::East, Heading::North, Heading::West, Heading::South] {
+        for heading in [
+            Direction::East,
+            Direction::North,
+            Direction::West,
+            Direction::South,
+        ] {
             let boundary = Boundary::from_matrix(&data, 3, 3, heading, 7);
             let (data, offset) = match heading {
-                Heading::South => (vec![3, 3, 0], 7),
+                Direction::South => (vec![3, 3, 0], 7),
                 heading => (vec![0, 0, 1], 7 + heading as usize),
             };
             assert_eq!(
@@ -389,11 +399,12 @@ mod tests {
     }
 
     pub fn do_test_next_boundary(grid: Grid) {
+        let (w, h) = (grid.tiles.width(), grid.tiles.height());
         for (heading, a, offset) in [
-            (Heading::East, grid.h, grid.w),
-            (Heading::North, grid.w, grid.h),
-            (Heading::West, grid.h, grid.w),
-            (Heading::South, grid.w, grid.h),
+            (Direction::East, h, w),
+            (Direction::North, w, h),
+            (Direction::West, h, w),
+            (Direction::South, w, h),
         ] {
             let boundary = Boundary {
                 data: (0..a as Steps).collect(),
@@ -429,20 +440,21 @@ mod tests {
         }
     }
 
-    pub fn multi_grid<'a>(grid: &Grid, n: usize) -> (Vec<u8>, usize, usize) {
-        let w = n * grid.w;
-        let h = n * grid.h;
+    pub fn multi_grid(grid: &Grid, n: usize) -> (Vec<u8>, usize, usize) {
+        let (w0, h0) = (grid.tiles.width(), grid.tiles.height());
+        let w = n * w0;
+        let h = n * h0;
         let mut data = vec![b'\n'; (w + 1) * h];
-        for row in 0..grid.h {
-            for col in 0..grid.w {
-                let d = grid.data[col + row * (grid.w + 1)];
+        for row in 0..h0 {
+            for col in 0..w0 {
+                let d = grid.tiles[(col, row)];
                 for y in 0..n {
                     for x in 0..n {
                         let d = match d {
                             b'S' if x != n / 2 || y != n / 2 => b'.',
                             b => b,
                         };
-                        data[(col + x * grid.w) + (row + y * grid.h) * (w + 1)] = d;
+                        data[(col + x * w0) + (row + y * h0) * (w + 1)] = d;
                     }
                 }
             }
@@ -457,16 +469,18 @@ mod tests {
         let data = read_input();
         let grid0: Grid = (&data).into();
         let (data, w, h) = multi_grid(&grid0, n);
-        let grid1 = Grid { data: &data, w, h };
+        let grid1 = Grid {
+            tiles: GridRef::with_stride(&data, w, h, w + 1),
+        };
 
         for steps in (40..=400).step_by(40) {
             let (count1, costs) = grid1.center_tile_costs(steps);
-            let enough_space = (0..grid1.w)
+            let enough_space = (0..w)
                 .map(|col| (col, 0))
-                .chain((0..grid1.h).map(|row| (grid1.w - 1, row)))
-                .chain((0..grid1.w).map(|col_inv| (grid1.w - 1 - col_inv, grid1.h - 1)))
-                .chain((0..grid1.h).map(|row_inv| (0, grid1.h - 1 - row_inv)))
-                .map(|(col, row)| costs[col + row * grid1.w])
+                .chain((0..h).map(|row| (w - 1, row)))
+                .chain((0..w).map(|col_inv| (w - 1 - col_inv, h - 1)))
+                .chain((0..h).map(|row_inv| (0, h - 1 - row_inv)))
+                .map(|(col, row)| costs[col + row * w])
                 .filter(|&d| d < Steps::MAX)
                 .count();
             assert_eq!(0, enough_space);
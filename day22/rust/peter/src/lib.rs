@@ -1,9 +1,8 @@
 use input::*;
+use mr_kaffee_utils::{intervals::Interval, parse::ParseError, solution::Solution, vector::Vec3D};
 use std::{
     cmp::Ordering,
     collections::{BinaryHeap, HashSet},
-    fs::read_to_string,
-    ops::RangeInclusive,
 };
 
 // tag::prelude[]
@@ -12,34 +11,57 @@ pub const IDENTIFIER: &str = "2023/22";
 pub type InputT = PuzzleData;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input22").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
 // tag::input[]
 pub mod input {
     use crate::{Brick, Point};
+    use mr_kaffee_utils::parse::{parse_token, ParseError};
+    use std::str::FromStr;
 
     #[derive(Debug)]
     pub struct PuzzleData(pub Vec<Brick>);
 
-    impl From<&str> for Point {
-        fn from(value: &str) -> Self {
-            let mut values = value.split(',').map(|v| v.parse().unwrap());
-            Self {
-                x: values.next().unwrap(),
-                y: values.next().unwrap(),
-                z: values.next().unwrap(),
-            }
+    fn parse_point(s: &str) -> Result<Point, ParseError> {
+        let mut values = s.split(',').map(|v| parse_token(s, v, "coordinate"));
+        let mut next = || {
+            values
+                .next()
+                .unwrap_or_else(|| Err(ParseError::after(s, s, "three coordinates")))
+        };
+        Ok([next()?, next()?, next()?].into())
+    }
+
+    impl FromStr for Brick {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let (a, b) = s
+                .split_once('~')
+                .ok_or_else(|| ParseError::after(s, s, "'~'"))?;
+            Ok(Self(
+                parse_point(a).map_err(|err| err.within(s, a))?,
+                parse_point(b).map_err(|err| err.within(s, b))?,
+            ))
         }
     }
 
     impl From<&str> for Brick {
         fn from(value: &str) -> Self {
-            value
-                .split_once('~')
-                .map(|(a, b)| Self(a.into(), b.into()))
-                .unwrap()
+            value.parse().unwrap()
+        }
+    }
+
+    impl FromStr for PuzzleData {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.lines()
+                .map(|line| line.parse().map_err(|err: ParseError| err.within(s, line)))
+                .collect::<Result<_, _>>()
+                .map(Self)
         }
     }
 
@@ -48,7 +70,7 @@ pub mod input {
         T: AsRef<str> + ?Sized,
     {
         fn from(s: &T) -> Self {
-            Self(s.as_ref().lines().map(Brick::from).collect())
+            s.as_ref().parse().unwrap()
         }
     }
 }
@@ -57,12 +79,7 @@ pub mod input {
 // tag::solution[]
 pub type Coord = usize;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
-pub struct Point {
-    pub x: Coord,
-    pub y: Coord,
-    pub z: Coord,
-}
+pub type Point = Vec3D<Coord>;
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 pub struct Brick(pub Point, pub Point);
@@ -72,26 +89,10 @@ pub struct SandStack {
     belows: Vec<Vec<(usize, Coord)>>,
 }
 
-trait Intersect {
-    fn intersect(&self, other: &Self) -> Self;
-}
-
-impl Intersect for RangeInclusive<Coord> {
-    fn intersect(&self, other: &Self) -> Self {
-        *self.start().max(other.start())..=*self.end().min(other.end())
-    }
-}
-
-impl From<(Coord, Coord, Coord)> for Point {
-    fn from((x, y, z): (Coord, Coord, Coord)) -> Self {
-        Self { x, y, z }
-    }
-}
-
 impl Brick {
     pub fn compare(&self, other: &Self) -> Option<(Ordering, Coord)> {
-        if !self.x_range().intersect(&other.x_range()).is_empty()
-            && !self.y_range().intersect(&other.y_range()).is_empty()
+        if self.x_range().intersects(&other.x_range())
+            && self.y_range().intersects(&other.y_range())
         {
             Some((
                 self.top_z().cmp(&other.top_z()),
@@ -102,20 +103,20 @@ impl Brick {
         }
     }
 
-    pub fn x_range(&self) -> RangeInclusive<Coord> {
-        self.0.x.min(self.1.x)..=self.0.x.max(self.1.x)
+    pub fn x_range(&self) -> Interval<Coord> {
+        (self.0.x().min(self.1.x())..=self.0.x().max(self.1.x())).into()
     }
 
-    pub fn y_range(&self) -> RangeInclusive<Coord> {
-        self.0.y.min(self.1.y)..=self.0.y.max(self.1.y)
+    pub fn y_range(&self) -> Interval<Coord> {
+        (self.0.y().min(self.1.y())..=self.0.y().max(self.1.y())).into()
     }
 
     pub fn top_z(&self) -> Coord {
-        self.0.z.max(self.1.z)
+        self.0.z().max(self.1.z())
     }
 
     pub fn bottom_z(&self) -> Coord {
-        self.0.z.min(self.1.z)
+        self.0.z().min(self.1.z())
     }
 }
 
@@ -157,8 +158,9 @@ impl SandStack {
         assert!(me.bottom_z() > z);
 
         offsets[k] = me.bottom_z() - z - 1;
-        me.0.z -= offsets[k];
-        me.1.z -= offsets[k];
+        let offset = Point::from((0, 0, offsets[k]));
+        me.0 -= offset;
+        me.1 -= offset;
 
         settled[k] = true;
     }
@@ -233,10 +235,47 @@ pub fn star_1_and_2(data: &PuzzleData) -> (usize, usize) {
         universe.count_disintegrateable(),
         universe.sum_count_falling(),
     )
-        .into()
+}
+
+pub fn star_1(data: &PuzzleData) -> usize {
+    let mut universe = SandStack::from(data);
+    universe.settle();
+    universe.count_disintegrateable()
+}
+
+pub fn star_2(data: &PuzzleData) -> usize {
+    let mut universe = SandStack::from(data);
+    universe.settle();
+    universe.sum_count_falling()
 }
 // end::solution[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -270,9 +309,31 @@ mod tests {
         println!("{data:?}");
     }
 
+    #[test]
+    pub fn test_parse_error() {
+        assert_eq!(
+            Some(ParseError::new(2, 9, "coordinate")),
+            "1,0,1~1,2,1\n0,0,2~2,x,2".parse::<PuzzleData>().err()
+        );
+        assert_eq!(
+            Some(ParseError::new(2, 4, "three coordinates")),
+            "1,0,1~1,2,1\n0,0~2,0,2\n".parse::<PuzzleData>().err()
+        );
+    }
+
     #[test]
     pub fn test_star_1_and_2() {
-        assert_eq!((5, 7), star_1_and_2(&CONTENT.into()).into());
+        assert_eq!((5, 7), star_1_and_2(&CONTENT.into()));
+    }
+
+    #[test]
+    pub fn test_star_1() {
+        assert_eq!(5, star_1(&CONTENT.into()));
+    }
+
+    #[test]
+    pub fn test_star_2() {
+        assert_eq!(7, star_2(&CONTENT.into()));
     }
 }
 // end::tests[]
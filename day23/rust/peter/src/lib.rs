@@ -1,5 +1,10 @@
 use input::*;
-use std::fs::read_to_string;
+use mr_kaffee_utils::{
+    bitset::{BitArray, BitSet, BitVec},
+    directions::Direction,
+    parse::ParseError,
+    solution::Solution,
+};
 use std::collections::{hash_map::Entry, HashMap};
 
 // tag::prelude[]
@@ -7,18 +12,38 @@ pub const IDENTIFIER: &str = "2023/23";
 
 pub type InputT<'a> = PuzzleData<'a>;
 
+/// Graph as list of nodes (col, row) and, per node, a list of adjacent nodes
+/// with the lengths of the paths to them (node index, length)
+pub type Graph = (Vec<(usize, usize)>, Vec<Vec<(usize, usize)>>);
+
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input23").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
 // tag::input[]
 pub mod input {
+    use mr_kaffee_utils::{grids::GridRef, parse::ParseError};
+
     #[derive(Debug)]
     pub struct PuzzleData<'a> {
-        pub data: &'a [u8],
-        pub w: usize,
-        pub h: usize,
+        pub grid: GridRef<'a>,
+    }
+
+    impl<'a> PuzzleData<'a> {
+        /// Parse the input, reporting anything that is not a rectangular grid of
+        /// '#', '.', '^', '>', 'v' or '<' as error
+        pub fn try_parse<T>(s: &'a T) -> Result<Self, ParseError>
+        where
+            T: AsRef<[u8]> + 'a + ?Sized,
+        {
+            let grid = GridRef::from_bytes(
+                s.as_ref(),
+                |b| matches!(b, b'#' | b'.' | b'^' | b'>' | b'v' | b'<'),
+                "'#', '.', '^', '>', 'v' or '<'",
+            )?;
+            Ok(Self { grid })
+        }
     }
 
     impl<'a, T> From<&'a T> for PuzzleData<'a>
@@ -26,10 +51,7 @@ pub mod input {
         T: AsRef<[u8]> + 'a + ?Sized,
     {
         fn from(s: &'a T) -> Self {
-            let data = s.as_ref();
-            let w = data.iter().position(|&b| b == b'\n').unwrap_or(data.len());
-            let h = (data.len() + 1) / (w + 1);
-            Self { data, w, h }
+            Self::try_parse(s).unwrap()
         }
     }
 }
@@ -37,53 +59,43 @@ pub mod input {
 
 // tag::solution[]
 impl PuzzleData<'_> {
-    const D: [(isize, isize, u8); 4] = [(1, 0, b'>'), (0, -1, b'^'), (-1, 0, b'<'), (0, 1, b'v')];
-
-    fn is_branch_point(&self, (col, row): (usize, usize)) -> bool {
-        Self::D
-            .iter()
-            .map(|(dc, dr, _)| (col.wrapping_add_signed(*dc), row.wrapping_add_signed(*dr)))
-            .filter(|&(col, row)| {
-                col < self.w && row < self.h && self.data[col + row * (self.w + 1)] != b'#'
-            })
+    fn is_branch_point(&self, pos: (usize, usize)) -> bool {
+        self.grid
+            .neighbours_4(pos)
+            .filter(|&adj| self.grid[adj] != b'#')
             .count()
             > 2
     }
 
     pub fn branch_points(&self) -> Vec<(usize, usize)> {
-        (0..self.w * self.h)
-            .map(|pos| (pos % self.w, pos / self.w))
+        self.grid
+            .positions()
             .filter(|&(col, row)| {
-                self.data[col + row * (self.w + 1)] != b'#'
-                    && (row == 0 || row == self.h - 1 || self.is_branch_point((col, row)))
+                self.grid[(col, row)] != b'#'
+                    && (row == 0
+                        || row == self.grid.height() - 1
+                        || self.is_branch_point((col, row)))
             })
             .collect::<Vec<_>>()
     }
 
     fn adj_iter(
         &self,
-        (col, row): (usize, usize),
+        pos: (usize, usize),
         ignore_slopes: bool,
     ) -> impl Iterator<Item = (usize, usize)> + '_ {
-        Self::D
-            .iter()
-            .map(move |&(dc, dr, ok)| {
-                (col.wrapping_add_signed(dc), row.wrapping_add_signed(dr), ok)
-            })
-            .filter(move |&(col, row, ok)| {
-                col < self.w
-                    && row < self.h
-                    && (ignore_slopes && self.data[col + row * (self.w + 1)] != b'#'
-                        || [b'.', ok].contains(&self.data[col + row * (self.w + 1)]))
+        // slopes may only be entered in the direction they point to
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.grid.step(pos, d).map(|adj| (adj, d.arrow())))
+            .filter(move |&(adj, ok)| {
+                ignore_slopes && self.grid[adj] != b'#' || [b'.', ok].contains(&self.grid[adj])
             })
-            .map(|(col, row, _)| (col, row))
+            .map(|(adj, _)| adj)
     }
 
     /// nodes are branch points and start (first) / target (last)
-    pub fn make_graph(
-        &self,
-        ignore_slopes: bool,
-    ) -> (Vec<(usize, usize)>, Vec<Vec<(usize, usize)>>) {
+    pub fn make_graph(&self, ignore_slopes: bool) -> Graph {
         let nodes = self.branch_points();
 
         // calculate length of (unique) paths between all nodes
@@ -110,43 +122,45 @@ impl PuzzleData<'_> {
     }
 }
 
-pub fn reachable(adj_masks: &[u64], idx: usize, seen: u64) -> u64 {
-    let mut queue = 1u64 << idx;
-    let mut reached = seen | queue;
-    while queue != 0 {
-        let cur = queue.trailing_zeros();
-        queue &= !(1 << cur);
+pub fn reachable<S: BitSet>(adj_masks: &[S], idx: usize, seen: &S) -> S {
+    let mut queue = S::with_capacity(adj_masks.len());
+    queue.insert(idx);
+    let mut reached = seen.clone();
+    reached.insert(idx);
+    while let Some(cur) = queue.first() {
+        queue.remove(cur);
 
-        let mask = adj_masks[cur as usize];
-        queue |= mask & !reached;
-        reached |= mask;
+        let mask = &adj_masks[cur];
+        let mut new = mask.clone();
+        new.difference_with(&reached);
+        queue.union_with(&new);
+        reached.union_with(mask);
     }
-    reached & !seen
+    reached.difference_with(seen);
+    reached
 }
 
-pub fn star(grid: &PuzzleData, ignore_slopes: bool) -> usize {
-    let (nodes, mut adjacents) = grid.make_graph(ignore_slopes);
-
-    // seen information is stored in bits of u64
-    assert!(nodes.len() <= 64);
+/// Length of the longest path from the first to the last node, storing sets of
+/// nodes in bit sets of type `S`
+pub fn longest_path<S: BitSet>(adjacents: &[Vec<(usize, usize)>]) -> usize {
+    let n = adjacents.len();
+    let target = n - 1;
 
-    let target = nodes.len() - 1;
-    if ignore_slopes {
-        // Idea taken from https://www.reddit.com/user/MattieShoes/
-        // At the last crossing before the target, we must go for the target
-        let (last_before_target, cost) = adjacents[target][0];
-        adjacents[last_before_target] = vec![(target, cost)];
-    }
-
-    let adj_masks: Vec<u64> = adjacents
+    let adj_masks: Vec<S> = adjacents
         .iter()
-        .map(|adjacents| adjacents.iter().fold(0, |mask, (adj, _)| mask | (1 << adj)))
+        .map(|adjacents| {
+            let mut mask = S::with_capacity(n);
+            adjacents.iter().for_each(|&(adj, _)| _ = mask.insert(adj));
+            mask
+        })
         .collect();
 
     let mut bests = HashMap::new();
 
     // find maximum
-    let mut queue = Vec::from([(0, 0, 1u64)]);
+    let mut start = S::with_capacity(n);
+    start.insert(0);
+    let mut queue = Vec::from([(0, 0, start)]);
     let mut max = 0;
     while let Some((cost, idx, seen)) = queue.pop() {
         if idx == target && cost > max {
@@ -155,8 +169,8 @@ pub fn star(grid: &PuzzleData, ignore_slopes: bool) -> usize {
         }
 
         // Efficient BFS to get reachable nodes
-        let reachable = reachable(&adj_masks, idx, seen);
-        if reachable & (1 << target) == 0 {
+        let reachable = reachable(&adj_masks, idx, &seen);
+        if !reachable.contains(target) {
             // Target is not reachable
             continue;
         }
@@ -173,13 +187,37 @@ pub fn star(grid: &PuzzleData, ignore_slopes: bool) -> usize {
         queue.extend(
             adjacents[idx]
                 .iter()
-                .filter(|(adj, _)| seen & (1 << adj) == 0)
-                .map(|&(adj, weight)| (cost + weight, adj, seen | 1 << adj)),
+                .filter(|&&(adj, _)| !seen.contains(adj))
+                .map(|&(adj, weight)| {
+                    let mut seen = seen.clone();
+                    seen.insert(adj);
+                    (cost + weight, adj, seen)
+                }),
         );
     }
     max
 }
 
+pub fn star(grid: &PuzzleData, ignore_slopes: bool) -> usize {
+    let (nodes, mut adjacents) = grid.make_graph(ignore_slopes);
+
+    let target = nodes.len() - 1;
+    if ignore_slopes {
+        // Idea taken from https://www.reddit.com/user/MattieShoes/
+        // At the last crossing before the target, we must go for the target
+        let (last_before_target, cost) = adjacents[target][0];
+        adjacents[last_before_target] = vec![(target, cost)];
+    }
+
+    // seen information is stored in bits, inline as long as the nodes fit in
+    // two words
+    match nodes.len() {
+        0..=64 => longest_path::<BitArray<1>>(&adjacents),
+        65..=128 => longest_path::<BitArray<2>>(&adjacents),
+        _ => longest_path::<BitVec>(&adjacents),
+    }
+}
+
 pub fn star_1(grid: &PuzzleData) -> usize {
     star(grid, false)
 }
@@ -189,6 +227,32 @@ pub fn star_2(grid: &PuzzleData) -> usize {
 }
 // end::solution[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData<'a>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        PuzzleData::try_parse(input)
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -228,8 +292,8 @@ mod tests {
     #[test]
     pub fn test_is_interesting() {
         let data = PuzzleData::from(CONTENT);
-        assert_eq!(true, data.is_branch_point((11, 3)));
-        assert_eq!(false, data.is_branch_point((4, 1)));
+        assert!(data.is_branch_point((11, 3)));
+        assert!(!data.is_branch_point((4, 1)));
     }
 
     #[test]
@@ -241,5 +305,23 @@ mod tests {
     pub fn test_star_2() {
         assert_eq!(154, star_2(&CONTENT.into()));
     }
+
+    #[test]
+    pub fn test_longest_path() {
+        // chain of diamonds 0 -> {1, 2} -> 3 -> {4, 5} -> 6 ... with more than
+        // 64 nodes, the lower arm (weights 2) is always longer
+        const DIAMONDS: usize = 40;
+        let mut adjacents = vec![Vec::new(); 3 * DIAMONDS + 1];
+        for k in (0..3 * DIAMONDS).step_by(3) {
+            for (arm, weight) in [(k + 1, 1), (k + 2, 2)] {
+                adjacents[k].push((arm, weight));
+                adjacents[arm].extend([(k, weight), (k + 3, weight)]);
+                adjacents[k + 3].push((arm, weight));
+            }
+        }
+
+        assert_eq!(4 * DIAMONDS, longest_path::<BitArray<2>>(&adjacents));
+        assert_eq!(4 * DIAMONDS, longest_path::<BitVec>(&adjacents));
+    }
 }
 // end::tests[]
@@ -1,6 +1,11 @@
 use input::*;
-use std::{cmp::Ordering, ops::RangeInclusive};
-use std::fs::read_to_string;
+use mr_kaffee_utils::{
+    linear::{self, LinearError},
+    parse::ParseError,
+    solution::Solution,
+    vector::Line,
+};
+use std::ops::RangeInclusive;
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/24";
@@ -8,7 +13,7 @@ pub const IDENTIFIER: &str = "2023/24";
 pub type InputT = PuzzleData;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input24").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 #[cfg(feature = "geometric")]
@@ -17,44 +22,55 @@ pub mod geometric;
 // tag::input[]
 pub type Coord = i64;
 pub type CoordE = i128;
-#[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
-pub struct PAndV<T> {
-    p: T,
-    v: T,
-}
-pub type Hail = [PAndV<Coord>; 3];
+pub type Hail = Line<Coord, 3>;
 
 pub mod input {
     use crate::Hail;
+    use mr_kaffee_utils::{
+        parse::{parse_token, ParseError},
+        vector::Line,
+    };
+    use std::str::FromStr;
 
     #[derive(Debug)]
     pub struct PuzzleData(pub Vec<Hail>);
 
+    fn parse_hail(input: &str, line: &str) -> Result<Hail, ParseError> {
+        let mut values = line.split(&[',', '@']).map(str::trim);
+        let mut coords = [0; 6];
+        for coord in coords.iter_mut() {
+            let value = values
+                .next()
+                .ok_or_else(|| ParseError::after(input, line, "six numbers"))?;
+            *coord = parse_token(input, value, "number")?;
+        }
+
+        match values.next() {
+            Some(value) => Err(ParseError::at(input, value, "end of line")),
+            None => Ok(Line::new(
+                [coords[0], coords[1], coords[2]].into(),
+                [coords[3], coords[4], coords[5]].into(),
+            )),
+        }
+    }
+
+    impl FromStr for PuzzleData {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.lines()
+                .map(|line| parse_hail(s, line))
+                .collect::<Result<_, _>>()
+                .map(Self)
+        }
+    }
+
     impl<T> From<&T> for PuzzleData
     where
         T: AsRef<str> + ?Sized,
     {
         fn from(s: &T) -> Self {
-            Self(
-                s.as_ref()
-                    .lines()
-                    .map(|line| {
-                        line.split(&[',', '@'])
-                            .map(str::trim)
-                            .map(str::parse)
-                            .map(Result::unwrap)
-                            .enumerate()
-                            .fold(Hail::default(), |mut h, (p, v)| {
-                                *match p {
-                                    0..=2 => &mut h[p].p,
-                                    3..=5 => &mut h[p - 3].v,
-                                    _ => panic!(),
-                                } = v;
-                                h
-                            })
-                    })
-                    .collect(),
-            )
+            s.as_ref().parse().unwrap()
         }
     }
 }
@@ -65,40 +81,30 @@ const X: usize = 0;
 const Y: usize = 1;
 const Z: usize = 2;
 
+/// The projection of a hail-stone's path to the x-y plane
+fn xy(h: &Hail) -> Line<Coord, 2> {
+    Line::new([h.p[X], h.p[Y]].into(), [h.v[X], h.v[Y]].into())
+}
+
 pub fn intersect_2d(
     h1: &Hail,
     h2: &Hail,
     x_range: &RangeInclusive<Coord>,
     y_range: &RangeInclusive<Coord>,
 ) -> bool {
-    let h1 = h1.map(|PAndV { p, v }| PAndV {
-        p: p as CoordE,
-        v: v as CoordE,
-    });
-    let h2 = h2.map(|PAndV { p, v }| PAndV {
-        p: p as CoordE,
-        v: v as CoordE,
-    });
-
-    let d = h2[X].v * h1[Y].v - h1[X].v * h2[Y].v;
-    if d == 0 {
+    let (l1, l2) = (xy(h1), xy(h2));
+
+    // check t >= 0 for both and p + v t in range with the denominator multiplied out
+    match l1.intersect_unreduced(&l2) {
         // parallel
-        h1[X].p == h2[X].p && h1[Y].p == h2[Y].p
-    } else {
-        let n1 = (h2[X].v * (h2[Y].p - h1[Y].p) - h2[Y].v * (h2[X].p - h1[X].p)) * d.signum();
-        let n2 = (h1[X].v * (h2[Y].p - h1[Y].p) - h1[Y].v * (h2[X].p - h1[X].p)) * d.signum();
-        let d = d.abs();
-
-        debug_assert_eq!(h1[X].p * d + h1[X].v * n1, h2[X].p * d + h2[X].v * n2);
-        debug_assert_eq!(h1[Y].p * d + h1[Y].v * n1, h2[Y].p * d + h2[Y].v * n2);
-
-        if n1 < 0 || n2 < 0 {
-            false
-        } else {
-            (*x_range.start() as CoordE * d..=*x_range.end() as CoordE * d)
-                .contains(&(h1[X].p * d + h1[X].v * n1))
-                && (*y_range.start() as CoordE * d..=*y_range.end() as CoordE * d)
-                    .contains(&(h1[Y].p * d + h1[Y].v * n1))
+        None => l1.p == l2.p,
+        Some((n1, n2, d)) => {
+            n1 >= 0
+                && n2 >= 0
+                && [(X, x_range), (Y, y_range)].into_iter().all(|(k, range)| {
+                    (*range.start() as CoordE * d..=*range.end() as CoordE * d)
+                        .contains(&(h1.p[k] as CoordE * d + h1.v[k] as CoordE * n1))
+                })
         }
     }
 }
@@ -127,47 +133,6 @@ pub fn star_1(PuzzleData(hails): &PuzzleData) -> usize {
 // end::star_1[]
 
 // tag::star_2[]
-pub fn solve<const N: usize, const M: usize>(mut mat: [[f64; M]; N]) -> [f64; N] {
-    assert!(M == N + 1, "M == N + 1 expected");
-
-    let mut h = 0;
-    let mut k = 0;
-
-    // gaussian eliminate
-    while h < N && k < M {
-        let i_max = (h..N)
-            .map(|i| (i, mat[i][k].abs()))
-            .max_by(|(_, v1), (_, v2)| v1.partial_cmp(v2).unwrap_or(Ordering::Equal))
-            .map(|(row, _)| row)
-            .unwrap();
-        if mat[i_max][k] == 0.0 {
-            k += 1;
-        } else {
-            (mat[i_max], mat[h]) = (mat[h], mat[i_max]);
-            for i in h + 1..N {
-                let f = mat[i][k] / mat[h][k];
-                mat[i][k] = 0.0;
-                for j in k + 1..M {
-                    mat[i][j] -= mat[h][j] * f;
-                }
-            }
-            h += 1;
-            k += 1;
-        }
-    }
-
-    // calculate solution
-    let mut r = [0.0; N];
-    for k in (0..N).rev() {
-        r[k] = mat[k][M - 1] / mat[k][k];
-        for row in mat[0..k].iter_mut() {
-            row[M - 1] -= row[k] * r[k];
-        }
-    }
-
-    r
-}
-
 pub fn star_2(PuzzleData(hails): &PuzzleData) -> Coord {
     // number of dimensions
     const N: usize = 3;
@@ -195,54 +160,65 @@ pub fn star_2(PuzzleData(hails): &PuzzleData) -> Coord {
     // - The problem has a unique solution
     // - Any solution satisfies the equations (we do not remove solutions by multiplying by 0)
     // => If the equations have a unique solution, it is the one we are looking for
-    let mut mat = [[0.0; 2 * N + 1]; 2 * N];
-    for k in 0..N {
-        let l = k + 1;
-        mat[k] = [
-            (hails[k][Y].v - hails[l][Y].v) as _,
-            (hails[l][Y].p - hails[k][Y].p) as _,
-            (hails[l][X].v - hails[k][X].v) as _,
-            (hails[k][X].p - hails[l][X].p) as _,
-            0.0,
-            0.0,
-            ((hails[l][X].v * hails[l][Y].p - hails[l][Y].v * hails[l][X].p)
-                - (hails[k][X].v * hails[k][Y].p - hails[k][Y].v * hails[k][X].p)) as _,
-        ];
-        mat[N + k] = [
-            0.0,
-            0.0,
-            (hails[k][Z].v - hails[l][Z].v) as _,
-            (hails[l][Z].p - hails[k][Z].p) as _,
-            (hails[l][Y].v - hails[k][Y].v) as _,
-            (hails[k][Y].p - hails[l][Y].p) as _,
-            ((hails[l][Y].v * hails[l][Z].p - hails[l][Z].v * hails[l][Y].p)
-                - (hails[k][Y].v * hails[k][Z].p - hails[k][Z].v * hails[k][Y].p)) as _,
-        ];
-    }
+    //
+    // The equations are solved exactly over the integers. If the first four hail-stones yield
+    // a singular system, the next four are tried.
+    let sol = hails
+        .windows(N + 1)
+        .map(|hails| {
+            let hails = hails
+                .iter()
+                .map(|h| Line::new(h.p.map(CoordE::from), h.v.map(CoordE::from)));
+            let mut mat = [[0; 2 * N + 1]; 2 * N];
+            for (k, (h_k, h_l)) in hails.clone().zip(hails.skip(1)).enumerate() {
+                // constant terms vx[i] y[i] - vy[i] x[i] and vy[i] z[i] - vz[i] y[i]
+                let (c_k, c_l) = (h_k.v.cross(h_k.p), h_l.v.cross(h_l.p));
+                mat[k] = [
+                    h_k.v[Y] - h_l.v[Y],
+                    h_l.p[Y] - h_k.p[Y],
+                    h_l.v[X] - h_k.v[X],
+                    h_k.p[X] - h_l.p[X],
+                    0,
+                    0,
+                    c_l[Z] - c_k[Z],
+                ];
+                mat[N + k] = [
+                    0,
+                    0,
+                    h_k.v[Z] - h_l.v[Z],
+                    h_l.p[Z] - h_k.p[Z],
+                    h_l.v[Y] - h_k.v[Y],
+                    h_k.p[Y] - h_l.p[Y],
+                    c_l[X] - c_k[X],
+                ];
+            }
+            linear::solve_integral(mat).and_then(|sol| {
+                let mut r = [0; 2 * N];
+                for (r, c) in r.iter_mut().zip(sol) {
+                    *r = Coord::try_from(c).map_err(|_| LinearError::Overflow)?;
+                }
+                Ok(r)
+            })
+        })
+        .find(|sol| sol != &Err(LinearError::Singular))
+        .expect("No regular system of equations")
+        .expect("No integral solution in range");
 
-    // solve the linear equations and transform to hail
-    let sol = solve(mat).map(|r| r.round() as Coord);
-    let r = [
-        PAndV {
-            p: sol[0],
-            v: sol[1],
-        },
-        PAndV {
-            p: sol[2],
-            v: sol[3],
-        },
-        PAndV {
-            p: sol[4],
-            v: sol[5],
-        },
-    ];
+    // transform to hail
+    let r = Hail::new(
+        [sol[0], sol[2], sol[4]].into(),
+        [sol[1], sol[3], sol[5]].into(),
+    );
 
     // check solution on all points
     debug_assert_eq!(
         None,
         hails
             .iter()
-            .map(|&h| [0, 1, 2].map(|k| (h[k].p - r[k].p, r[k].v - h[k].v)))
+            .map(|h| {
+                let (dp, dv) = (h.p - r.p, r.v - h.v);
+                [X, Y, Z].map(|k| (dp[k], dv[k]))
+            })
             .position(|deltas| {
                 // check t is the same for all coordinates with non-zero velocity delta
                 let (chk_1, _) = deltas
@@ -264,10 +240,36 @@ pub fn star_2(PuzzleData(hails): &PuzzleData) -> Coord {
         "Inconsistent solution"
     );
 
-    r.into_iter().map(|c| c.p).sum()
+    r.p.dot([1, 1, 1].into())
 }
 // end::star_2[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData;
+    type Answer1 = usize;
+    type Answer2 = Coord;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        input.parse()
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+
+    fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        Some(star_2(input))
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
@@ -286,36 +288,30 @@ mod tests {
         println!("{hails:?}");
         assert_eq!(
             vec![
-                [
-                    PAndV { p: 19, v: -2 },
-                    PAndV { p: 13, v: 1 },
-                    PAndV { p: 30, v: -2 }
-                ],
-                [
-                    PAndV { p: 18, v: -1 },
-                    PAndV { p: 19, v: -1 },
-                    PAndV { p: 22, v: -2 }
-                ],
-                [
-                    PAndV { p: 20, v: -2 },
-                    PAndV { p: 25, v: -2 },
-                    PAndV { p: 34, v: -4 }
-                ],
-                [
-                    PAndV { p: 12, v: -1 },
-                    PAndV { p: 31, v: -2 },
-                    PAndV { p: 28, v: -1 }
-                ],
-                [
-                    PAndV { p: 20, v: 1 },
-                    PAndV { p: 19, v: -5 },
-                    PAndV { p: 15, v: -3 }
-                ]
+                Line::new([19, 13, 30].into(), [-2, 1, -2].into()),
+                Line::new([18, 19, 22].into(), [-1, -1, -2].into()),
+                Line::new([20, 25, 34].into(), [-2, -2, -4].into()),
+                Line::new([12, 31, 28].into(), [-1, -2, -1].into()),
+                Line::new([20, 19, 15].into(), [1, -5, -3].into()),
             ],
             hails
         );
     }
 
+    #[test]
+    pub fn test_parse_error() {
+        assert_eq!(
+            Some(ParseError::new(2, 20, "six numbers")),
+            "19, 13, 30 @ -2,  1, -2\n18, 19, 22 @ -1, -1"
+                .parse::<PuzzleData>()
+                .err()
+        );
+        assert_eq!(
+            Some(ParseError::new(1, 14, "number")),
+            "19, 13, 30 @ x2,  1, -2".parse::<PuzzleData>().err()
+        );
+    }
+
     #[test]
     pub fn test_star_1() {
         const RANGE: RangeInclusive<Coord> = 7..=27;
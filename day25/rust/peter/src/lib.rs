@@ -1,65 +1,63 @@
 use input::PuzzleData;
-use std::fs::read_to_string;
+use mr_kaffee_utils::{parse::ParseError, solution::Solution};
+use std::convert::Infallible;
 
 // tag::prelude[]
 pub const IDENTIFIER: &str = "2023/25";
 
-pub type InputT = PuzzleData;
+pub type InputT<'a> = PuzzleData<'a>;
 
 pub fn read_input() -> String {
-    read_to_string("../../../inputs/input25").unwrap()
+    Day::read_input(None).unwrap()
 }
 // end::prelude[]
 
 // tag::input[]
 mod input {
-    use std::collections::HashMap;
-
-    pub struct PuzzleData(pub Vec<Vec<usize>>);
-
-    impl<T: AsRef<str>> From<T> for PuzzleData {
-        fn from(value: T) -> Self {
-            let mut adjacents = Vec::<Vec<usize>>::new();
-            let mut indices = HashMap::new();
-            for line in value.as_ref().lines() {
-                let mut parts = line
-                    .split::<&[char]>(&[' ', ':'])
-                    .map(str::trim)
-                    .filter(|v| !v.is_empty())
-                    .map(|key| {
-                        let idx = indices.len();
-                        *indices.entry(key).or_insert(idx)
-                    });
-                let key = parts.next().unwrap();
-                for value in parts {
-                    adjacents.resize(adjacents.len().max(key + 1).max(value + 1), Vec::new());
-                    adjacents[key].push(value);
-                    adjacents[value].push(key);
+    use mr_kaffee_utils::{
+        graph::{AdjacencyList, GraphBuilder},
+        parse::ParseError,
+    };
+
+    /// Undirected graph of components connected by wires of weight 1
+    pub struct PuzzleData<'a>(pub AdjacencyList<u16, &'a str>);
+
+    impl<'a> PuzzleData<'a> {
+        pub fn try_parse(s: &'a str) -> Result<Self, ParseError> {
+            let mut builder = GraphBuilder::undirected();
+            for line in s.lines() {
+                let (key, values) = line
+                    .split_once(':')
+                    .filter(|(key, _)| !key.trim().is_empty())
+                    .ok_or_else(|| ParseError::at(s, line, "'<component>: <components>'"))?;
+                let key = key.trim();
+                builder.vertex(key);
+                for value in values.split_ascii_whitespace() {
+                    builder.edge(key, value, 1);
                 }
             }
 
-            Self(adjacents)
+            Ok(Self(builder.build_list()))
+        }
+    }
+
+    impl<'a, T> From<&'a T> for PuzzleData<'a>
+    where
+        T: AsRef<str> + ?Sized,
+    {
+        fn from(s: &'a T) -> Self {
+            Self::try_parse(s.as_ref()).unwrap()
         }
     }
 }
 // end::input[]
 
 #[cfg(feature = "min-cut")]
-pub mod min_cut;
+pub fn star_1(PuzzleData(g): &PuzzleData) -> usize {
+    use mr_kaffee_utils::graph::{Graph, MinCut};
 
-#[cfg(feature = "min-cut")]
-pub fn star_1(PuzzleData(adjacents): &PuzzleData) -> usize {
-    use min_cut::MinCut;
-
-    let n = adjacents.len();
-    let labels = (0..n as u16).collect();
-    let adjacents = adjacents
-        .iter()
-        .map(|adjacents| adjacents.iter().map(|&idx| (idx, 1u16)).collect())
-        .collect();
-
-    let g = min_cut::adjacency_list::AdjacencyList { adjacents, labels };
-    let (w, p) = g.min_cut_with_bound(|(w, _)| w <= &3).unwrap();
+    let n = g.len();
+    let (w, p) = g.clone().min_cut_with_bound(|(w, _)| w <= &3).unwrap();
     assert_eq!(3, w);
 
     let l1 = p.len();
@@ -69,63 +67,46 @@ pub fn star_1(PuzzleData(adjacents): &PuzzleData) -> usize {
 // tag::star_1[]
 #[cfg(not(feature = "min-cut"))]
 mod direct_solution {
-    use std::collections::{HashMap, HashSet, VecDeque};
-
-    pub fn get_shortest_path(
-        adjacents: &[Vec<usize>],
-        forbidden: &[Vec<(usize, usize)>],
-        start: usize,
-        target: usize,
-    ) -> (Option<HashMap<usize, usize>>, usize) {
-        let mut seen = HashSet::from([start]);
-        let mut queue = VecDeque::from([start]);
-        let mut parents = HashMap::new();
-        let mut reached = 0;
-        while let Some(idx) = queue.pop_front() {
-            reached += 1;
-            if idx == target {
-                return (Some(parents), reached);
-            }
-
-            for &adj in adjacents[idx].iter().filter(|&&adj| {
-                !forbidden
-                    .iter()
-                    .any(|forbidden| forbidden.contains(&(adj.min(idx), adj.max(idx))))
-            }) {
-                if seen.insert(adj) {
-                    queue.push_back(adj);
-                    parents.insert(adj, idx);
-                }
-            }
-        }
+    use mr_kaffee_utils::{graph::Adjacents, search};
 
-        (None, reached)
+    fn is_allowed(forbidden: &[Vec<(usize, usize)>], idx: usize, adj: usize) -> bool {
+        !forbidden
+            .iter()
+            .any(|forbidden| forbidden.contains(&(adj.min(idx), adj.max(idx))))
     }
 
-    pub fn get_partitions_connected_by_three_paths(
-        adjacents: &[Vec<usize>],
+    pub fn get_partitions_connected_by_three_paths<G: Adjacents>(
+        g: &G,
         start: usize,
     ) -> (usize, usize) {
         let mut paths = [Vec::new(), Vec::new(), Vec::new()];
-        for target in 1..adjacents.len() - 1 {
+        for target in 1..g.len() - 1 {
             // find three disjoint paths
             for k in 0..3 {
-                let (Some(parents), _) = get_shortest_path(adjacents, &paths[0..k], start, target)
-                else {
+                let (forbidden, path_k) = paths.split_at_mut(k);
+                let forbidden = &*forbidden;
+                let Some((_, path)) = search::bfs(
+                    [start],
+                    |&idx| {
+                        let iter = g.adjacents(idx).map(|(adj, _)| adj);
+                        iter.filter(move |&adj| is_allowed(forbidden, idx, adj))
+                    },
+                    |&idx| idx == target,
+                ) else {
                     panic!("Less than three paths from {} to {}!", start, target);
                 };
-                paths[k].clear();
-                let mut cur = target;
-                while let Some(&parent) = parents.get(&cur) {
-                    let link = (cur.min(parent), cur.max(parent));
-                    paths[k].push(link);
-                    cur = parent;
-                }
+                path_k[0].clear();
+                path_k[0].extend(path.windows(2).map(|w| (w[0].min(w[1]), w[0].max(w[1]))));
             }
 
             // return partition sizes, if there is no fourth path
-            if let (None, reached) = get_shortest_path(adjacents, &paths, start, target) {
-                return (reached, adjacents.len() - reached);
+            let forbidden = &paths;
+            let reached = search::reachable([start], |&idx| {
+                let iter = g.adjacents(idx).map(|(adj, _)| adj);
+                iter.filter(move |&adj| is_allowed(forbidden, idx, adj))
+            });
+            if !reached.contains(&target) {
+                return (reached.len(), g.len() - reached.len());
             }
         }
 
@@ -134,13 +115,35 @@ mod direct_solution {
 }
 
 #[cfg(not(feature = "min-cut"))]
-pub fn star_1(PuzzleData(adjacents): &PuzzleData) -> usize {
+pub fn star_1(PuzzleData(g): &PuzzleData) -> usize {
     // get a size of partitions connected by three paths
-    let (p1, p2) = direct_solution::get_partitions_connected_by_three_paths(adjacents, 0);
+    let (p1, p2) = direct_solution::get_partitions_connected_by_three_paths(g, 0);
     p1 * p2
 }
 // end::star_1[]
 
+pub struct Day;
+
+impl Solution for Day {
+    const IDENTIFIER: &'static str = IDENTIFIER;
+
+    type Input<'a> = PuzzleData<'a>;
+    type Answer1 = usize;
+    type Answer2 = Infallible;
+
+    fn parse(input: &str) -> Self::Input<'_> {
+        input.into()
+    }
+
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        PuzzleData::try_parse(input)
+    }
+
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+        star_1(input)
+    }
+}
+
 // tag::tests[]
 #[cfg(test)]
 mod tests {
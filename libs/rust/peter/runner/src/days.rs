@@ -0,0 +1,71 @@
+//! Glue code calling into the solutions of the individual days
+use crate::{solve, timed, Run, Star};
+use mr_kaffee_utils::{inputs::input_file_name, solution::Solution};
+use std::{ops::RangeInclusive, path::Path};
+
+/// All days that have a solution
+pub const DAYS: RangeInclusive<u8> = 0..=25;
+
+/// Run the solution `S` on its input, read from `inputs` if given or from the
+/// default location otherwise
+pub fn run_solution<S: Solution>(inputs: Option<&Path>) -> Run {
+    let path = inputs.map(|inputs| inputs.join(input_file_name(S::day())));
+    let (input, read) = timed(|| S::read_input(path.as_deref()));
+    let input =
+        input.unwrap_or_else(|err| panic!("Could not read input for {}: {}", S::IDENTIFIER, err));
+    let (data, parse) = timed(|| S::try_parse(&input));
+    let data =
+        data.unwrap_or_else(|err| panic!("Could not parse input for {}: {}", S::IDENTIFIER, err));
+
+    let mut stars = vec![solve(1, || S::star_1(&data))];
+    let (answer, time) = timed(|| S::star_2(&data));
+    if let Some(answer) = answer {
+        stars.push(Star {
+            star: 2,
+            answer: answer.to_string(),
+            time,
+        });
+    }
+
+    Run {
+        identifier: S::IDENTIFIER,
+        day: S::day(),
+        parse: read + parse,
+        stars,
+    }
+}
+
+/// Run the solution for the given day with inputs read from the `inputs`
+/// directory if given, returns `None` if there is no solution for that day
+pub fn run(day: u8, inputs: Option<&Path>) -> Option<Run> {
+    const RUNS: [fn(Option<&Path>) -> Run; 26] = [
+        run_solution::<mr_kaffee_2023_00::Day>,
+        run_solution::<mr_kaffee_2023_01::Day>,
+        run_solution::<mr_kaffee_2023_02::Day>,
+        run_solution::<mr_kaffee_2023_03::Day>,
+        run_solution::<mr_kaffee_2023_04::Day>,
+        run_solution::<mr_kaffee_2023_05::Day>,
+        run_solution::<mr_kaffee_2023_06::Day>,
+        run_solution::<mr_kaffee_2023_07::Day>,
+        run_solution::<mr_kaffee_2023_08::Day>,
+        run_solution::<mr_kaffee_2023_09::Day>,
+        run_solution::<mr_kaffee_2023_10::Day>,
+        run_solution::<mr_kaffee_2023_11::Day>,
+        run_solution::<mr_kaffee_2023_12::Day>,
+        run_solution::<mr_kaffee_2023_13::Day>,
+        run_solution::<mr_kaffee_2023_14::Day>,
+        run_solution::<mr_kaffee_2023_15::Day>,
+        run_solution::<mr_kaffee_2023_16::Day>,
+        run_solution::<mr_kaffee_2023_17::Day>,
+        run_solution::<mr_kaffee_2023_18::Day>,
+        run_solution::<mr_kaffee_2023_19::Day>,
+        run_solution::<mr_kaffee_2023_20::Day>,
+        run_solution::<mr_kaffee_2023_21::Day>,
+        run_solution::<mr_kaffee_2023_22::Day>,
+        run_solution::<mr_kaffee_2023_23::Day>,
+        run_solution::<mr_kaffee_2023_24::Day>,
+        run_solution::<mr_kaffee_2023_25::Day>,
+    ];
+
+    RUNS.get(day as usize).map(|run| run(inputs))
+}
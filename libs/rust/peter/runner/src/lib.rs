@@ -0,0 +1,244 @@
+//! Run the solutions of several days of AoC 2023 from a single binary
+use mr_kaffee_utils::answers::{Answers, Check};
+use serde::Serialize;
+use std::{
+    collections::BTreeSet,
+    fmt::Display,
+    ops::RangeInclusive,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+pub mod days;
+
+/// Answer to a single star together with the time it took to compute it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Star {
+    pub star: u8,
+    pub answer: String,
+    pub time: Duration,
+}
+
+/// Answers and timings for a single day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Run {
+    pub identifier: &'static str,
+    pub day: u8,
+    pub parse: Duration,
+    pub stars: Vec<Star>,
+}
+
+impl Run {
+    /// Total time for parsing and solving all stars
+    pub fn total(&self) -> Duration {
+        self.stars.iter().map(|star| star.time).sum::<Duration>() + self.parse
+    }
+
+    /// One record per star, each repeating the parse time of the day
+    pub fn records(&self) -> Vec<Record<'_>> {
+        self.stars
+            .iter()
+            .map(|star| Record {
+                identifier: self.identifier,
+                day: self.day,
+                star: star.star,
+                answer: &star.answer,
+                parse_time_ns: self.parse.as_nanos() as u64,
+                solve_time_ns: star.time.as_nanos() as u64,
+            })
+            .collect()
+    }
+
+    /// Check the answers to all stars against the expected `answers`
+    pub fn check(&self, answers: &Answers) -> Vec<(&Star, Check)> {
+        self.stars
+            .iter()
+            .map(|star| (star, answers.check(self.day, star.star, &star.answer)))
+            .collect()
+    }
+}
+
+/// Machine-readable record of the answer to a single star
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record<'a> {
+    pub identifier: &'a str,
+    pub day: u8,
+    pub star: u8,
+    pub answer: &'a str,
+    pub parse_time_ns: u64,
+    pub solve_time_ns: u64,
+}
+
+/// Call `f` and return its result together with the elapsed time
+pub fn timed<T, F: FnOnce() -> T>(f: F) -> (T, Duration) {
+    let t = Instant::now();
+    let value = f();
+    (value, t.elapsed())
+}
+
+/// Solve a star by calling `f` and record its answer and elapsed time
+pub fn solve<T: Display, F: FnOnce() -> T>(star: u8, f: F) -> Star {
+    let (answer, time) = timed(f);
+    Star {
+        star,
+        answer: answer.to_string(),
+        time,
+    }
+}
+
+/// A selection of days, parsed from `all`, a single day `7` or a range of
+/// days `3-9` (inclusive)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DaySelection(pub RangeInclusive<u8>);
+
+/// The days that are run if nothing else is selected
+pub const ALL: RangeInclusive<u8> = 1..=25;
+
+impl FromStr for DaySelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |day: &str| {
+            day.trim()
+                .parse::<u8>()
+                .ok()
+                .filter(|day| days::DAYS.contains(day))
+                .ok_or_else(|| format!("Illegal day: '{}', expected 0 <= day <= 25.", day))
+        };
+
+        match s.split_once('-') {
+            _ if s == "all" => Ok(Self(ALL)),
+            Some((from, to)) => Ok(Self(parse(from)?..=parse(to)?)),
+            None => parse(s).map(|day| Self(day..=day)),
+        }
+    }
+}
+
+/// Collect the days from all selections in ascending order without
+/// duplicates; an empty list of selections yields [`ALL`] days
+pub fn selected_days(selections: &[DaySelection]) -> Vec<u8> {
+    if selections.is_empty() {
+        return ALL.collect();
+    }
+
+    selections
+        .iter()
+        .flat_map(|DaySelection(days)| days.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_day_selection_from_str() {
+        assert_eq!(Ok(DaySelection(ALL)), "all".parse());
+        assert_eq!(Ok(DaySelection(7..=7)), "7".parse());
+        assert_eq!(Ok(DaySelection(3..=9)), "3-9".parse());
+        assert_eq!(Ok(DaySelection(0..=0)), "00".parse());
+        assert!("26".parse::<DaySelection>().is_err());
+        assert!("x-3".parse::<DaySelection>().is_err());
+    }
+
+    #[test]
+    pub fn test_selected_days() {
+        assert_eq!(ALL.collect::<Vec<_>>(), selected_days(&[]));
+        assert_eq!(
+            vec![2, 3, 4, 5, 9],
+            selected_days(&[
+                DaySelection(9..=9),
+                DaySelection(2..=4),
+                DaySelection(3..=5)
+            ])
+        );
+    }
+
+    #[test]
+    pub fn test_run_total() {
+        let run = Run {
+            identifier: "2023/99",
+            day: 99,
+            parse: Duration::from_millis(1),
+            stars: vec![
+                solve(1, || 17),
+                Star {
+                    star: 2,
+                    answer: "42".to_string(),
+                    time: Duration::from_millis(2),
+                },
+            ],
+        };
+        assert!(run.total() >= Duration::from_millis(3));
+        assert_eq!("17", run.stars[0].answer);
+    }
+
+    #[test]
+    pub fn test_run_records() {
+        let run = Run {
+            identifier: "2023/07",
+            day: 7,
+            parse: Duration::from_micros(3),
+            stars: vec![Star {
+                star: 1,
+                answer: "17".to_string(),
+                time: Duration::from_nanos(1500),
+            }],
+        };
+
+        let records = run.records();
+        assert_eq!(
+            vec![Record {
+                identifier: "2023/07",
+                day: 7,
+                star: 1,
+                answer: "17",
+                parse_time_ns: 3000,
+                solve_time_ns: 1500,
+            }],
+            records
+        );
+        assert_eq!(
+            r#"{"identifier":"2023/07","day":7,"star":1,"answer":"17","parse_time_ns":3000,"solve_time_ns":1500}"#,
+            serde_json::to_string(&records[0]).unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_run_check() {
+        let run = Run {
+            identifier: "2023/07",
+            day: 7,
+            parse: Duration::ZERO,
+            stars: vec![solve(1, || 17), solve(2, || 42)],
+        };
+
+        let answers = "7 1 17\n7 2 43".parse().unwrap();
+        assert_eq!(
+            vec![
+                (1, Check::Match),
+                (
+                    2,
+                    Check::Mismatch {
+                        expected: "43".to_string()
+                    }
+                )
+            ],
+            run.check(&answers)
+                .into_iter()
+                .map(|(star, check)| (star.star, check))
+                .collect::<Vec<_>>()
+        );
+
+        let answers = "8 1 17".parse().unwrap();
+        assert_eq!(
+            vec![(1, Check::Unknown), (2, Check::Unknown)],
+            run.check(&answers)
+                .into_iter()
+                .map(|(star, check)| (star.star, check))
+                .collect::<Vec<_>>()
+        );
+    }
+}
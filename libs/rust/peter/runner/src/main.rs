@@ -0,0 +1,145 @@
+use clap::{Parser, ValueEnum};
+use mr_kaffee_2023::{days, selected_days, DaySelection, Run};
+use mr_kaffee_utils::answers::{answers_path, Answers, Check, ANSWERS_FILE};
+use std::{panic, path::PathBuf, process::ExitCode, time::Duration};
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    /// human readable table
+    Text,
+    /// one JSON record per star and line
+    Json,
+}
+
+#[derive(Parser, Debug)]
+struct Cli {
+    /// days to run: `all`, a single day like `7` or an inclusive range like
+    /// `3-9` (defaults to all)
+    days: Vec<DaySelection>,
+
+    /// directory containing the inputs `input[dd]` (defaults to the directory
+    /// given by the `AOC_INPUTS_DIR` environment variable or the repository's
+    /// `inputs` folder)
+    #[arg(short, long)]
+    inputs: Option<PathBuf>,
+
+    /// check the answers against the expected answers and exit with an error
+    /// if any of them differs
+    #[arg(short, long)]
+    check: bool,
+
+    /// file with expected answers `<day> <star> <answer>` used with `--check`
+    /// (defaults to `answers` in the inputs directory)
+    #[arg(short, long)]
+    answers: Option<PathBuf>,
+
+    /// output format
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+}
+
+fn print_row(day: &str, star: &str, answer: &str, time: &str) {
+    println!("{:<7} | {:<7} | {:>20} | {:>12}", day, star, answer, time);
+}
+
+fn print_run(run: &Run) {
+    print_row(run.identifier, "parse", "", &format!("{:?}", run.parse));
+    for star in &run.stars {
+        print_row(
+            "",
+            &star.star.to_string(),
+            &star.answer,
+            &format!("{:?}", star.time),
+        );
+    }
+}
+
+fn print_records(run: &Run) {
+    for record in run.records() {
+        println!("{}", serde_json::to_string(&record).unwrap());
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let answers = if cli.check {
+        let path = cli.answers.clone().unwrap_or_else(|| match &cli.inputs {
+            Some(inputs) => inputs.join(ANSWERS_FILE),
+            None => answers_path(),
+        });
+        match Answers::read(Some(&path)) {
+            Ok(answers) => Some(answers),
+            Err(err) => {
+                eprintln!("Could not read answers from {}: {}", path.display(), err);
+                return ExitCode::FAILURE;
+            }
+        }
+    } else {
+        None
+    };
+
+    let text = cli.format == Format::Text;
+    if text {
+        print_row("Day", "Star", "Answer", "Time");
+        println!("{:-<8}+{:-<9}+{:-<22}+{:-<13}", "", "", "", "");
+    }
+
+    let mut total = Duration::ZERO;
+    let mut failed = Vec::new();
+    let mut mismatches = Vec::new();
+    let mut unknown = Vec::new();
+    for day in selected_days(&cli.days) {
+        match panic::catch_unwind(|| days::run(day, cli.inputs.as_deref())) {
+            Ok(Some(run)) => {
+                total += run.total();
+                match cli.format {
+                    Format::Text => print_run(&run),
+                    Format::Json => print_records(&run),
+                }
+                for (star, check) in answers.iter().flat_map(|answers| run.check(answers)) {
+                    match check {
+                        Check::Match => (),
+                        Check::Mismatch { expected } => mismatches.push((
+                            run.identifier,
+                            star.star,
+                            expected,
+                            star.answer.clone(),
+                        )),
+                        Check::Unknown => unknown.push((run.identifier, star.star)),
+                    }
+                }
+            }
+            Ok(None) => unreachable!("Day selection contains unknown day {}", day),
+            Err(_) => {
+                if text {
+                    print_row(&format!("2023/{:02}", day), "", "failed", "");
+                }
+                failed.push(day);
+            }
+        }
+    }
+
+    if text {
+        println!("{:-<8}+{:-<9}+{:-<22}+{:-<13}", "", "", "", "");
+        print_row("Total", "", "", &format!("{:?}", total));
+    }
+
+    for (identifier, star) in &unknown {
+        eprintln!("No expected answer for {}, star {}", identifier, star);
+    }
+    for (identifier, star, expected, actual) in &mismatches {
+        eprintln!("Wrong answer for {}, star {}:", identifier, star);
+        eprintln!("- {}", expected);
+        eprintln!("+ {}", actual);
+    }
+    if !failed.is_empty() {
+        eprintln!("Failed days: {:?}", failed);
+    }
+
+    if failed.is_empty() && mismatches.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,155 @@
+//! Known-good answers to check solutions against
+//!
+//! Answers are read from a plain text file with one answer per line in the
+//! format `<day> <star> <answer>`, e.g., `7 2 250384185`. Empty lines and lines
+//! starting with `#` are ignored. Since answers depend on the inputs, the file
+//! is expected next to the inputs as `answers` in [`inputs_dir`] by default.
+use crate::{
+    inputs::inputs_dir,
+    parse::{parse_token, ParseError},
+};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::read_to_string,
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Name of the answers file in the inputs directory
+pub const ANSWERS_FILE: &str = "answers";
+
+/// Expected answers keyed by day and star
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers(pub BTreeMap<(u8, u8), String>);
+
+/// Outcome of checking an answer against the expected one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    /// The answer is as expected
+    Match,
+    /// The answer differs from the expected one
+    Mismatch { expected: String },
+    /// There is no expected answer
+    Unknown,
+}
+
+/// The default location of the answers file
+pub fn answers_path() -> PathBuf {
+    inputs_dir().join(ANSWERS_FILE)
+}
+
+impl Answers {
+    /// Read answers from `path` or, if no path is given, from [`answers_path`]
+    pub fn read(path: Option<&Path>) -> io::Result<Self> {
+        let answers = match path {
+            Some(path) => read_to_string(path)?,
+            None => read_to_string(answers_path())?,
+        };
+        answers
+            .parse()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// The expected answer for the given day and star, if any
+    pub fn get(&self, day: u8, star: u8) -> Option<&str> {
+        self.0.get(&(day, star)).map(String::as_str)
+    }
+
+    /// Check `answer` against the expected answer for the given day and star
+    pub fn check(&self, day: u8, star: u8, answer: &str) -> Check {
+        match self.get(day, star) {
+            Some(expected) if expected == answer => Check::Match,
+            Some(expected) => Check::Mismatch {
+                expected: expected.to_string(),
+            },
+            None => Check::Unknown,
+        }
+    }
+}
+
+impl FromStr for Answers {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                let mut parts = line.splitn(3, char::is_whitespace);
+                let mut next = |expected| {
+                    parts
+                        .next()
+                        .map(str::trim)
+                        .filter(|part| !part.is_empty())
+                        .ok_or_else(|| ParseError::after(s, line, expected))
+                };
+                let day = next("day")?;
+                let star = next("star")?;
+                let answer = next("answer")?;
+                Ok((
+                    (parse_token(s, day, "day")?, parse_token(s, star, "star")?),
+                    answer.to_string(),
+                ))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for ((day, star), answer) in &self.0 {
+            writeln!(f, "{} {} {}", day, star, answer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT: &str = r#"# answers for my inputs
+1 1 142
+1 2 281
+
+7 2 some answer with spaces
+"#;
+
+    #[test]
+    pub fn test_from_str() {
+        let answers = CONTENT.parse::<Answers>().unwrap();
+        assert_eq!(3, answers.0.len());
+        assert_eq!(Some("142"), answers.get(1, 1));
+        assert_eq!(Some("some answer with spaces"), answers.get(7, 2));
+        assert_eq!(None, answers.get(7, 1));
+        assert_eq!(Ok(answers.clone()), answers.to_string().parse());
+    }
+
+    #[test]
+    pub fn test_from_str_error() {
+        assert_eq!(
+            Err(ParseError::new(2, 4, "answer")),
+            "1 1 142\n1 2".parse::<Answers>()
+        );
+        assert_eq!(
+            Err(ParseError::new(1, 3, "star")),
+            "1 x 142".parse::<Answers>()
+        );
+    }
+
+    #[test]
+    pub fn test_check() {
+        let answers = CONTENT.parse::<Answers>().unwrap();
+        assert_eq!(Check::Match, answers.check(1, 1, "142"));
+        assert_eq!(
+            Check::Mismatch {
+                expected: "281".to_string()
+            },
+            answers.check(1, 2, "280")
+        );
+        assert_eq!(Check::Unknown, answers.check(2, 1, "8"));
+    }
+}
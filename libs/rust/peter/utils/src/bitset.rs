@@ -0,0 +1,480 @@
+//! Sets of small non-negative integers stored as bits
+//!
+//! There are two variants implementing the common [`BitSet`] trait:
+//! [`BitArray`] stores a fixed number of 64-bit words inline and is `Copy`,
+//! [`BitVec`] stores its words on the heap and grows as needed.
+//!
+//! # Examples
+//! ```
+//! # use mr_kaffee_utils::bitset::{BitArray, BitSet, BitVec};
+//! let a = BitArray::<2>::from_iter([1, 70, 3]);
+//! let b = BitArray::<2>::from_iter([3, 4]);
+//! assert_eq!(vec![1, 3, 4, 70], (a | b).iter().collect::<Vec<_>>());
+//! assert_eq!(vec![3], (a & b).iter().collect::<Vec<_>>());
+//! assert_eq!(vec![1, 70], (a - b).iter().collect::<Vec<_>>());
+//!
+//! let mut c = BitVec::default();
+//! c.insert(200);
+//! assert!(c.contains(200));
+//! assert_eq!(Some(200), c.first());
+//! ```
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Sub, SubAssign},
+};
+
+const BITS: usize = u64::BITS as usize;
+
+/// Common interface of [`BitArray`] and [`BitVec`]
+pub trait BitSet: Clone + Eq + Hash {
+    /// An empty set with room for elements `0..bits`
+    fn with_capacity(bits: usize) -> Self;
+
+    /// The words storing the bits, element `i` is bit `i % 64` of word
+    /// `i / 64`
+    fn words(&self) -> &[u64];
+
+    fn insert(&mut self, i: usize) -> bool;
+
+    fn remove(&mut self, i: usize) -> bool;
+
+    /// Add all elements of `other` to `self`
+    fn union_with(&mut self, other: &Self);
+
+    /// Remove all elements not in `other` from `self`
+    fn intersect_with(&mut self, other: &Self);
+
+    /// Remove all elements of `other` from `self`
+    fn difference_with(&mut self, other: &Self);
+
+    fn clear(&mut self);
+
+    fn contains(&self, i: usize) -> bool {
+        self.words()
+            .get(i / BITS)
+            .is_some_and(|w| w & (1 << (i % BITS)) != 0)
+    }
+
+    /// The number of elements
+    fn len(&self) -> usize {
+        self.words().iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.words().iter().all(|&w| w == 0)
+    }
+
+    /// The smallest element
+    fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    fn is_disjoint(&self, other: &Self) -> bool {
+        self.words()
+            .iter()
+            .zip(other.words())
+            .all(|(a, b)| a & b == 0)
+    }
+
+    fn is_subset(&self, other: &Self) -> bool {
+        self.words()
+            .iter()
+            .enumerate()
+            .all(|(k, a)| a & !other.words().get(k).unwrap_or(&0) == 0)
+    }
+
+    /// Iterate over elements in ascending order
+    fn iter(&self) -> Ones<'_> {
+        Ones::new(self.words())
+    }
+}
+
+/// Iterator over the set bits of a slice of words in ascending order
+#[derive(Debug, Clone)]
+pub struct Ones<'a> {
+    words: &'a [u64],
+    offset: usize,
+    cur: u64,
+}
+
+impl<'a> Ones<'a> {
+    pub fn new(words: &'a [u64]) -> Self {
+        let (cur, words) = words.split_first().map_or((0, words), |(&c, w)| (c, w));
+        Self {
+            words,
+            offset: 0,
+            cur,
+        }
+    }
+}
+
+impl Iterator for Ones<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.cur == 0 {
+            let (&cur, words) = self.words.split_first()?;
+            self.cur = cur;
+            self.words = words;
+            self.offset += BITS;
+        }
+        let bit = self.cur.trailing_zeros() as usize;
+        self.cur &= self.cur - 1;
+        Some(self.offset + bit)
+    }
+}
+
+/// Bit set with room for `64 * W` elements stored inline
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitArray<const W: usize>(pub [u64; W]);
+
+impl<const W: usize> BitArray<W> {
+    pub const CAPACITY: usize = W * BITS;
+
+    pub const fn new() -> Self {
+        Self([0; W])
+    }
+}
+
+impl<const W: usize> Default for BitArray<W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize> BitSet for BitArray<W> {
+    /// # Panics
+    /// If `bits` exceeds [`BitArray::CAPACITY`]
+    fn with_capacity(bits: usize) -> Self {
+        assert!(
+            bits <= Self::CAPACITY,
+            "{} bits do not fit in a BitArray<{}>",
+            bits,
+            W
+        );
+        Self::new()
+    }
+
+    fn words(&self) -> &[u64] {
+        &self.0
+    }
+
+    /// # Panics
+    /// If `i` is not less than [`BitArray::CAPACITY`]
+    fn insert(&mut self, i: usize) -> bool {
+        let w = &mut self.0[i / BITS];
+        let mask = 1 << (i % BITS);
+        let inserted = *w & mask == 0;
+        *w |= mask;
+        inserted
+    }
+
+    fn remove(&mut self, i: usize) -> bool {
+        match self.0.get_mut(i / BITS) {
+            Some(w) => {
+                let mask = 1 << (i % BITS);
+                let removed = *w & mask != 0;
+                *w &= !mask;
+                removed
+            }
+            None => false,
+        }
+    }
+
+    fn union_with(&mut self, other: &Self) {
+        self.0.iter_mut().zip(other.0).for_each(|(a, b)| *a |= b);
+    }
+
+    fn intersect_with(&mut self, other: &Self) {
+        self.0.iter_mut().zip(other.0).for_each(|(a, b)| *a &= b);
+    }
+
+    fn difference_with(&mut self, other: &Self) {
+        self.0.iter_mut().zip(other.0).for_each(|(a, b)| *a &= !b);
+    }
+
+    fn clear(&mut self) {
+        self.0 = [0; W];
+    }
+}
+
+/// Bit set stored on the heap, growing as elements are inserted
+///
+/// Trailing zero words are ignored in comparisons and hashing, so sets with
+/// the same elements are equal regardless of their capacity.
+#[derive(Clone, Default)]
+pub struct BitVec(Vec<u64>);
+
+impl BitVec {
+    /// The words up to and including the last non-zero word
+    fn trimmed(&self) -> &[u64] {
+        let len = self.0.iter().rposition(|&w| w != 0).map_or(0, |k| k + 1);
+        &self.0[..len]
+    }
+}
+
+impl BitSet for BitVec {
+    fn with_capacity(bits: usize) -> Self {
+        Self(vec![0; bits.div_ceil(BITS)])
+    }
+
+    fn words(&self) -> &[u64] {
+        &self.0
+    }
+
+    fn insert(&mut self, i: usize) -> bool {
+        if i / BITS >= self.0.len() {
+            self.0.resize(i / BITS + 1, 0);
+        }
+        let w = &mut self.0[i / BITS];
+        let mask = 1 << (i % BITS);
+        let inserted = *w & mask == 0;
+        *w |= mask;
+        inserted
+    }
+
+    fn remove(&mut self, i: usize) -> bool {
+        match self.0.get_mut(i / BITS) {
+            Some(w) => {
+                let mask = 1 << (i % BITS);
+                let removed = *w & mask != 0;
+                *w &= !mask;
+                removed
+            }
+            None => false,
+        }
+    }
+
+    fn union_with(&mut self, other: &Self) {
+        if other.0.len() > self.0.len() {
+            self.0.resize(other.0.len(), 0);
+        }
+        self.0.iter_mut().zip(&other.0).for_each(|(a, b)| *a |= b);
+    }
+
+    fn intersect_with(&mut self, other: &Self) {
+        self.0.truncate(other.0.len());
+        self.0.iter_mut().zip(&other.0).for_each(|(a, b)| *a &= b);
+    }
+
+    fn difference_with(&mut self, other: &Self) {
+        self.0.iter_mut().zip(&other.0).for_each(|(a, b)| *a &= !b);
+    }
+
+    fn clear(&mut self) {
+        self.0.fill(0);
+    }
+}
+
+impl PartialEq for BitVec {
+    fn eq(&self, other: &Self) -> bool {
+        self.trimmed() == other.trimmed()
+    }
+}
+
+impl Eq for BitVec {}
+
+impl Hash for BitVec {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.trimmed().hash(state);
+    }
+}
+
+macro_rules! impl_bitset_traits {
+    ([$($gen:tt)*] $t:ty) => {
+        impl<$($gen)*> FromIterator<usize> for $t {
+            fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+                let mut set = Self::default();
+                iter.into_iter().for_each(|i| _ = set.insert(i));
+                set
+            }
+        }
+
+        impl<$($gen)*> Extend<usize> for $t {
+            fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+                iter.into_iter().for_each(|i| _ = self.insert(i));
+            }
+        }
+
+        impl<'a, $($gen)*> IntoIterator for &'a $t {
+            type Item = usize;
+            type IntoIter = Ones<'a>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.iter()
+            }
+        }
+
+        impl<$($gen)*> fmt::Debug for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_set().entries(self.iter()).finish()
+            }
+        }
+
+        impl<$($gen)*> BitOrAssign<&$t> for $t {
+            fn bitor_assign(&mut self, rhs: &$t) {
+                self.union_with(rhs);
+            }
+        }
+
+        impl<$($gen)*> BitAndAssign<&$t> for $t {
+            fn bitand_assign(&mut self, rhs: &$t) {
+                self.intersect_with(rhs);
+            }
+        }
+
+        impl<$($gen)*> SubAssign<&$t> for $t {
+            fn sub_assign(&mut self, rhs: &$t) {
+                self.difference_with(rhs);
+            }
+        }
+
+        impl<$($gen)*> BitOr for &$t {
+            type Output = $t;
+
+            fn bitor(self, rhs: Self) -> Self::Output {
+                let mut set = self.clone();
+                set.union_with(rhs);
+                set
+            }
+        }
+
+        impl<$($gen)*> BitAnd for &$t {
+            type Output = $t;
+
+            fn bitand(self, rhs: Self) -> Self::Output {
+                let mut set = self.clone();
+                set.intersect_with(rhs);
+                set
+            }
+        }
+
+        impl<$($gen)*> Sub for &$t {
+            type Output = $t;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                let mut set = self.clone();
+                set.difference_with(rhs);
+                set
+            }
+        }
+    };
+}
+
+impl_bitset_traits!([const W: usize] BitArray<W>);
+impl_bitset_traits!([] BitVec);
+
+impl<const W: usize> BitOr for BitArray<W> {
+    type Output = Self;
+
+    fn bitor(mut self, rhs: Self) -> Self::Output {
+        self.union_with(&rhs);
+        self
+    }
+}
+
+impl<const W: usize> BitAnd for BitArray<W> {
+    type Output = Self;
+
+    fn bitand(mut self, rhs: Self) -> Self::Output {
+        self.intersect_with(&rhs);
+        self
+    }
+}
+
+impl<const W: usize> Sub for BitArray<W> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self.difference_with(&rhs);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn check_set_ops<S: BitSet + FromIterator<usize>>() {
+        let a = S::from_iter([0, 5, 63, 64, 100]);
+        let b = S::from_iter([5, 64, 127]);
+
+        assert_eq!(5, a.len());
+        assert!(a.contains(63) && a.contains(100) && !a.contains(1));
+        assert!(!a.contains(1000));
+        assert_eq!(Some(0), a.first());
+        assert_eq!(vec![0, 5, 63, 64, 100], a.iter().collect::<Vec<_>>());
+
+        let mut u = a.clone();
+        u.union_with(&b);
+        assert_eq!(vec![0, 5, 63, 64, 100, 127], u.iter().collect::<Vec<_>>());
+
+        let mut i = a.clone();
+        i.intersect_with(&b);
+        assert_eq!(vec![5, 64], i.iter().collect::<Vec<_>>());
+        assert!(i.is_subset(&a) && i.is_subset(&b) && !a.is_subset(&b));
+
+        let mut d = a.clone();
+        d.difference_with(&b);
+        assert_eq!(vec![0, 63, 100], d.iter().collect::<Vec<_>>());
+        assert!(d.is_disjoint(&b));
+
+        let mut s = S::with_capacity(128);
+        assert!(s.is_empty());
+        assert!(s.insert(7));
+        assert!(!s.insert(7));
+        assert!(s.remove(7));
+        assert!(!s.remove(7));
+        assert!(!s.remove(1000));
+        assert_eq!(None, s.first());
+
+        s.union_with(&a);
+        s.clear();
+        assert!(s.is_empty());
+    }
+
+    #[test]
+    pub fn test_bit_array() {
+        check_set_ops::<BitArray<2>>();
+
+        let a = BitArray::<2>::from_iter([1, 2]);
+        let b = BitArray::<2>::from_iter([2, 3]);
+        assert_eq!(BitArray::from_iter([1, 2, 3]), a | b);
+        assert_eq!(BitArray::from_iter([2]), a & b);
+        assert_eq!(BitArray::from_iter([1]), a - b);
+        assert_eq!("{1, 2}", format!("{:?}", a));
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn test_bit_array_capacity() {
+        BitArray::<1>::with_capacity(65);
+    }
+
+    #[test]
+    pub fn test_bit_vec() {
+        check_set_ops::<BitVec>();
+
+        // equality and hashing ignore capacity
+        let mut a = BitVec::with_capacity(1000);
+        a.insert(3);
+        let mut b = BitVec::default();
+        b.insert(3);
+        b.insert(500);
+        b.remove(500);
+        assert_eq!(a, b);
+        assert_eq!(1, HashSet::from([a.clone(), b]).len());
+
+        let c = BitVec::from_iter([3, 200]);
+        assert_eq!(BitVec::from_iter([3]), &a & &c);
+        assert_eq!(c, &a | &c);
+        assert_eq!(BitVec::from_iter([200]), &c - &a);
+
+        let mut d = a;
+        d |= &c;
+        d -= &BitVec::from_iter([3]);
+        assert_eq!(vec![200], d.iter().collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,152 @@
+//! Directions on a 2D grid
+//!
+//! Rows grow downwards, so moving [`Direction::North`] decrements the row.
+//! Directions are ordered counter-clockwise starting east, which is the order
+//! of the deltas in [`D4`].
+use crate::{grids::D4, parse::ParseError};
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    East = 0,
+    North = 1,
+    West = 2,
+    South = 3,
+}
+
+impl Direction {
+    pub const ALL: [Self; 4] = [Self::East, Self::North, Self::West, Self::South];
+
+    /// The direction with index `idx` modulo 4
+    pub fn from_index(idx: usize) -> Self {
+        Self::ALL[idx & 3]
+    }
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Parse a direction from an arrow (`>^<v`), a compass letter (`ENWS`) or
+    /// a letter for right, up, left or down (`RULD`)
+    pub fn from_byte(b: u8) -> Option<Self> {
+        match b {
+            b'>' | b'E' | b'R' => Some(Self::East),
+            b'^' | b'N' | b'U' => Some(Self::North),
+            b'<' | b'W' | b'L' => Some(Self::West),
+            b'v' | b'S' | b'D' => Some(Self::South),
+            _ => None,
+        }
+    }
+
+    /// The arrow pointing in this direction
+    pub fn arrow(self) -> u8 {
+        b">^<v"[self.index()]
+    }
+
+    /// Turn by 90° counter-clockwise
+    pub fn turn_left(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    /// Turn by 90° clockwise
+    pub fn turn_right(self) -> Self {
+        Self::from_index(self.index() + 3)
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::from_index(self.index() + 2)
+    }
+
+    /// Whether this is east or west
+    pub fn is_horizontal(self) -> bool {
+        self.index() & 1 == 0
+    }
+
+    /// The change in column and row for a step in this direction
+    pub fn delta(self) -> (isize, isize) {
+        D4[self.index()]
+    }
+
+    /// Take one step from `(col, row)`, `None` if this leaves the non-negative
+    /// quadrant
+    pub fn step(self, (col, row): (usize, usize)) -> Option<(usize, usize)> {
+        let (dc, dr) = self.delta();
+        Some((col.checked_add_signed(dc)?, row.checked_add_signed(dr)?))
+    }
+
+    /// Take `n` steps from `(x, y)`
+    pub fn steps(self, (x, y): (isize, isize), n: isize) -> (isize, isize) {
+        let (dx, dy) = self.delta();
+        (x + n * dx, y + n * dy)
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (self.arrow() as char).fmt(f)
+    }
+}
+
+impl FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.as_bytes() {
+            &[b] => Self::from_byte(b),
+            _ => None,
+        }
+        .ok_or_else(|| ParseError::new(1, 1, "one of '>^<v', 'ENWS' or 'RULD'"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_turn() {
+        use Direction::*;
+        assert_eq!(North, East.turn_left());
+        assert_eq!(East, South.turn_left());
+        assert_eq!(South, East.turn_right());
+        assert_eq!(West, East.reverse());
+        for d in Direction::ALL {
+            assert_eq!(d, d.turn_left().turn_right());
+            assert_eq!(d.reverse(), d.turn_left().turn_left());
+            assert_ne!(d.is_horizontal(), d.turn_left().is_horizontal());
+        }
+    }
+
+    #[test]
+    pub fn test_parse() {
+        use Direction::*;
+        assert_eq!(
+            vec![East, North, West, South],
+            b">^<v".map(|b| Direction::from_byte(b).unwrap())
+        );
+        assert_eq!(
+            vec![East, North, West, South],
+            b"ENWS".map(|b| Direction::from_byte(b).unwrap())
+        );
+        assert_eq!(
+            vec![East, North, West, South],
+            b"RULD".map(|b| Direction::from_byte(b).unwrap())
+        );
+        assert_eq!(None, Direction::from_byte(b'x'));
+        assert_eq!(Ok(South), "v".parse());
+        assert!("vv".parse::<Direction>().is_err());
+        for d in Direction::ALL {
+            assert_eq!(Ok(d), d.to_string().parse());
+        }
+    }
+
+    #[test]
+    pub fn test_step() {
+        use Direction::*;
+        assert_eq!(Some((2, 1)), East.step((1, 1)));
+        assert_eq!(Some((1, 0)), North.step((1, 1)));
+        assert_eq!(None, North.step((1, 0)));
+        assert_eq!(None, West.step((0, 1)));
+        assert_eq!((-2, 7), South.steps((-2, 3), 4));
+    }
+}
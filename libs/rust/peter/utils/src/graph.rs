@@ -0,0 +1,485 @@
+//! Graphs with labelled vertices and weighted edges
+//!
+//! Vertices are identified by indices `0..len`, each vertex has a label. The
+//! traits [`Graph`], [`Weight`], [`Adjacents`] and [`Merge`] are implemented by
+//! [`AdjacencyList`] and [`AdjacencyMatrix`], which are conveniently built from
+//! edges between labels with a [`GraphBuilder`].
+//!
+//! Undirected graphs store every edge in both directions. The algorithms in
+//! this module work on anything implementing [`Adjacents`].
+//!
+//! # Examples
+//! ```
+//! # use mr_kaffee_utils::graph::{self, Graph, GraphBuilder};
+//! let mut builder = GraphBuilder::directed();
+//! builder.edge("shirt", "tie", 1).edge("tie", "jacket", 1).edge("shirt", "belt", 1);
+//! let g = builder.build_list();
+//!
+//! let order = graph::topological_sort(&g).unwrap();
+//! let labels = order.iter().map(|&idx| *g.label(idx)).collect::<Vec<_>>();
+//! assert_eq!("shirt", labels[0]);
+//! assert!(labels.iter().position(|&l| l == "tie") < labels.iter().position(|&l| l == "jacket"));
+//! ```
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt::{Display, Write},
+    hash::Hash,
+};
+
+pub mod adjacency_list;
+pub mod adjacency_matrix;
+pub mod min_cut;
+
+pub use adjacency_list::AdjacencyList;
+pub use adjacency_matrix::AdjacencyMatrix;
+pub use min_cut::MinCut;
+
+pub trait Graph {
+    /// The type of edge weights.
+    type Weight;
+
+    /// The type of vertex labels.
+    type Label;
+
+    /// Get list of vertex labels.
+    fn vertex_labels(&self) -> &[Self::Label];
+
+    /// Get label of vertex at given index.
+    fn label(&self, idx: usize) -> &Self::Label {
+        &self.vertex_labels()[idx]
+    }
+
+    /// Get index for vertex with given label.
+    fn index_for_label(&self, label: &Self::Label) -> Option<usize>;
+
+    /// Get length of graph = number of vertices.
+    fn len(&self) -> usize;
+
+    /// Check whether the graph is trivial
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+pub trait Weight: Graph {
+    /// Get weight of edge between two vertices at given indices.
+    fn weight(&self, s_idx: usize, t_idx: usize) -> Self::Weight;
+}
+
+pub trait Adjacents: Graph {
+    /// Type of adjacents iterator
+    type AdjIt<'a>: Iterator<Item = (usize, Self::Weight)>
+    where
+        Self::Weight: 'a,
+        Self: 'a;
+
+    /// Get iterator over adjacents of node at given index
+    fn adjacents(&self, idx: usize) -> Self::AdjIt<'_>;
+}
+
+pub trait Merge {
+    /// Merge `t` into `s`. This will result in deleting `t`.
+    fn merge(&mut self, s: usize, t: usize);
+}
+
+/// Builder for graphs that assigns indices to vertex labels as they appear
+#[derive(Debug, Clone)]
+pub struct GraphBuilder<W, L> {
+    indices: HashMap<L, usize>,
+    labels: Vec<L>,
+    adjacents: Vec<Vec<(usize, W)>>,
+    directed: bool,
+}
+
+impl<W, L> GraphBuilder<W, L>
+where
+    W: Clone,
+    L: Clone + Eq + Hash,
+{
+    fn new(directed: bool) -> Self {
+        Self {
+            indices: HashMap::new(),
+            labels: Vec::new(),
+            adjacents: Vec::new(),
+            directed,
+        }
+    }
+
+    /// A builder adding every edge in both directions
+    pub fn undirected() -> Self {
+        Self::new(false)
+    }
+
+    /// A builder adding every edge in the given direction only
+    pub fn directed() -> Self {
+        Self::new(true)
+    }
+
+    /// Get the index of the vertex with the given label, adding a new vertex
+    /// if there is none yet
+    pub fn vertex(&mut self, label: L) -> usize {
+        let idx = self.labels.len();
+        let idx = *self.indices.entry(label.clone()).or_insert(idx);
+        if idx == self.labels.len() {
+            self.labels.push(label);
+            self.adjacents.push(Vec::new());
+        }
+        idx
+    }
+
+    /// Add an edge from `s` to `t`, adding vertices as needed
+    ///
+    /// There is no check for duplicate edges or edges connecting a vertex to
+    /// itself.
+    pub fn edge(&mut self, s: L, t: L, w: W) -> &mut Self {
+        let s_idx = self.vertex(s);
+        let t_idx = self.vertex(t);
+        if !self.directed {
+            self.adjacents[t_idx].push((s_idx, w.clone()));
+        }
+        self.adjacents[s_idx].push((t_idx, w));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn build_list(self) -> AdjacencyList<W, L> {
+        AdjacencyList::new(self.labels, self.adjacents)
+    }
+
+    /// Build an adjacency matrix, in which [`Default`] represents a missing
+    /// edge
+    ///
+    /// If there are duplicate edges, the one added last determines the
+    /// weight.
+    pub fn build_matrix(self) -> AdjacencyMatrix<W, L>
+    where
+        W: Default,
+    {
+        let n = self.labels.len();
+        let mut matrix = vec![vec![W::default(); n]; n];
+        for (s_idx, adjacents) in self.adjacents.into_iter().enumerate() {
+            for (t_idx, w) in adjacents {
+                matrix[s_idx][t_idx] = w;
+            }
+        }
+        AdjacencyMatrix {
+            matrix,
+            labels: self.labels,
+        }
+    }
+}
+
+impl<W, L> Extend<((L, L), W)> for GraphBuilder<W, L>
+where
+    W: Clone,
+    L: Clone + Eq + Hash,
+{
+    fn extend<T: IntoIterator<Item = ((L, L), W)>>(&mut self, iter: T) {
+        for ((s, t), w) in iter {
+            self.edge(s, t, w);
+        }
+    }
+}
+
+/// Connected components, ignoring the direction of edges
+///
+/// Components are ordered by their smallest vertex index, vertices within a
+/// component are sorted.
+pub fn connected_components<G: Adjacents>(g: &G) -> Vec<Vec<usize>> {
+    // union-find with the smallest index as representative
+    fn find(parents: &mut [usize], mut idx: usize) -> usize {
+        while parents[idx] != idx {
+            parents[idx] = parents[parents[idx]];
+            idx = parents[idx];
+        }
+        idx
+    }
+
+    let mut parents = (0..g.len()).collect::<Vec<_>>();
+    for s_idx in 0..g.len() {
+        for (t_idx, _) in g.adjacents(s_idx) {
+            let (a, b) = (find(&mut parents, s_idx), find(&mut parents, t_idx));
+            parents[a.max(b)] = a.min(b);
+        }
+    }
+
+    let mut components: Vec<Vec<usize>> = Vec::new();
+    let mut component_indices = vec![usize::MAX; g.len()];
+    for idx in 0..g.len() {
+        let root = find(&mut parents, idx);
+        if root == idx {
+            component_indices[root] = components.len();
+            components.push(Vec::new());
+        }
+        components[component_indices[root]].push(idx);
+    }
+    components
+}
+
+/// Order vertices so that every vertex comes before its adjacents
+///
+/// Return `None` if the graph has a cycle, which includes any undirected
+/// graph with at least one edge.
+pub fn topological_sort<G: Adjacents>(g: &G) -> Option<Vec<usize>> {
+    let mut in_degrees = vec![0; g.len()];
+    for idx in 0..g.len() {
+        for (adj, _) in g.adjacents(idx) {
+            in_degrees[adj] += 1;
+        }
+    }
+
+    let mut queue = (0..g.len())
+        .filter(|&idx| in_degrees[idx] == 0)
+        .collect::<VecDeque<_>>();
+    let mut order = Vec::with_capacity(g.len());
+    while let Some(idx) = queue.pop_front() {
+        order.push(idx);
+        for (adj, _) in g.adjacents(idx) {
+            in_degrees[adj] -= 1;
+            if in_degrees[adj] == 0 {
+                queue.push_back(adj);
+            }
+        }
+    }
+
+    (order.len() == g.len()).then_some(order)
+}
+
+/// Strongly connected components using Tarjan's algorithm
+///
+/// Components are returned in reverse topological order, i.e., there are no
+/// edges from a component to any component after it. Vertices within a
+/// component are sorted.
+///
+/// See <https://en.wikipedia.org/wiki/Tarjan%27s_strongly_connected_components_algorithm>
+pub fn strongly_connected_components<G: Adjacents>(g: &G) -> Vec<Vec<usize>> {
+    const UNSEEN: usize = usize::MAX;
+
+    let mut indices = vec![UNSEEN; g.len()];
+    let mut low_links = vec![UNSEEN; g.len()];
+    let mut on_stack = vec![false; g.len()];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+    let mut counter = 0;
+
+    for root in 0..g.len() {
+        if indices[root] != UNSEEN {
+            continue;
+        }
+
+        // explicit call stack to avoid deep recursion
+        let mut calls = Vec::from([(root, g.adjacents(root))]);
+        indices[root] = counter;
+        low_links[root] = counter;
+        counter += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        while let Some((idx, adjacents)) = calls.last_mut() {
+            let idx = *idx;
+            match adjacents.next() {
+                Some((adj, _)) if indices[adj] == UNSEEN => {
+                    indices[adj] = counter;
+                    low_links[adj] = counter;
+                    counter += 1;
+                    stack.push(adj);
+                    on_stack[adj] = true;
+                    calls.push((adj, g.adjacents(adj)));
+                }
+                Some((adj, _)) if on_stack[adj] => {
+                    low_links[idx] = low_links[idx].min(indices[adj]);
+                }
+                Some(_) => (),
+                None => {
+                    calls.pop();
+                    if let Some(&(parent, _)) = calls.last() {
+                        low_links[parent] = low_links[parent].min(low_links[idx]);
+                    }
+
+                    if low_links[idx] == indices[idx] {
+                        let mut component = Vec::new();
+                        while let Some(member) = stack.pop() {
+                            on_stack[member] = false;
+                            component.push(member);
+                            if member == idx {
+                                break;
+                            }
+                        }
+                        component.sort_unstable();
+                        components.push(component);
+                    }
+                }
+            }
+        }
+    }
+
+    components
+}
+
+/// Render a graph in the [DOT language](https://graphviz.org/doc/info/lang.html)
+///
+/// Vertices are labelled with their labels, edges with their weights. If the
+/// graph is not `directed`, edges stored in both directions are written once.
+pub fn to_dot<G>(g: &G, directed: bool) -> String
+where
+    G: Adjacents,
+    G::Label: Display,
+    G::Weight: Display,
+{
+    fn escape<T: Display>(value: T) -> String {
+        value.to_string().replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    let (kind, op) = if directed {
+        ("digraph", "->")
+    } else {
+        ("graph", "--")
+    };
+
+    let mut dot = format!("{} {{\n", kind);
+    for idx in 0..g.len() {
+        writeln!(dot, "    {} [label=\"{}\"];", idx, escape(g.label(idx))).unwrap();
+    }
+    for s_idx in 0..g.len() {
+        for (t_idx, w) in g
+            .adjacents(s_idx)
+            .filter(|&(t_idx, _)| directed || s_idx <= t_idx)
+        {
+            writeln!(
+                dot,
+                "    {} {} {} [label=\"{}\"];",
+                s_idx,
+                op,
+                t_idx,
+                escape(w)
+            )
+            .unwrap();
+        }
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_builder() {
+        let mut builder = GraphBuilder::undirected();
+        assert_eq!(0, builder.vertex("a"));
+        builder.edge("b", "a", 2).edge("b", "c", 3);
+        assert_eq!(0, builder.vertex("a"));
+        assert_eq!(3, builder.len());
+
+        let list = builder.clone().build_list();
+        assert_eq!(&["a", "b", "c"], list.vertex_labels());
+        assert_eq!(2, list.weight(0, 1));
+        assert_eq!(2, list.weight(1, 0));
+        assert_eq!(0, list.weight(0, 2));
+
+        let matrix = builder.build_matrix();
+        assert_eq!(Some(2), matrix.index_for_label(&"c"));
+        assert_eq!(3, matrix.weight(2, 1));
+
+        let mut builder = GraphBuilder::directed();
+        builder.extend([(("a", "b"), 1), (("b", "c"), 1)]);
+        let list = builder.build_list();
+        assert_eq!(vec![(1, 1)], list.adjacents(0).collect::<Vec<_>>());
+        assert_eq!(0, list.adjacents(2).count());
+    }
+
+    #[test]
+    pub fn test_connected_components() {
+        let mut builder = GraphBuilder::undirected();
+        builder.vertex('x');
+        builder.extend([
+            (('a', 'b'), 1),
+            (('c', 'd'), 1),
+            (('d', 'b'), 1),
+            (('e', 'f'), 1),
+        ]);
+        let g = builder.build_list();
+        assert_eq!(
+            vec![vec![0], vec![1, 2, 3, 4], vec![5, 6]],
+            connected_components(&g)
+        );
+
+        // directions are ignored
+        let mut builder = GraphBuilder::directed();
+        builder.extend([((0, 1), 1), ((2, 1), 1), ((3, 4), 1)]);
+        assert_eq!(
+            vec![vec![0, 1, 2], vec![3, 4]],
+            connected_components(&builder.build_list())
+        );
+    }
+
+    fn directed(edges: &[(usize, usize)], n: usize) -> AdjacencyList<u8, usize> {
+        let mut builder = GraphBuilder::directed();
+        (0..n).for_each(|idx| _ = builder.vertex(idx));
+        builder.extend(edges.iter().map(|&e| (e, 1)));
+        builder.build_list()
+    }
+
+    #[test]
+    pub fn test_topological_sort() {
+        let g = directed(&[(3, 1), (1, 0), (3, 2), (2, 0), (4, 3)], 5);
+        let order = topological_sort(&g).unwrap();
+        let pos = |idx| order.iter().position(|&k| k == idx).unwrap();
+        for s_idx in 0..g.len() {
+            for (t_idx, _) in g.adjacents(s_idx) {
+                assert!(pos(s_idx) < pos(t_idx));
+            }
+        }
+
+        let g = directed(&[(0, 1), (1, 2), (2, 0), (3, 0)], 4);
+        assert_eq!(None, topological_sort(&g));
+
+        let g = AdjacencyList::from([((0, 1), 1)]);
+        assert_eq!(None, topological_sort(&g));
+    }
+
+    #[test]
+    pub fn test_strongly_connected_components() {
+        // 0 <-> 1 -> 2 <-> 3 -> 4, 5 -> 5
+        let g = directed(&[(0, 1), (1, 0), (1, 2), (2, 3), (3, 2), (3, 4), (5, 5)], 6);
+        assert_eq!(
+            vec![vec![4], vec![2, 3], vec![0, 1], vec![5]],
+            strongly_connected_components(&g)
+        );
+
+        // a long cycle does not overflow the stack
+        let n = 100_000;
+        let edges = (0..n).map(|k| (k, (k + 1) % n)).collect::<Vec<_>>();
+        let g = directed(&edges, n);
+        let components = strongly_connected_components(&g);
+        assert_eq!(1, components.len());
+        assert_eq!(n, components[0].len());
+    }
+
+    #[test]
+    pub fn test_to_dot() {
+        let mut builder = GraphBuilder::undirected();
+        builder.edge("a", "b \"2\"", 5);
+        let g = builder.build_list();
+        assert_eq!(
+            "graph {\n    0 [label=\"a\"];\n    1 [label=\"b \\\"2\\\"\"];\n    0 -- 1 [label=\"5\"];\n}\n",
+            to_dot(&g, false)
+        );
+
+        let mut builder = GraphBuilder::directed();
+        builder.edge(1, 2, 'x').edge(2, 1, 'y');
+        let g = builder.build_list();
+        assert_eq!(
+            "digraph {\n    0 [label=\"1\"];\n    1 [label=\"2\"];\n    0 -> 1 [label=\"x\"];\n    1 -> 0 [label=\"y\"];\n}\n",
+            to_dot(&g, true)
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use super::{Adjacents, Graph, GraphBuilder, Merge, Weight};
+use std::{hash::Hash, iter::Copied, ops::AddAssign, slice::Iter};
+
+#[derive(Debug, Clone)]
+pub struct AdjacencyList<W, L> {
+    pub(crate) adjacents: Vec<Vec<(usize, W)>>,
+    pub(crate) labels: Vec<L>,
+}
+
+impl<W, L> FromIterator<((L, L), W)> for AdjacencyList<W, L>
+where
+    W: Copy,
+    L: Copy + Eq + Hash,
+{
+    /// See [`AdjacencyList::from`]
+    fn from_iter<T: IntoIterator<Item = ((L, L), W)>>(iter: T) -> Self {
+        iter.into()
+    }
+}
+
+impl<W, L, T> From<T> for AdjacencyList<W, L>
+where
+    W: Copy,
+    L: Copy + Eq + Hash,
+    T: IntoIterator<Item = ((L, L), W)>,
+{
+    /**
+     * Create an adjacency list from an iterator over edges.
+     *
+     * It is an error if the iterator yields two edge connecting the
+     * same vertices or an edge connecting a vertex to itself. This
+     * will result in an inconsistent adjacency list.
+     *
+     * You can verify consistency with [`AdjacencyList::consistent`]
+     */
+    fn from(value: T) -> Self {
+        let mut builder = GraphBuilder::undirected();
+        builder.extend(value);
+        builder.build_list()
+    }
+}
+
+impl<W, L> Graph for AdjacencyList<W, L>
+where
+    W: Copy + Default,
+    L: PartialEq,
+{
+    type Weight = W;
+    type Label = L;
+
+    fn vertex_labels(&self) -> &[Self::Label] {
+        &self.labels
+    }
+
+    fn index_for_label(&self, label: &Self::Label) -> Option<usize> {
+        self.labels.iter().position(|l| l == label)
+    }
+
+    fn len(&self) -> usize {
+        self.labels.len()
+    }
+}
+
+impl<W, L> Weight for AdjacencyList<W, L>
+where
+    W: Copy + Default,
+    L: PartialEq,
+{
+    fn weight(&self, s_idx: usize, t_idx: usize) -> Self::Weight {
+        self.adjacents[s_idx]
+            .iter()
+            .find(|(idx, _)| idx == &t_idx)
+            .map(|(_, w)| *w)
+            .unwrap_or_default()
+    }
+}
+
+impl<W, L> Adjacents for AdjacencyList<W, L>
+where
+    W: Copy + Default,
+    L: PartialEq,
+{
+    type AdjIt<'a>
+        = Copied<Iter<'a, (usize, W)>>
+    where
+        W: 'a,
+        L: 'a;
+
+    fn adjacents(&self, idx: usize) -> Self::AdjIt<'_> {
+        self.adjacents[idx].iter().copied()
+    }
+}
+
+impl<W, L> AdjacencyList<W, L> {
+    /// Create an adjacency list from vertex labels and, per vertex, a list of
+    /// adjacents given by index and edge weight
+    ///
+    /// # Panics
+    /// If the number of labels and adjacency lists differ
+    pub fn new(labels: Vec<L>, adjacents: Vec<Vec<(usize, W)>>) -> Self {
+        assert_eq!(labels.len(), adjacents.len());
+        Self { adjacents, labels }
+    }
+
+    pub fn remove(&mut self, idx: usize) -> Vec<(usize, W)> {
+        // remove row
+        let mut removed = self.adjacents.swap_remove(idx);
+        let n = self.adjacents.len();
+
+        // remove edges from other ends
+        for (s_idx, _) in removed.iter_mut() {
+            if *s_idx == n {
+                // last was swapped in place of idx
+                *s_idx = idx;
+            }
+            let k = self.adjacents[*s_idx]
+                .iter()
+                .position(|(i, _)| i == &idx)
+                .unwrap();
+            self.adjacents[*s_idx].swap_remove(k);
+        }
+
+        // update indices for node that was swapped in place of idx
+        if idx < n {
+            for k in 0..self.adjacents[idx].len() {
+                let s_idx = self.adjacents[idx][k].0;
+                let (t_idx, _) = self.adjacents[s_idx]
+                    .iter_mut()
+                    .find(|(i, _)| i == &n)
+                    .unwrap();
+                *t_idx = idx;
+            }
+        }
+
+        // remove label
+        self.labels.swap_remove(idx);
+
+        removed
+    }
+
+    /**
+     * Return a consistent adjacency list as `Ok` value or the labels of a
+     * duplicate edge or a self-referential edge in an `Err` value. The
+     * second label is `None`, if the edge is self-referential.
+     *
+     * # Examples
+     * ```
+     * # use mr_kaffee_utils::graph::adjacency_list::AdjacencyList;
+     *
+     * let edges = [(("a", "b"), 1), (("b", "c"), 1), (("c", "a"), 2)];
+     * let graph = AdjacencyList::from(edges).consistent().unwrap();
+     *
+     * let edges = [(("a", "b"), 1), (("b", "c"), 1), (("b", "a"), 2)];
+     * let duplicate = AdjacencyList::from(edges).consistent().unwrap_err();
+     * assert!(("a", Some("b")) == duplicate || ("b", Some("a")) == duplicate);
+     *
+     * let edges = [(("a", "b"), 1), (("b", "c"), 1), (("a", "a"), 2)];
+     * let duplicate = AdjacencyList::from(edges).consistent().unwrap_err();
+     * assert!(("a", None) == duplicate);
+     * ```
+     */
+    pub fn consistent(mut self) -> Result<Self, (L, Option<L>)> {
+        match self
+            .adjacents
+            .iter()
+            .enumerate()
+            .find_map(|(s_idx, adjacents)| {
+                adjacents
+                    .iter()
+                    .enumerate()
+                    .find(|&(k, (t_idx, _))| adjacents[0..k].iter().any(|(idx, _)| idx == t_idx))
+                    .map(|(_, &(t_idx, _))| (s_idx.min(t_idx), s_idx.max(t_idx)))
+                    .map(|(s_idx, t_idx)| {
+                        (
+                            self.labels.swap_remove(t_idx),
+                            if s_idx == t_idx {
+                                None
+                            } else {
+                                Some(self.labels.swap_remove(s_idx))
+                            },
+                        )
+                    })
+            }) {
+            Some(val) => Err(val),
+            None => Ok(self),
+        }
+    }
+}
+
+impl<W, L> Merge for AdjacencyList<W, L>
+where
+    W: Copy + AddAssign,
+{
+    fn merge(&mut self, s: usize, t: usize) {
+        let removed = self.remove(t);
+        let s = if s == self.adjacents.len() { t } else { s };
+        for (t_idx, w) in removed {
+            if let Some(k) = self.adjacents[s].iter().position(|(i, _)| i == &t_idx) {
+                // update edge weights if they exist s
+                let (_, w0) = &mut self.adjacents[s][k];
+                *w0 += w;
+                let (_, w0) = self.adjacents[t_idx]
+                    .iter_mut()
+                    .find(|(i, _)| i == &s)
+                    .unwrap();
+                *w0 += w;
+            } else if t_idx != s {
+                // insert new edges if they do not exist for s
+                self.adjacents[s].push((t_idx, w));
+                self.adjacents[t_idx].push((s, w));
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use super::{Adjacents, Graph, GraphBuilder, Merge, Weight};
+use std::{
+    hash::Hash,
+    iter::{Copied, Enumerate, Filter},
+    ops::AddAssign,
+    slice::Iter,
+};
+
+#[derive(Debug, Clone)]
+/// `Graph` implementation using an adjacency matrix
+pub struct AdjacencyMatrix<W, L> {
+    pub(crate) matrix: Vec<Vec<W>>,
+    pub(crate) labels: Vec<L>,
+}
+
+impl<W, L> FromIterator<((L, L), W)> for AdjacencyMatrix<W, L>
+where
+    W: Copy + Default,
+    L: Copy + Eq + Hash,
+{
+    fn from_iter<T: IntoIterator<Item = ((L, L), W)>>(iter: T) -> Self {
+        iter.into()
+    }
+}
+
+impl<W, L, T> From<T> for AdjacencyMatrix<W, L>
+where
+    W: Copy + Default,
+    L: Copy + Eq + Hash,
+    T: IntoIterator<Item = ((L, L), W)>,
+{
+    /// construct adjacency matrix from iterator over edges represented
+    /// by tuples `((s, t), w): ((L, L), W)`
+    fn from(value: T) -> Self {
+        let mut builder = GraphBuilder::undirected();
+        builder.extend(value);
+        builder.build_matrix()
+    }
+}
+
+impl<W, L> Graph for AdjacencyMatrix<W, L>
+where
+    W: Copy + Eq + Default,
+    L: PartialEq,
+{
+    type Weight = W;
+    type Label = L;
+
+    fn vertex_labels(&self) -> &[Self::Label] {
+        &self.labels
+    }
+
+    fn index_for_label(&self, label: &Self::Label) -> Option<usize> {
+        self.labels.iter().position(|l| l == label)
+    }
+
+    fn len(&self) -> usize {
+        self.matrix.len()
+    }
+}
+
+impl<W, L> Weight for AdjacencyMatrix<W, L>
+where
+    W: Copy + Eq + Default,
+    L: PartialEq,
+{
+    fn weight(&self, s_idx: usize, t_idx: usize) -> Self::Weight {
+        self.matrix[s_idx][t_idx]
+    }
+}
+
+type AdjPred<W> = for<'a> fn(&'a (usize, W)) -> bool;
+
+impl<W, L> Adjacents for AdjacencyMatrix<W, L>
+where
+    W: Copy + Eq + Default,
+    L: PartialEq,
+{
+    type AdjIt<'a>
+        = Filter<Enumerate<Copied<Iter<'a, W>>>, AdjPred<W>>
+    where
+        W: 'a,
+        L: 'a;
+
+    fn adjacents(&self, idx: usize) -> Self::AdjIt<'_> {
+        self.matrix[idx]
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|(_, w)| w != &W::default())
+    }
+}
+
+impl<W, L> AdjacencyMatrix<W, L> {
+    pub fn remove(&mut self, idx: usize) {
+        self.matrix.swap_remove(idx);
+        for row in self.matrix.iter_mut() {
+            row.swap_remove(idx);
+        }
+        self.labels.swap_remove(idx);
+    }
+}
+
+impl<W, L> Merge for AdjacencyMatrix<W, L>
+where
+    W: AddAssign + Copy + Eq + Default,
+    L: PartialEq,
+{
+    fn merge(&mut self, s: usize, t: usize) {
+        for k in (0..self.matrix.len()).filter(|&k| k != s && k != t) {
+            let w = self.matrix[t][k];
+            self.matrix[s][k] += w;
+            let w = self.matrix[k][t];
+            self.matrix[k][s] += w;
+        }
+
+        self.remove(t);
+    }
+}
@@ -0,0 +1,299 @@
+//! Minimum cut of weighted, undirected graphs
+use super::{Adjacents, Merge};
+use std::{
+    collections::{BinaryHeap, HashMap, HashSet},
+    hash::Hash,
+    ops::AddAssign,
+};
+
+/// Implementation of the Stoer-Wagner algorithm to find a minimum cut
+/// of a [`Graph`](super::Graph).
+///
+/// See <https://blog.thomasjungblut.com/graph/mincut/mincut/>
+/// See <https://en.wikipedia.org/wiki/Stoer%E2%80%93Wagner_algorithm>
+pub trait MinCut<W, L> {
+    /// Perform a minimum cut phase.
+    ///
+    /// Return the weight of the cut and the indices of the last two
+    /// vertices, `s` and `t`
+    fn min_cut_phase(&self) -> (W, usize, usize);
+
+    /// Perform minimum cut. Stop early if bound yields true.
+    ///
+    /// Return the weight of the minimum cut and a list of vertex
+    /// labels in one of the partitions.
+    ///
+    /// There is no guarantee on which of the partitions is returned.
+    fn min_cut_with_bound<F>(self, bound: F) -> Option<(W, Vec<L>)>
+    where
+        F: Fn(&(W, Vec<L>)) -> bool;
+
+    /// Perform minimum cut.
+    ///
+    /// Equivalent to [`Self::min_cut_with_bound`] with a bound function
+    /// that always yields `false`.
+    fn min_cut(self) -> Option<(W, Vec<L>)>
+    where
+        Self: Sized,
+    {
+        self.min_cut_with_bound(|_| false)
+    }
+}
+
+impl<T, W, L> MinCut<W, L> for T
+where
+    T: Adjacents<Weight = W, Label = L> + Merge,
+    W: Copy + Ord + AddAssign + Default,
+    L: Copy + Eq + Hash,
+{
+    fn min_cut_phase(&self) -> (W, usize, usize) {
+        let mut seen = HashSet::from([0]);
+        let mut weights = vec![W::default(); self.len()];
+        let mut queue = BinaryHeap::new();
+        for (idx, w) in self.adjacents(0) {
+            weights[idx] = w;
+            queue.push((w, idx));
+        }
+
+        let (mut w, mut s, mut t) = (W::default(), 0, 0);
+        while let Some((cur_w, cur_idx)) = queue.pop() {
+            if !seen.insert(cur_idx) {
+                continue;
+            }
+
+            (w, s, t) = (cur_w, t, cur_idx);
+            if seen.len() == self.len() {
+                break;
+            }
+
+            for (next_idx, next_w) in self
+                .adjacents(cur_idx)
+                .filter(|(idx, _)| !seen.contains(idx))
+            {
+                let w = &mut weights[next_idx];
+                *w += next_w;
+                queue.push((*w, next_idx));
+            }
+        }
+        (w, s, t)
+    }
+
+    fn min_cut_with_bound<F>(mut self, bound: F) -> Option<(W, Vec<L>)>
+    where
+        F: Fn(&(W, Vec<L>)) -> bool,
+    {
+        let mut merged = self
+            .vertex_labels()
+            .iter()
+            .map(|&l| (l, vec![l]))
+            .collect::<HashMap<_, _>>();
+        let mut best = None;
+        for _ in 0..self.len().saturating_sub(1) {
+            let (w, s_idx, t_idx) = self.min_cut_phase();
+            let t_labels = merged.remove(self.label(t_idx)).unwrap();
+
+            if best.as_ref().map(|(min_w, _)| &w < min_w).unwrap_or(true) {
+                best = Some((w, t_labels.clone()));
+            }
+
+            merged.get_mut(self.label(s_idx)).unwrap().extend(t_labels);
+            self.merge(s_idx, t_idx);
+
+            if best.as_ref().map(&bound).unwrap_or_default() {
+                break;
+            }
+        }
+
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MinCut;
+    use crate::graph::{adjacency_list::*, adjacency_matrix::*, Graph, Merge, Weight};
+    use std::{collections::HashSet, fmt::Debug, hash::Hash};
+
+    //  1-(2)-2-(3)-3-(4)-4
+    //  |   / |     |   / |
+    // (3)(2)(2)   (2)(2)(2)
+    //  | /   |     | /   |
+    //  5-(3)-6-(1)-7-(3)-8
+    const EDGES: [((usize, usize), u64); 12] = [
+        ((1, 2), 2),
+        ((2, 3), 3),
+        ((3, 4), 4),
+        ((1, 5), 3),
+        ((2, 5), 2),
+        ((2, 6), 2),
+        ((3, 7), 2),
+        ((4, 7), 2),
+        ((4, 8), 2),
+        ((5, 6), 3),
+        ((6, 7), 1),
+        ((7, 8), 3),
+    ];
+
+    pub fn do_test_min_cut_phase<G>(g: G)
+    where
+        G: MinCut<u64, usize> + Graph<Weight = u64, Label = usize>,
+    {
+        // (1) -> 5 @ 3
+        // (1, 5) -> 2 @ 4
+        // (1, 2, 5) -> 6 @ 5
+        // (1, 2, 5, 6) -> 3 @ 3
+        // (1, 2, 3, 5, 6) -> 4 @ 4
+        // (1, 2, 3, 4, 5, 6) -> 7 @ 5
+        // (1, 2, 3, 4, 5, 6, 7) -> 8 @ 5
+
+        let s = g.index_for_label(&7).unwrap();
+        let t = g.index_for_label(&8).unwrap();
+        assert_eq!((5, s, t), g.min_cut_phase());
+    }
+
+    #[test]
+    pub fn test_min_cut_phase() {
+        do_test_min_cut_phase(AdjacencyMatrix::from(EDGES));
+        do_test_min_cut_phase(AdjacencyList::from(EDGES));
+    }
+
+    pub fn do_test_merge<G>(mut g: G)
+    where
+        G: Merge + Weight<Weight = u64, Label = usize>,
+    {
+        //  1-(2)-2-(3)-3-(4)-4             2-(3)-3-(4)-4
+        //  |   / |     |   / |           / |     |   / |
+        // (3)(2)(2)   (2)(2)(2)  =>    (4)(2)   (2)(2)(2)
+        //  | /   |     | /   |         /   |     | /   |
+        //  5-(3)-6-(1)-7-(3)-8       5-(3)-6-(1)-7-(3)-8
+        let s = g.index_for_label(&5).unwrap();
+        let t = g.index_for_label(&1).unwrap();
+        g.merge(s, t);
+        assert_eq!(7, g.len());
+        let weights = [2, 3, 4, 5, 6, 7, 8]
+            .into_iter()
+            .filter_map(|l| g.index_for_label(&l))
+            .map(|k| g.weight(k, 4))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![4, 0, 0, 0, 3, 0, 0], weights);
+
+        //        2-(3)-3-(4)-4             2-(3)-3-(6)-4
+        //      / |     |   / |           / |   /   \   |
+        //    (4)(2)   (2)(2)(2)  =>    (4)(2)(1)   (3)(2)
+        //    /   |     | /   |         /   | /       \ |
+        //  5-(3)-6-(1)-7-(3)-8       5-(3)-6           8
+        let s = g.index_for_label(&3).unwrap();
+        let t = g.index_for_label(&7).unwrap();
+        g.merge(s, t);
+        assert_eq!(6, g.len());
+        let weights = [2, 3, 4, 5, 6, 8]
+            .into_iter()
+            .filter_map(|l| g.index_for_label(&l))
+            .map(|k| g.weight(k, 2))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![3, 0, 6, 0, 1, 3], weights);
+    }
+
+    #[test]
+    pub fn test_merge() {
+        do_test_merge(AdjacencyMatrix::from(EDGES));
+        do_test_merge(AdjacencyList::from(EDGES));
+    }
+
+    /// test minimum cut
+    /// make sure minimum cut of g yields given weight an partitions
+    fn do_test_min_cut<G, W, L>(g: G, exp_w: W, exp_pq: &HashSet<Vec<L>>)
+    where
+        G: MinCut<W, L> + Graph<Weight = W, Label = L>,
+        W: Eq + Debug,
+        L: Copy + Eq + Ord + Hash + Debug,
+    {
+        let mut ns = g.vertex_labels().to_vec();
+        ns.sort_unstable();
+
+        let (w, mut p) = g.min_cut().unwrap();
+        p.sort_unstable();
+        let q = ns
+            .into_iter()
+            .filter(|n| !p.contains(n))
+            .collect::<Vec<_>>();
+
+        assert_eq!(exp_w, w);
+        assert_eq!(exp_pq, &HashSet::from([p, q]));
+    }
+
+    #[test]
+    pub fn test_min_cut_1() {
+        let exp_pq = HashSet::from([vec![1, 2, 5, 6], vec![3, 4, 7, 8]]);
+
+        // original graph
+        do_test_min_cut(AdjacencyMatrix::from(EDGES), 4, &exp_pq);
+        do_test_min_cut(AdjacencyList::from(EDGES), 4, &exp_pq);
+
+        // changed representation
+        // swap nodes within one partition
+        let AdjacencyMatrix {
+            mut matrix,
+            mut labels,
+        } = AdjacencyMatrix::from(EDGES);
+        matrix.swap(0, 1);
+        labels.swap(0, 1);
+        for row in matrix.iter_mut() {
+            row.swap(0, 1);
+        }
+        let g2 = AdjacencyMatrix { matrix, labels };
+        do_test_min_cut(g2, 4, &exp_pq);
+
+        // changed representation
+        // swap nodes across partitions
+        let AdjacencyMatrix {
+            mut matrix,
+            mut labels,
+        } = AdjacencyMatrix::from(EDGES);
+        matrix.swap(0, 7);
+        labels.swap(0, 7);
+        for row in matrix.iter_mut() {
+            row.swap(0, 7);
+        }
+        let g3 = AdjacencyMatrix { matrix, labels };
+        do_test_min_cut(g3, 4, &exp_pq);
+    }
+
+    const CONTENT: &str = r#"jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr
+"#;
+
+    pub fn adj_iter(data: &str) -> impl Iterator<Item = ((&str, &str), u64)> {
+        data.lines().flat_map(|line| {
+            let mut parts = line
+                .split::<&[char]>(&[' ', ':'])
+                .map(str::trim)
+                .filter(|v| !v.is_empty());
+            let key = parts.next().unwrap();
+            parts.map(move |value| ((key, value), 1))
+        })
+    }
+
+    #[test]
+    pub fn test_min_cut_2() {
+        let exp_pq = HashSet::from([
+            vec!["bvb", "hfx", "jqt", "ntq", "rhn", "xhk"],
+            vec![
+                "cmg", "frs", "lhk", "lsr", "nvd", "pzl", "qnr", "rsh", "rzs",
+            ],
+        ]);
+        do_test_min_cut(AdjacencyMatrix::from(adj_iter(CONTENT)), 3, &exp_pq);
+        do_test_min_cut(AdjacencyList::from(adj_iter(CONTENT)), 3, &exp_pq);
+    }
+}
@@ -1,21 +1,54 @@
 //! Module to handle 2D grids
+//!
+//! A [`Grid`] stores its cells row by row. Consecutive rows are `stride` cells
+//! apart in the underlying data, which allows to wrap a puzzle input without
+//! copying: with a stride of `w + 1`, the newline terminating each line is
+//! simply skipped (see [`Grid::from_bytes`]).
+//!
+//! A [`View`] shows a grid rotated, transposed or mirrored without copying any
+//! data. Coordinates passed to a view are relative to the view's orientation.
+use crate::{
+    directions::Direction,
+    parse::{check_grid, ParseError},
+};
 use std::{
     fmt::Display,
-    ops::{Index, IndexMut},
+    marker::PhantomData,
+    ops::{Deref, DerefMut, Index, IndexMut},
 };
 
-#[derive(Debug, Clone)]
-pub struct Grid {
-    data: Vec<u8>,
+/// Offsets to the four direct neighbours: east, north, west, south
+pub const D4: [(isize, isize); 4] = [(1, 0), (0, -1), (-1, 0), (0, 1)];
+
+/// Offsets to all eight neighbours counter-clockwise, starting east
+pub const D8: [(isize, isize); 8] = [
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T = u8, D = Vec<T>> {
+    data: D,
     w: usize,
     h: usize,
+    stride: usize,
+    _cell: PhantomData<T>,
 }
 
-impl Display for Grid {
+/// Grid borrowing its data, e.g., from a puzzle input
+pub type GridRef<'a, T = u8> = Grid<T, &'a [T]>;
+
+impl<D: Deref<Target = [u8]>> Display for Grid<u8, D> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in 0..self.height() {
-            for col in 0..self.width() {
-                (self[(col, row)] as char).fmt(f)?;
+        for row in self.rows() {
+            for &b in row {
+                (b as char).fmt(f)?;
             }
             '\n'.fmt(f)?;
         }
@@ -23,8 +56,35 @@ impl Display for Grid {
     }
 }
 
-impl Grid {
-    pub fn data(&self) -> &[u8] {
+impl<T, D: Deref<Target = [T]>> Grid<T, D> {
+    /// Grid with rows of width `w` stored without gaps in `data`
+    pub fn new(data: D, w: usize, h: usize) -> Self {
+        Self::with_stride(data, w, h, w)
+    }
+
+    /// Grid with rows of width `w` that start `stride` cells apart in `data`
+    ///
+    /// # Panics
+    /// if `stride < w` or if `data` is too short to hold `h` rows
+    pub fn with_stride(data: D, w: usize, h: usize, stride: usize) -> Self {
+        assert!(stride >= w, "Stride {} is less than width {}.", stride, w);
+        assert!(
+            h == 0 || data.len() >= (h - 1) * stride + w,
+            "Data of length {} is too short for {} rows.",
+            data.len(),
+            h
+        );
+        Self {
+            data,
+            w,
+            h,
+            stride,
+            _cell: PhantomData,
+        }
+    }
+
+    /// The underlying data including any cells between rows
+    pub fn data(&self) -> &[T] {
         self.data.as_ref()
     }
 
@@ -36,47 +96,407 @@ impl Grid {
         self.h
     }
 
+    /// Distance between the starts of two consecutive rows in the data
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// The number of cells in the grid, i.e., `width * height`
     pub fn len(&self) -> usize {
         self.w * self.h
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Coordinates for the index `pos` into the data
     pub fn to_col_row(&self, pos: usize) -> (usize, usize) {
-        (pos % self.w, pos / self.w)
+        (pos % self.stride, pos / self.stride)
     }
 
+    /// Index into the data for the given coordinates
     pub fn to_idx(&self, (col, row): (usize, usize)) -> usize {
-        col + self.w * row
+        col + self.stride * row
+    }
+
+    pub fn contains(&self, (col, row): (usize, usize)) -> bool {
+        col < self.w && row < self.h
+    }
+
+    /// The cell at the given coordinates or `None` if they are out of bounds
+    pub fn get(&self, (col, row): (usize, usize)) -> Option<&T> {
+        self.contains((col, row))
+            .then(|| &self.data[col + self.stride * row])
+    }
+
+    /// The coordinates `(dc, dr)` away from `(col, row)` if they are in bounds
+    pub fn offset(
+        &self,
+        (col, row): (usize, usize),
+        (dc, dr): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let (col, row) = (col.wrapping_add_signed(dc), row.wrapping_add_signed(dr));
+        self.contains((col, row)).then_some((col, row))
+    }
+
+    /// The coordinates one step from `pos` in direction `dir` if they are in
+    /// bounds
+    pub fn step(&self, pos: (usize, usize), dir: Direction) -> Option<(usize, usize)> {
+        self.offset(pos, dir.delta())
+    }
+
+    /// The in-bounds neighbours of `pos` in the order east, north, west, south
+    pub fn neighbours_4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        D4.into_iter().filter_map(move |d| self.offset(pos, d))
+    }
+
+    /// All in-bounds neighbours of `pos` including diagonals, counter-clockwise
+    /// starting east
+    pub fn neighbours_8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        D8.into_iter().filter_map(move |d| self.offset(pos, d))
+    }
+
+    /// All coordinates row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let w = self.w;
+        (0..self.h).flat_map(move |row| (0..w).map(move |col| (col, row)))
+    }
+
+    /// All coordinates and cells row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> + '_ {
+        self.positions().map(|pos| (pos, &self[pos]))
+    }
+
+    /// The coordinates of the first cell, row by row, that satisfies `f`
+    pub fn position<F: FnMut(&T) -> bool>(&self, mut f: F) -> Option<(usize, usize)> {
+        self.positions().find(|&pos| f(&self[pos]))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.data[row * self.stride..row * self.stride + self.w]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.h).map(|row| self.row(row))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(col < self.w, "Column {} out of bounds.", col);
+        self.data[col..].iter().step_by(self.stride).take(self.h)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.w).map(|col| self.column(col))
+    }
+
+    /// Owned grid without gaps between rows with cells mapped by `f`
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid::new(self.rows().flatten().map(f).collect(), self.w, self.h)
+    }
+
+    /// Grid borrowing this grid's data
+    pub fn as_grid_ref(&self) -> GridRef<'_, T> {
+        Grid::with_stride(&self.data, self.w, self.h, self.stride)
+    }
+
+    /// View on the grid in its original orientation
+    pub fn view(&self) -> View<T, &[T]> {
+        View::new(self.as_grid_ref(), Transform::IDENTITY)
     }
 }
 
-impl Index<(usize, usize)> for Grid {
-    type Output = u8;
+impl<T, D: DerefMut<Target = [T]>> Grid<T, D> {
+    pub fn get_mut(&mut self, (col, row): (usize, usize)) -> Option<&mut T> {
+        self.contains((col, row))
+            .then(|| &mut self.data[col + self.stride * row])
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.data[row * self.stride..row * self.stride + self.w]
+    }
+
+    pub fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let (a, b) = (self.to_idx(a), self.to_idx(b));
+        self.data.swap(a, b);
+    }
+
+    /// Grid mutably borrowing this grid's data
+    pub fn as_grid_mut(&mut self) -> Grid<T, &mut [T]> {
+        Grid::with_stride(&mut self.data, self.w, self.h, self.stride)
+    }
+
+    /// Mutable view on the grid in its original orientation
+    pub fn view_mut(&mut self) -> View<T, &mut [T]> {
+        View::new(self.as_grid_mut(), Transform::IDENTITY)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Owned grid with all cells set to `value`
+    pub fn filled(value: T, w: usize, h: usize) -> Self {
+        Self::new(vec![value; w * h], w, h)
+    }
+}
+
+impl<'a> GridRef<'a> {
+    /// Wrap a puzzle input without copying, reporting anything that is not a
+    /// rectangular grid of symbols for which `is_symbol` holds as error
+    ///
+    /// See [`check_grid`] for details.
+    pub fn from_bytes<F>(input: &'a [u8], is_symbol: F, expected: &str) -> Result<Self, ParseError>
+    where
+        F: Fn(u8) -> bool,
+    {
+        let (w, h) = check_grid(input, is_symbol, expected)?;
+        Ok(Self::with_stride(input, w, h, w + 1))
+    }
+}
+
+impl<T, D: Deref<Target = [T]>> Index<(usize, usize)> for Grid<T, D> {
+    type Output = T;
 
     fn index(&self, (col, row): (usize, usize)) -> &Self::Output {
-        &self.data[col + self.w * row]
+        &self.data[col + self.stride * row]
     }
 }
 
-impl IndexMut<(usize, usize)> for Grid {
+impl<T, D: DerefMut<Target = [T]>> IndexMut<(usize, usize)> for Grid<T, D> {
     fn index_mut(&mut self, (col, row): (usize, usize)) -> &mut Self::Output {
-        &mut self.data[col + self.w * row]
+        &mut self.data[col + self.stride * row]
     }
 }
 
-impl Index<usize> for Grid {
-    type Output = u8;
+impl<T, D: Deref<Target = [T]>> Index<usize> for Grid<T, D> {
+    type Output = T;
 
     fn index(&self, pos: usize) -> &Self::Output {
         &self.data[pos]
     }
 }
 
-impl IndexMut<usize> for Grid {
+impl<T, D: DerefMut<Target = [T]>> IndexMut<usize> for Grid<T, D> {
     fn index_mut(&mut self, pos: usize) -> &mut Self::Output {
         &mut self.data[pos]
     }
 }
 
+/// One of the eight ways to map a grid onto itself by rotations and
+/// reflections
+///
+/// View coordinates are mapped to grid coordinates by first swapping them if
+/// `transpose` is set and then mirroring columns and rows if `flip_cols` and
+/// `flip_rows` are set respectively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Transform {
+    pub transpose: bool,
+    pub flip_cols: bool,
+    pub flip_rows: bool,
+}
+
+impl Transform {
+    pub const IDENTITY: Self = Self {
+        transpose: false,
+        flip_cols: false,
+        flip_rows: false,
+    };
+
+    /// Swap columns and rows
+    pub fn transpose(self) -> Self {
+        Self {
+            transpose: !self.transpose,
+            ..self
+        }
+    }
+
+    /// Mirror left to right
+    pub fn flip_horizontal(self) -> Self {
+        if self.transpose {
+            Self {
+                flip_rows: !self.flip_rows,
+                ..self
+            }
+        } else {
+            Self {
+                flip_cols: !self.flip_cols,
+                ..self
+            }
+        }
+    }
+
+    /// Mirror top to bottom
+    pub fn flip_vertical(self) -> Self {
+        self.transpose().flip_horizontal().transpose()
+    }
+
+    /// Rotate clockwise by 90°, so that the left column becomes the top row
+    pub fn rotate_cw(self) -> Self {
+        self.transpose().flip_horizontal()
+    }
+
+    /// Rotate counter-clockwise by 90°, so that the right column becomes the
+    /// top row
+    pub fn rotate_ccw(self) -> Self {
+        self.flip_horizontal().transpose()
+    }
+
+    pub fn rotate_180(self) -> Self {
+        self.flip_horizontal().flip_vertical()
+    }
+
+    /// Width and height of a transformed grid with width `w` and height `h`
+    pub fn dims(&self, (w, h): (usize, usize)) -> (usize, usize) {
+        if self.transpose {
+            (h, w)
+        } else {
+            (w, h)
+        }
+    }
+
+    /// Grid coordinates for view coordinates `(x, y)` on a grid with width `w`
+    /// and height `h`
+    pub fn apply(&self, (x, y): (usize, usize), (w, h): (usize, usize)) -> (usize, usize) {
+        let (col, row) = if self.transpose { (y, x) } else { (x, y) };
+        (
+            if self.flip_cols { w - 1 - col } else { col },
+            if self.flip_rows { h - 1 - row } else { row },
+        )
+    }
+}
+
+/// Transformed view on a grid, see [`Transform`]
+///
+/// Views are usually obtained from [`Grid::view`] or [`Grid::view_mut`] and
+/// borrow the grid's data. Transformations are chained, e.g.,
+/// `grid.view().transpose().flip_vertical()`.
+#[derive(Debug, Clone)]
+pub struct View<T, D = Vec<T>> {
+    grid: Grid<T, D>,
+    transform: Transform,
+}
+
+impl<T, D: Deref<Target = [T]>> View<T, D> {
+    pub fn new(grid: Grid<T, D>, transform: Transform) -> Self {
+        Self { grid, transform }
+    }
+
+    pub fn transform(&self) -> Transform {
+        self.transform
+    }
+
+    pub fn transpose(self) -> Self {
+        self.with(Transform::transpose)
+    }
+
+    pub fn flip_horizontal(self) -> Self {
+        self.with(Transform::flip_horizontal)
+    }
+
+    pub fn flip_vertical(self) -> Self {
+        self.with(Transform::flip_vertical)
+    }
+
+    pub fn rotate_cw(self) -> Self {
+        self.with(Transform::rotate_cw)
+    }
+
+    pub fn rotate_ccw(self) -> Self {
+        self.with(Transform::rotate_ccw)
+    }
+
+    pub fn rotate_180(self) -> Self {
+        self.with(Transform::rotate_180)
+    }
+
+    fn with<F: Fn(Transform) -> Transform>(self, f: F) -> Self {
+        Self {
+            transform: f(self.transform),
+            ..self
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.transform.dims((self.grid.w, self.grid.h)).0
+    }
+
+    pub fn height(&self) -> usize {
+        self.transform.dims((self.grid.w, self.grid.h)).1
+    }
+
+    /// Grid coordinates for the view coordinates `pos`
+    pub fn to_grid_pos(&self, pos: (usize, usize)) -> (usize, usize) {
+        self.transform.apply(pos, (self.grid.w, self.grid.h))
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        x < self.width() && y < self.height()
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        self.contains(pos).then(|| &self[pos])
+    }
+
+    /// All view coordinates row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let w = self.width();
+        (0..self.height()).flat_map(move |y| (0..w).map(move |x| (x, y)))
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> + '_ {
+        (0..self.width()).map(move |x| &self[(x, y)])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.height()).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> + '_ {
+        (0..self.height()).map(move |y| &self[(x, y)])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width()).map(|x| self.column(x))
+    }
+
+    /// Owned copy of the grid as seen through the view
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::new(
+            self.positions().map(|pos| self[pos].clone()).collect(),
+            self.width(),
+            self.height(),
+        )
+    }
+}
+
+impl<T, D: DerefMut<Target = [T]>> View<T, D> {
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        self.contains(pos).then(|| &mut self[pos])
+    }
+
+    pub fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let (a, b) = (self.to_grid_pos(a), self.to_grid_pos(b));
+        self.grid.swap(a, b);
+    }
+}
+
+impl<T, D: Deref<Target = [T]>> Index<(usize, usize)> for View<T, D> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &Self::Output {
+        &self.grid[self.to_grid_pos(pos)]
+    }
+}
+
+impl<T, D: DerefMut<Target = [T]>> IndexMut<(usize, usize)> for View<T, D> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut Self::Output {
+        let pos = self.to_grid_pos(pos);
+        &mut self.grid[pos]
+    }
+}
+
 pub trait MakeGrid {
     fn make_grid(self, boundary: Option<u8>) -> Grid;
 }
@@ -95,17 +515,24 @@ impl<T: AsRef<[u8]>> MakeGrid for T {
             .split(|&b| b == b'\n')
             .filter(|line| !line.is_empty())
             .fold((data, h), |(mut data, h), line| {
-                boundary.map(|boundary| data.push(boundary));
+                if let Some(boundary) = boundary {
+                    data.push(boundary)
+                }
                 data.extend_from_slice(line);
-                boundary.map(|boundary| data.push(boundary));
+                if let Some(boundary) = boundary {
+                    data.push(boundary)
+                }
                 if w * (h + 1) != data.len() {
                     panic!("Inconsistent line length at line {}.", h + 1);
                 }
                 (data, h + 1)
             });
-        boundary.map(|boundary| (h, _) = (h + 1, data.resize(w * (h + 1), boundary)));
+        if let Some(boundary) = boundary {
+            h += 1;
+            data.resize(w * h, boundary);
+        }
 
-        Grid { data, w, h }
+        Grid::new(data, w, h)
     }
 }
 
@@ -133,11 +560,117 @@ mod tests {
         let grid = "123\n456\n789\nxyz\n".make_grid(None);
         assert_eq!(3, grid.width());
         assert_eq!(4, grid.height());
+        assert_eq!(b"123456789xyz", grid.data());
+        println!("{:?}", grid);
+    }
+
+    #[test]
+    pub fn test_from_bytes() {
+        let grid = GridRef::from_bytes(b"123\n456\n789\nxyz\n", |_| true, "").unwrap();
+        assert_eq!((3, 4, 4), (grid.width(), grid.height(), grid.stride()));
+        assert_eq!(b'6', grid[(2, 1)]);
+        assert_eq!(Some(&b'x'), grid.get((0, 3)));
+        assert_eq!(None, grid.get((3, 0)));
+        assert_eq!(None, grid.get((0, 4)));
+        assert_eq!((0, 2), grid.to_col_row(grid.to_idx((0, 2))));
+        assert_eq!(Some((1, 3)), grid.position(|&b| b == b'y'));
+        assert_eq!("123\n456\n789\nxyz\n", grid.to_string());
+        assert_eq!("123\n456\n789\nxyz".make_grid(None), grid.map(|&b| b));
+    }
+
+    #[test]
+    pub fn test_rows_columns() {
+        let grid = GridRef::from_bytes(b"123\n456\n789\nxyz", |_| true, "").unwrap();
         assert_eq!(
-            &[b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b'x', b'y', b'z'],
-            grid.data()
+            vec![b"123", b"456", b"789", b"xyz"],
+            grid.rows().collect::<Vec<_>>()
         );
-        println!("{:?}", grid);
+        assert_eq!(
+            vec![b"147x".to_vec(), b"258y".to_vec(), b"369z".to_vec()],
+            grid.columns()
+                .map(|col| col.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    pub fn test_neighbours() {
+        let grid = Grid::filled(0u32, 3, 2);
+        assert_eq!(
+            vec![(1, 0), (0, 1)],
+            grid.neighbours_4((0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(2, 1), (1, 0), (0, 1)],
+            grid.neighbours_4((1, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(2, 1), (2, 0), (1, 0), (0, 0), (0, 1)],
+            grid.neighbours_8((1, 1)).collect::<Vec<_>>()
+        );
+        assert_eq!(None, grid.offset((2, 1), (1, 0)));
+        assert_eq!(Some((2, 0)), grid.step((2, 1), Direction::North));
+        assert_eq!(None, grid.step((2, 1), Direction::South));
+    }
+
+    #[test]
+    pub fn test_get_mut() {
+        let mut data = b"12\n34\n".to_vec();
+        let mut grid = Grid::with_stride(&mut data[..], 2, 2, 3);
+        *grid.get_mut((1, 1)).unwrap() = b'x';
+        grid.row_mut(0)[0] = b'y';
+        assert_eq!(None, grid.get_mut((2, 0)));
+        assert_eq!(b"y2\n3x\n", &data[..]);
+    }
+
+    #[test]
+    pub fn test_views() {
+        let grid = "123\n456\n".make_grid(None);
+        let to_string = |view: View<u8, &[u8]>| view.to_grid().to_string();
+        assert_eq!("123\n456\n", to_string(grid.view()));
+        assert_eq!("14\n25\n36\n", to_string(grid.view().transpose()));
+        assert_eq!("321\n654\n", to_string(grid.view().flip_horizontal()));
+        assert_eq!("456\n123\n", to_string(grid.view().flip_vertical()));
+        assert_eq!("41\n52\n63\n", to_string(grid.view().rotate_cw()));
+        assert_eq!("36\n25\n14\n", to_string(grid.view().rotate_ccw()));
+        assert_eq!("654\n321\n", to_string(grid.view().rotate_180()));
+        assert_eq!(
+            "123\n456\n",
+            to_string(grid.view().rotate_cw().rotate_ccw())
+        );
+        assert_eq!(
+            "63\n52\n41\n",
+            to_string(grid.view().transpose().rotate_180())
+        );
+        assert_eq!(
+            Transform::IDENTITY,
+            Transform::IDENTITY
+                .rotate_cw()
+                .rotate_cw()
+                .rotate_cw()
+                .rotate_cw()
+        );
+
+        let view = grid.view().rotate_cw();
+        assert_eq!((2, 3), (view.width(), view.height()));
+        assert_eq!(Some(&b'6'), view.get((0, 2)));
+        assert_eq!(None, view.get((2, 0)));
+        assert_eq!(
+            vec![b"456".to_vec(), b"123".to_vec()],
+            view.columns()
+                .map(|col| col.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    pub fn test_view_mut() {
+        let mut data = b"12\n34\n".to_vec();
+        let mut grid = Grid::with_stride(&mut data[..], 2, 2, 3);
+        let mut view = grid.view_mut().rotate_cw();
+        view[(0, 0)] = b'x';
+        view.swap((1, 0), (1, 1));
+        assert_eq!(b"21\nx4\n", &data[..]);
     }
 
     #[should_panic]
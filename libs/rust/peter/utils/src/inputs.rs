@@ -0,0 +1,83 @@
+//! Module to locate and read puzzle inputs
+//!
+//! The input for a day is resolved as follows:
+//!
+//! 1. If a path is given explicitly (e.g., as command line argument), the
+//!    input is read from that path. The path `-` stands for stdin.
+//! 2. If the environment variable [`INPUTS_DIR_VAR`] is set, the input is read
+//!    from the file `input[dd]` in the directory it points to.
+//! 3. Otherwise, the input is read from the file `input[dd]` in the
+//!    repository's `inputs` folder, located relative to this crate's manifest.
+//!
+//! Here, `[dd]` is the two digit number of the day with leading zeros.
+use std::{
+    env,
+    ffi::OsStr,
+    fs::read_to_string,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+/// Environment variable pointing to a directory with inputs
+pub const INPUTS_DIR_VAR: &str = "AOC_INPUTS_DIR";
+
+/// Path that stands for stdin
+pub const STDIN: &str = "-";
+
+/// The directory inputs are read from if no explicit path is given
+pub fn inputs_dir() -> PathBuf {
+    env::var_os(INPUTS_DIR_VAR)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("../../../../inputs"))
+}
+
+/// The file name of the input for the given day
+pub fn input_file_name(day: u8) -> String {
+    format!("input{:02}", day)
+}
+
+/// Read the input for the given day from `path` or, if no path is given, from
+/// the default location in [`inputs_dir`]
+pub fn read_input(day: u8, path: Option<&Path>) -> io::Result<String> {
+    match path {
+        Some(path) if path.as_os_str() == OsStr::new(STDIN) => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        }
+        Some(path) => read_to_string(path),
+        None => read_to_string(inputs_dir().join(input_file_name(day))),
+    }
+}
+
+/// The input path given as first command line argument, if any
+pub fn path_from_args() -> Option<PathBuf> {
+    env::args_os().nth(1).map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    pub fn test_input_file_name() {
+        assert_eq!("input07", input_file_name(7));
+        assert_eq!("input25", input_file_name(25));
+    }
+
+    #[test]
+    pub fn test_read_input_from_path() {
+        let path = env::temp_dir().join(format!("mr-kaffee-utils-test-{}", std::process::id()));
+        fs::write(&path, "some input").unwrap();
+        let input = read_input(1, Some(&path));
+        fs::remove_file(&path).unwrap();
+        assert_eq!("some input", input.unwrap());
+    }
+
+    #[test]
+    pub fn test_read_input_missing() {
+        let path = Path::new("this/file/does/not/exist");
+        assert!(read_input(1, Some(path)).is_err());
+    }
+}
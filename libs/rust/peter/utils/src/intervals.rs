@@ -0,0 +1,467 @@
+//! Half-open intervals, sets of intervals and n-dimensional boxes
+//!
+//! An [`Interval`] `start..end` contains all values `x` with
+//! `start <= x < end`. It is empty if `start >= end`. Operations may produce
+//! empty intervals with different bounds, so use [`Interval::is_empty`] rather
+//! than comparing to a specific empty interval.
+use crate::euclid::{One, Zero};
+use std::ops::{Add, Mul, Range, RangeInclusive, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        &self.start <= value && value < &self.end
+    }
+
+    /// The values contained in both intervals
+    pub fn intersect(&self, other: &Self) -> Self {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// Whether there is a value contained in both intervals
+    pub fn intersects(&self, other: &Self) -> bool {
+        !self.intersect(other).is_empty()
+    }
+
+    /// The values not contained in `other`, split into the parts below and
+    /// above `other`, each of which may be empty
+    pub fn difference(&self, other: &Self) -> (Self, Self) {
+        if other.is_empty() {
+            (*self, Self::new(self.end, self.end))
+        } else {
+            (
+                Self::new(self.start, self.end.min(other.start)),
+                Self::new(self.start.max(other.end), self.end),
+            )
+        }
+    }
+
+    /// The smallest interval containing both intervals
+    pub fn hull(&self, other: &Self) -> Self {
+        match (self.is_empty(), other.is_empty()) {
+            (_, true) => *self,
+            (true, false) => *other,
+            _ => Self::new(self.start.min(other.start), self.end.max(other.end)),
+        }
+    }
+
+    /// Split into the values below `at` and the values not below `at`
+    pub fn split(&self, at: T) -> (Self, Self) {
+        (
+            Self::new(self.start, self.end.min(at)),
+            Self::new(self.start.max(at), self.end),
+        )
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T>> Interval<T> {
+    /// The interval of length `len` starting at `start`
+    pub fn from_len(start: T, len: T) -> Self {
+        Self::new(start, start + len)
+    }
+
+    /// Add `delta` to all values
+    pub fn shift(&self, delta: T) -> Self {
+        Self::new(self.start + delta, self.end + delta)
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Zero> Interval<T> {
+    /// The number of values contained in the interval
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::ZERO
+        } else {
+            self.end - self.start
+        }
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Self {
+            start: range.start,
+            end: range.end,
+        }
+    }
+}
+
+impl<T: Copy + Add<Output = T> + One> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        Self {
+            start: *range.start(),
+            end: *range.end() + T::ONE,
+        }
+    }
+}
+
+impl<T> From<Interval<T>> for Range<T> {
+    fn from(interval: Interval<T>) -> Self {
+        interval.start..interval.end
+    }
+}
+
+/// A set of values represented by disjoint intervals
+///
+/// The intervals are kept sorted, non-empty and separated by at least one value
+/// not contained in the set, so equal sets have equal representations.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+}
+
+impl<T: Copy + Ord> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let idx = self.intervals.partition_point(|iv| &iv.end <= value);
+        idx < self.intervals.len() && self.intervals[idx].contains(value)
+    }
+
+    /// The smallest value in the set
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|iv| iv.start)
+    }
+
+    /// Add all values of `interval` to the set
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        // intervals overlapping or touching the new one are merged
+        let lo = self.intervals.partition_point(|iv| iv.end < interval.start);
+        let hi = self
+            .intervals
+            .partition_point(|iv| iv.start <= interval.end);
+        let merged = self.intervals[lo..hi]
+            .iter()
+            .fold(interval, |merged, iv| merged.hull(iv));
+        self.intervals.splice(lo..hi, [merged]);
+    }
+
+    /// Remove all values of `interval` from the set
+    pub fn remove(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+
+        // intervals overlapping the removed one are cut
+        let lo = self
+            .intervals
+            .partition_point(|iv| iv.end <= interval.start);
+        let hi = self.intervals.partition_point(|iv| iv.start < interval.end);
+        if lo == hi {
+            return;
+        }
+        let (below, _) = self.intervals[lo].difference(&interval);
+        let (_, above) = self.intervals[hi - 1].difference(&interval);
+        let parts = [below, above].into_iter().filter(|iv| !iv.is_empty());
+        self.intervals.splice(lo..hi, parts);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        other.iter().for_each(|&iv| union.insert(iv));
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let (a, b) = (&self.intervals, &other.intervals);
+        let (mut i, mut j) = (0, 0);
+        let mut intervals = Vec::new();
+        while i < a.len() && j < b.len() {
+            let iv = a[i].intersect(&b[j]);
+            if !iv.is_empty() {
+                intervals.push(iv);
+            }
+            if a[i].end < b[j].end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        other.iter().for_each(|&iv| difference.remove(iv));
+        difference
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T>> IntervalSet<T> {
+    /// Add `delta` to all values
+    pub fn shift(&self, delta: T) -> Self {
+        Self {
+            intervals: self.intervals.iter().map(|iv| iv.shift(delta)).collect(),
+        }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T> + Zero> IntervalSet<T> {
+    /// The number of values contained in the set
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::ZERO, |len, iv| len + iv.len())
+    }
+}
+
+impl<T: Copy + Ord> From<Interval<T>> for IntervalSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self::from_iter([interval])
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        iter.into_iter().for_each(|iv| set.insert(iv));
+        set
+    }
+}
+
+impl<T> IntoIterator for IntervalSet<T> {
+    type Item = Interval<T>;
+    type IntoIter = std::vec::IntoIter<Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = std::slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+/// An n-dimensional box, i.e., the product of one interval per dimension
+///
+/// A cuboid is empty if any of its intervals is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cuboid<T, const N: usize>(pub [Interval<T>; N]);
+
+impl<T: Copy + Ord, const N: usize> Cuboid<T, N> {
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().any(Interval::is_empty)
+    }
+
+    pub fn contains(&self, point: &[T; N]) -> bool {
+        self.0.iter().zip(point).all(|(iv, x)| iv.contains(x))
+    }
+
+    pub fn intersect(&self, other: &Self) -> Self {
+        Self(std::array::from_fn(|d| self.0[d].intersect(&other.0[d])))
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        !self.intersect(other).is_empty()
+    }
+
+    /// Split into the parts below `at` and not below `at` in dimension `dim`
+    pub fn split(&self, dim: usize, at: T) -> (Self, Self) {
+        let (below, above) = self.0[dim].split(at);
+        let (mut lower, mut upper) = (*self, *self);
+        lower.0[dim] = below;
+        upper.0[dim] = above;
+        (lower, upper)
+    }
+
+    /// The part not contained in `other` as at most `2 N` disjoint non-empty
+    /// cuboids
+    pub fn difference(&self, other: &Self) -> Vec<Self> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        if !self.intersects(other) {
+            return vec![*self];
+        }
+
+        // cut off slabs below and above other dimension by dimension
+        let mut parts = Vec::new();
+        let mut rest = *self;
+        for d in 0..N {
+            let (below, above) = rest.0[d].difference(&other.0[d]);
+            for iv in [below, above] {
+                if !iv.is_empty() {
+                    let mut part = rest;
+                    part.0[d] = iv;
+                    parts.push(part);
+                }
+            }
+            rest.0[d] = rest.0[d].intersect(&other.0[d]);
+        }
+        parts
+    }
+}
+
+impl<T, const N: usize> Cuboid<T, N>
+where
+    T: Copy + Ord + Sub<Output = T> + Mul<Output = T> + Zero + One,
+{
+    /// The number of points contained in the cuboid
+    pub fn volume(&self) -> T {
+        self.0.iter().fold(T::ONE, |volume, iv| volume * iv.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet<i64> {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    #[test]
+    pub fn test_interval() {
+        let a = Interval::new(2, 8);
+        let b = Interval::from(5..=9);
+        assert_eq!(Interval::new(5, 10), b);
+        assert_eq!(6, a.len());
+        assert_eq!(0, Interval::new(8, 2).len());
+        assert!(a.contains(&2) && !a.contains(&8));
+
+        assert_eq!(Interval::new(5, 8), a.intersect(&b));
+        assert!(a.intersects(&b));
+        assert!(!a.intersects(&Interval::new(8, 9)));
+
+        assert_eq!(
+            (Interval::new(2, 5), Interval::new(10, 8)),
+            a.difference(&b)
+        );
+        let (below, above) = a.difference(&Interval::new(3, 4));
+        assert_eq!((Interval::new(2, 3), Interval::new(4, 8)), (below, above));
+        let (below, above) = a.difference(&Interval::new(0, 10));
+        assert!(below.is_empty() && above.is_empty());
+
+        assert_eq!(Interval::new(2, 10), a.hull(&b));
+        assert_eq!(Interval::new(-1, 5), a.shift(-3));
+        assert_eq!((Interval::new(2, 4), Interval::new(4, 8)), a.split(4));
+        assert_eq!(3..7, Range::from(Interval::from_len(3, 4)));
+    }
+
+    #[test]
+    pub fn test_interval_set() {
+        let mut s = set(&[(5, 7), (0, 2), (10, 12)]);
+        assert_eq!(
+            &[
+                Interval::new(0, 2),
+                Interval::new(5, 7),
+                Interval::new(10, 12)
+            ],
+            s.intervals()
+        );
+        assert_eq!(6, s.len());
+
+        // merge touching and overlapping
+        s.insert(Interval::new(2, 3));
+        s.insert(Interval::new(6, 10));
+        assert_eq!(set(&[(0, 3), (5, 12)]), s);
+        assert!(s.contains(&0) && s.contains(&11) && !s.contains(&3) && !s.contains(&12));
+
+        s.remove(Interval::new(1, 6));
+        assert_eq!(set(&[(0, 1), (6, 12)]), s);
+        s.remove(Interval::new(8, 9));
+        assert_eq!(set(&[(0, 1), (6, 8), (9, 12)]), s);
+        s.remove(Interval::new(-5, 20));
+        assert!(s.is_empty());
+
+        let a = set(&[(0, 5), (10, 15)]);
+        let b = set(&[(3, 12), (14, 20)]);
+        assert_eq!(set(&[(0, 20)]), a.union(&b));
+        assert_eq!(set(&[(3, 5), (10, 12), (14, 15)]), a.intersection(&b));
+        assert_eq!(set(&[(0, 3), (12, 14)]), a.difference(&b));
+        assert_eq!(set(&[(5, 10), (15, 20)]), b.difference(&a));
+        assert_eq!(set(&[(10, 15), (20, 25)]), a.shift(10));
+        assert_eq!(Some(0), a.min());
+    }
+
+    #[test]
+    pub fn test_cuboid() {
+        let a = Cuboid([
+            Interval::new(0, 4),
+            Interval::new(0, 4),
+            Interval::new(0, 4),
+        ]);
+        let b = Cuboid([
+            Interval::new(1, 2),
+            Interval::new(2, 6),
+            Interval::new(-1, 3),
+        ]);
+        assert_eq!(64, a.volume());
+        assert!(a.contains(&[3, 0, 1]) && !a.contains(&[3, 4, 1]));
+
+        let c = a.intersect(&b);
+        assert_eq!(
+            Cuboid([
+                Interval::new(1, 2),
+                Interval::new(2, 4),
+                Interval::new(0, 3)
+            ]),
+            c
+        );
+
+        let parts = a.difference(&b);
+        assert!(parts.len() <= 6);
+        assert_eq!(
+            a.volume() - c.volume(),
+            parts.iter().map(Cuboid::volume).sum::<i32>()
+        );
+        for (k, p) in parts.iter().enumerate() {
+            assert!(!p.is_empty() && !p.intersects(&b));
+            assert!(parts[k + 1..].iter().all(|q| !p.intersects(q)));
+        }
+
+        let far = Cuboid([Interval::new(5, 6); 3]);
+        assert_eq!(vec![a], a.difference(&far));
+        assert_eq!(Vec::<Cuboid<i32, 3>>::new(), c.difference(&a));
+
+        let (lower, upper) = a.split(1, 1);
+        assert_eq!((16, 48), (lower.volume(), upper.volume()));
+    }
+}
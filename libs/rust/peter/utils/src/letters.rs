@@ -3,39 +3,41 @@
 //! From time to time, AoC puzzles involve pixel displays with letters. Typically '#' represents a lit
 //! pixel and '.' represents a dark pixel.
 //!
-//! This module helps to parse those letters.
+//! This module helps to parse those letters and to render text into such displays.
 //!
-//! The alphabet is still quite incomplete. It contains all the different letters that I have seen in my
-//! solutions to AoC 2015 to 2022 so far.
+//! The alphabets contain all capital letters. Letters that I have not seen in AoC puzzles so far are
+//! my best guess of how they would look like. If a letter does not match exactly, the closest letter
+//! and a confidence score are available with [`Letters::decode_closest`] and
+//! [`big::BigLetters::decode_big_closest`].
 use std::any::Any;
 
 pub const LIT: char = '#';
 pub const DARK: char = '.';
 
-pub const A: &[u8] = &[];
+pub const A: &[u8] = ".##..#..#.#..#.####.#..#.#..#.".as_bytes();
 pub const B: &[u8] = "###..#..#.###..#..#.#..#.###..".as_bytes();
 pub const C: &[u8] = ".##..#..#.#....#....#..#..##..".as_bytes();
-pub const D: &[u8] = &[];
+pub const D: &[u8] = "###..#..#.#..#.#..#.#..#.###..".as_bytes();
 pub const E: &[u8] = "####.#....###..#....#....####.".as_bytes();
 pub const F: &[u8] = "####.#....###..#....#....#....".as_bytes();
 pub const G: &[u8] = ".##..#..#.#....#.##.#..#..###.".as_bytes();
 pub const H: &[u8] = "#..#.#..#.####.#..#.#..#.#..#.".as_bytes();
-pub const I: &[u8] = &[];
+pub const I: &[u8] = ".###...#....#....#....#...###.".as_bytes();
 pub const J: &[u8] = "..##....#....#....#.#..#..##..".as_bytes();
 pub const K: &[u8] = "#..#.#.#..##...#.#..#.#..#..#.".as_bytes();
 pub const L: &[u8] = "#....#....#....#....#....####.".as_bytes();
-pub const M: &[u8] = &[];
-pub const N: &[u8] = &[];
-pub const O: &[u8] = &[];
+pub const M: &[u8] = "#...###.###.#.##...##...##...#".as_bytes();
+pub const N: &[u8] = "#..#.##.#.#.##.#..#.#..#.#..#.".as_bytes();
+pub const O: &[u8] = ".##..#..#.#..#.#..#.#..#..##..".as_bytes();
 pub const P: &[u8] = "###..#..#.#..#.###..#....#....".as_bytes();
-pub const Q: &[u8] = &[];
+pub const Q: &[u8] = ".##..#..#.#..#.#..#.#.##..###.".as_bytes();
 pub const R: &[u8] = "###..#..#.#..#.###..#.#..#..#.".as_bytes();
-pub const S: &[u8] = &[];
-pub const T: &[u8] = &[];
-pub const U: &[u8] = &[];
-pub const V: &[u8] = &[];
-pub const W: &[u8] = &[];
-pub const X: &[u8] = &[];
+pub const S: &[u8] = ".###.#....#.....##.....#.###..".as_bytes();
+pub const T: &[u8] = "#####..#....#....#....#....#..".as_bytes();
+pub const U: &[u8] = "#..#.#..#.#..#.#..#.#..#..##..".as_bytes();
+pub const V: &[u8] = "#...##...##...#.#.#..#.#...#..".as_bytes();
+pub const W: &[u8] = "#...##...##...##.#.###.###...#".as_bytes();
+pub const X: &[u8] = "#...#.#.#...#....#...#.#.#...#".as_bytes();
 pub const Y: &[u8] = "#...##...#.#.#...#....#....#..".as_bytes();
 pub const Z: &[u8] = "####....#...#...#...#....####.".as_bytes();
 
@@ -55,6 +57,13 @@ pub trait Letters {
     /// Each letter is supposed to be 5 pixels wide and 6 pixel high
     fn decode(&self, nl: usize) -> Result<String, String>;
 
+    /// Decode a pixel display into the closest letters, tolerating pixel
+    /// errors
+    ///
+    /// Each letter comes with a confidence score, which is the fraction of
+    /// pixels matching the letter. It is `1.0` for an exact match.
+    fn decode_closest(&self, nl: usize) -> Vec<(char, f64)>;
+
     /// Create a printable string representing the display using the given
     /// chars for lit and dark pixels
     fn printable(&self, nl: usize, lit: char, off: char) -> String;
@@ -82,9 +91,12 @@ where
                     .find(|(_, letter)| is_match(s, k, w, letter, WIDTH, HEIGHT, 0))
                     .map(|(idx, _)| (b'A' + idx as u8) as char)
                     .ok_or_else(|| {
+                        let (letter, confidence) = closest(s, k, w, &ALPHABET, WIDTH, HEIGHT, 0);
                         format!(
-                            "Could not parse {}th letter of\n{}",
+                            "Could not parse {}th letter (closest is {} with confidence {:.2}) of\n{}",
                             k + 1,
+                            letter,
+                            confidence,
                             s.printable_default(nl)
                         )
                     })
@@ -92,6 +104,15 @@ where
             .collect()
     }
 
+    fn decode_closest(&self, nl: usize) -> Vec<(char, f64)> {
+        let s = self.as_ref();
+        let (n, w) = dimensions(s, nl, WIDTH, HEIGHT, 0);
+
+        (0..n)
+            .map(|k| closest(s, k, w, &ALPHABET, WIDTH, HEIGHT, 0))
+            .collect()
+    }
+
     fn printable(&self, nl: usize, lit: char, dark: char) -> String {
         let s = self.as_ref();
         let (n, w) = dimensions(s, nl, WIDTH, HEIGHT, 0);
@@ -118,6 +139,13 @@ impl Letters for [char] {
         self.iter().map(|&c| c as u8).collect::<Vec<_>>().decode(nl)
     }
 
+    fn decode_closest(&self, nl: usize) -> Vec<(char, f64)> {
+        self.iter()
+            .map(|&c| c as u8)
+            .collect::<Vec<_>>()
+            .decode_closest(nl)
+    }
+
     fn printable(&self, nl: usize, lit: char, off: char) -> String {
         self.iter()
             .map(|&c| c as u8)
@@ -126,34 +154,52 @@ impl Letters for [char] {
     }
 }
 
+/// Render capital letters into a pixel display
+///
+/// Letters are separated by `space` dark pixels in addition to the dark column
+/// included in each letter. Each line is terminated with `nl`, which is
+/// typically `b"\n"`. The result can be decoded with [`Letters::decode`] if
+/// `space` is `0`, passing `nl.len()`.
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_utils::letters::*;
+/// let display = render("HI", 0, b"\n").unwrap();
+/// assert_eq!(Ok("HI".to_string()), display.decode(1));
+/// assert!(render("Hi", 0, b"\n").is_err());
+/// ```
+pub fn render(text: &str, space: usize, nl: &[u8]) -> Result<Vec<u8>, String> {
+    render_with(text, &ALPHABET, WIDTH, HEIGHT, space, nl)
+}
+
 pub mod big {
-    use super::{dimensions, is_match, Letters};
+    use super::{closest, dimensions, is_match, render_with, Letters};
 
-    pub const A: &[u8] = &[];
-    pub const B: &[u8] = &[];
+    pub const A: &[u8] = "..##...#..#.#....##....##....########....##....##....##....#".as_bytes();
+    pub const B: &[u8] = "#####.#....##....##....######.#....##....##....##....######.".as_bytes();
     pub const C: &[u8] = ".####.#....##.....#.....#.....#.....#.....#.....#....#.####.".as_bytes();
-    pub const D: &[u8] = &[];
+    pub const D: &[u8] = "#####.#....##....##....##....##....##....##....##....######.".as_bytes();
     pub const E: &[u8] = "#######.....#.....#.....#####.#.....#.....#.....#.....######".as_bytes();
-    pub const F: &[u8] = &[];
+    pub const F: &[u8] = "#######.....#.....#.....#####.#.....#.....#.....#.....#.....".as_bytes();
     pub const G: &[u8] = ".####.#....##.....#.....#.....#..####....##....##...##.###.#".as_bytes();
     pub const H: &[u8] = "#....##....##....##....########....##....##....##....##....#".as_bytes();
-    pub const I: &[u8] = "###.#..#..#..#..#..#.###".as_bytes();
-    pub const J: &[u8] = &[];
-    pub const K: &[u8] = &[];
+    pub const I: &[u8] = "..###....#.....#.....#.....#.....#.....#.....#.....#....###.".as_bytes();
+    pub const J: &[u8] = "...###....#.....#.....#.....#.....#.....#.#...#.#...#..###..".as_bytes();
+    pub const K: &[u8] = "#....##...#.#..#..#.#...##....##....#.#...#..#..#...#.#....#".as_bytes();
     pub const L: &[u8] = "#.....#.....#.....#.....#.....#.....#.....#.....#.....######".as_bytes();
-    pub const M: &[u8] = &[];
-    pub const N: &[u8] = &[];
-    pub const O: &[u8] = &[];
-    pub const P: &[u8] = &[];
-    pub const Q: &[u8] = &[];
-    pub const R: &[u8] = &[];
-    pub const S: &[u8] = &[];
-    pub const T: &[u8] = &[];
-    pub const U: &[u8] = &[];
-    pub const V: &[u8] = &[];
-    pub const W: &[u8] = &[];
-    pub const X: &[u8] = &[];
-    pub const Y: &[u8] = &[];
+    pub const M: &[u8] = "#....###..###.##.##.##.##....##....##....##....##....##....#".as_bytes();
+    pub const N: &[u8] = "#....###...###...##.#..##.#..##..#.##..#.##...###...###....#".as_bytes();
+    pub const O: &[u8] = ".####.#....##....##....##....##....##....##....##....#.####.".as_bytes();
+    pub const P: &[u8] = "#####.#....##....##....######.#.....#.....#.....#.....#.....".as_bytes();
+    pub const Q: &[u8] = ".####.#....##....##....##....##....##....##..#.##...#..###.#".as_bytes();
+    pub const R: &[u8] = "#####.#....##....##....######.#..#..#...#.#...#.#....##....#".as_bytes();
+    pub const S: &[u8] = ".####.#....##.....#......####......#.....#.....##....#.####.".as_bytes();
+    pub const T: &[u8] = "######..##....##....##....##....##....##....##....##....##..".as_bytes();
+    pub const U: &[u8] = "#....##....##....##....##....##....##....##....##....#.####.".as_bytes();
+    pub const V: &[u8] = "#....##....##....##....#.#..#..#..#..#..#...##....##....##..".as_bytes();
+    pub const W: &[u8] = "#....##....##....##....##....##.##.##.##.###..####..###....#".as_bytes();
+    pub const X: &[u8] = "#....##....#.#..#..#..#...##....##...#..#..#..#.#....##....#".as_bytes();
+    pub const Y: &[u8] = "#....##....#.#..#..#..#...##....##....##....##....##....##..".as_bytes();
     pub const Z: &[u8] = "######.....#.....#....#....#....#....#....#.....#.....######".as_bytes();
 
     pub const ALPHABET: [&[u8]; 26] = [
@@ -164,14 +210,37 @@ pub mod big {
     pub const HEIGHT: usize = 10;
     pub const SPACE: usize = 2;
 
+    /// Render capital letters into a pixel display with big letters
+    ///
+    /// Letters are separated by `space` dark pixels, lines are terminated with
+    /// `nl`. The result can be decoded with [`BigLetters::decode_big`] if `space`
+    /// is [`SPACE`], passing `nl.len()`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_utils::letters::big::*;
+    /// let display = render_big("HI", SPACE, b"\n").unwrap();
+    /// assert_eq!(Ok("HI".to_string()), display.decode_big(1));
+    /// ```
+    pub fn render_big(text: &str, space: usize, nl: &[u8]) -> Result<Vec<u8>, String> {
+        render_with(text, &ALPHABET, WIDTH, HEIGHT, space, nl)
+    }
+
     pub trait BigLetters {
         /// Decode a pixel display into letters
         ///
         /// The `nl` parameter specifies how many extra pixels are added at the
         /// end of each line. This is typically `0`, or `1` if `\n` 'pixels' are included
         ///
-        /// Each letter is supposed to be 5 pixels wide and 6 pixel high
+        /// Each letter is supposed to be 6 pixels wide and 10 pixel high with
+        /// 2 pixels space between letters
         fn decode_big(&self, nl: usize) -> Result<String, String>;
+
+        /// Decode a pixel display into the closest letters, tolerating pixel
+        /// errors
+        ///
+        /// See [`Letters::decode_closest`]
+        fn decode_big_closest(&self, nl: usize) -> Vec<(char, f64)>;
     }
 
     impl<T> BigLetters for T
@@ -190,15 +259,28 @@ pub mod big {
                         .find(|(_, letter)| is_match(s, k, w, letter, WIDTH, HEIGHT, SPACE))
                         .map(|(idx, _)| (b'A' + idx as u8) as char)
                         .ok_or_else(|| {
+                            let (letter, confidence) =
+                                closest(s, k, w, &ALPHABET, WIDTH, HEIGHT, SPACE);
                             format!(
-                                "Could not parse {}th letter of\n{}",
+                                "Could not parse {}th letter (closest is {} with confidence {:.2}) of\n{}",
                                 k + 1,
+                                letter,
+                                confidence,
                                 s.printable_default(nl)
                             )
                         })
                 })
                 .collect()
         }
+
+        fn decode_big_closest(&self, nl: usize) -> Vec<(char, f64)> {
+            let s = self.as_ref();
+            let (n, w) = dimensions(s, nl, WIDTH, HEIGHT, SPACE);
+
+            (0..n)
+                .map(|k| closest(s, k, w, &ALPHABET, WIDTH, HEIGHT, SPACE))
+                .collect()
+        }
     }
 
     impl BigLetters for [char] {
@@ -208,6 +290,13 @@ pub mod big {
                 .collect::<Vec<_>>()
                 .decode_big(nl)
         }
+
+        fn decode_big_closest(&self, nl: usize) -> Vec<(char, f64)> {
+            self.iter()
+                .map(|&c| c as u8)
+                .collect::<Vec<_>>()
+                .decode_big_closest(nl)
+        }
     }
 }
 
@@ -220,11 +309,6 @@ fn is_match(
     height: usize,
     space: usize,
 ) -> bool {
-    if letter.len() < width * height {
-        // letter not implemented
-        return false;
-    }
-
     for row in 0..height {
         for col in 0..width {
             if s[(width + space) * k + col + w * row] != letter[col + width * row] {
@@ -236,6 +320,78 @@ fn is_match(
     true
 }
 
+fn render_with(
+    text: &str,
+    alphabet: &[&[u8]; 26],
+    width: usize,
+    height: usize,
+    space: usize,
+    nl: &[u8],
+) -> Result<Vec<u8>, String> {
+    let letters = text
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' => Ok(alphabet[(b - b'A') as usize]),
+            _ => Err(format!(
+                "Cannot render {:?}, expected capital letter",
+                b as char
+            )),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let n = letters.len();
+    let w = n * width + n.saturating_sub(1) * space + nl.len();
+    let mut display = Vec::with_capacity(height * w);
+    for row in 0..height {
+        for (k, letter) in letters.iter().enumerate() {
+            if k > 0 {
+                display.extend((0..space).map(|_| DARK as u8));
+            }
+            display.extend_from_slice(&letter[width * row..width * (row + 1)]);
+        }
+        display.extend_from_slice(nl);
+    }
+
+    Ok(display)
+}
+
+/// Find the letter with the most pixels matching the `k`-th letter in the
+/// display, return it with the fraction of matching pixels
+fn closest(
+    s: &[u8],
+    k: usize,
+    w: usize,
+    alphabet: &[&[u8]; 26],
+    width: usize,
+    height: usize,
+    space: usize,
+) -> (char, f64) {
+    let (idx, matches) = alphabet
+        .iter()
+        .map(|letter| {
+            (0..height)
+                .flat_map(|row| (0..width).map(move |col| (col, row)))
+                .filter(|&(col, row)| {
+                    let pixel = s[(width + space) * k + col + w * row];
+                    (pixel == LIT as u8) == (letter[col + width * row] == LIT as u8)
+                })
+                .count()
+        })
+        .enumerate()
+        .fold((0, 0), |best, (idx, matches)| {
+            if matches > best.1 {
+                (idx, matches)
+            } else {
+                best
+            }
+        });
+
+    (
+        (b'A' + idx as u8) as char,
+        matches as f64 / (width * height) as f64,
+    )
+}
+
 fn dimensions<T: Any>(
     s: &[T],
     nl: usize,
@@ -288,6 +444,27 @@ mod tests {
         ));
     }
 
+    #[test]
+    pub fn test_render() {
+        let data = render("HZKHFEJZ", 0, b"\n").unwrap();
+        assert_eq!(format!("{DATA}\n").as_bytes(), data);
+
+        let data = render("HI", 2, b"|").unwrap();
+        let expected = [
+            "#..#....###.|",
+            "#..#.....#..|",
+            "####.....#..|",
+            "#..#.....#..|",
+            "#..#.....#..|",
+            "#..#....###.|",
+        ]
+        .concat();
+        assert_eq!(expected.as_bytes(), data);
+
+        assert_eq!(Ok(vec![b'\n'; 6]), render("", 0, b"\n"));
+        assert!(render("A B", 0, b"\n").is_err());
+    }
+
     #[test]
     pub fn test_decode() {
         let text = DATA.decode(1);
@@ -299,4 +476,58 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    pub fn test_alphabets() {
+        for (alphabet, len) in [(&ALPHABET, WIDTH * HEIGHT), (&big::ALPHABET, 60)] {
+            for (k, letter) in alphabet.iter().enumerate() {
+                assert_eq!(len, letter.len(), "{}", (b'A' + k as u8) as char);
+                assert!(!alphabet[k + 1..].contains(letter));
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_decode_alphabet() {
+        let expected = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+        assert_eq!(
+            Ok(expected.to_string()),
+            render(expected, 0, b"\n").unwrap().decode(1)
+        );
+        assert_eq!(
+            Ok(expected.to_string()),
+            render(expected, 0, b"").unwrap().decode(0)
+        );
+
+        use big::BigLetters;
+        assert_eq!(
+            Ok(expected.to_string()),
+            big::render_big(expected, big::SPACE, b"\n")
+                .unwrap()
+                .decode_big(1)
+        );
+    }
+
+    #[test]
+    pub fn test_decode_closest() {
+        let mut data = render("HQM", 0, b"\n").unwrap();
+        assert_eq!(
+            vec![('H', 1.0), ('Q', 1.0), ('M', 1.0)],
+            data.decode_closest(1)
+        );
+
+        // switch off bottom left pixel of Q
+        data[WIDTH + 1 + 5 * (3 * WIDTH + 1)] = DARK as u8;
+        let err = data.decode(1).unwrap_err();
+        assert!(err.starts_with("Could not parse 2th letter (closest is Q with confidence 0.97)"));
+        assert_eq!(('Q', 29.0 / 30.0), data.decode_closest(1)[1]);
+
+        use big::BigLetters;
+        let mut data = big::render_big("WV", big::SPACE, b"\n").unwrap();
+        data[0] = DARK as u8;
+        assert_eq!(
+            vec![('W', 59.0 / 60.0), ('V', 1.0)],
+            data.decode_big_closest(1)
+        );
+    }
 }
@@ -1,66 +1,11 @@
-pub mod permutations {
-
-    /// An iterator over all permutations using Heap's algorithm in a non-recursive form.
-    ///
-    /// See https://en.wikipedia.org/wiki/Heap%27s_algorithm
-    pub struct Permutations<T: Copy> {
-        items: Vec<T>,
-        c: Vec<usize>,
-        k: usize,
-        count: usize,
-    }
-
-    impl<T: Copy, D: IntoIterator<Item = T>> From<D> for Permutations<T> {
-        fn from(value: D) -> Self {
-            let items = Vec::from_iter(value);
-            let c = vec![0; items.len()];
-            Self {
-                items,
-                c,
-                k: 0,
-                count: 0,
-            }
-        }
-    }
-
-    impl<T: Copy> Iterator for Permutations<T> {
-        type Item = Vec<T>;
-
-        fn next(&mut self) -> Option<Self::Item> {
-            if self.count == 0 {
-                self.count = 1;
-                return Some(self.items.clone());
-            }
-
-            while self.k < self.items.len() {
-                if self.c[self.k] < self.k {
-                    if self.k & 1 == 0 {
-                        (self.items[0], self.items[self.k]) = (self.items[self.k], self.items[0]);
-                    } else {
-                        (self.items[self.c[self.k]], self.items[self.k]) =
-                            (self.items[self.k], self.items[self.c[self.k]]);
-                    }
-                    self.c[self.k] += 1;
-                    self.k = 0;
-                    self.count += 1;
-                    return Some(self.items.clone());
-                } else {
-                    self.c[self.k] = 0;
-                    self.k += 1;
-                }
-            }
-            None
-        }
-    }
-}
-
 pub mod euclid {
-    use std::ops::Rem;
+    use std::ops::{Div, Mul, Neg, Rem, Sub};
 
     /// Calculate multiplicate inverse of `a` modulo `m`
     /// with the [Extended Euclidean Algorithm](https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm)
     ///
-    /// The result is in the range `0..m` (inclusive lower, exclusive upper bound)
+    /// The result is in the range `0..m` (inclusive lower, exclusive upper
+    /// bound). It is `None` if `a` and `m` are not co-prime.
     ///
     /// # Examples
     ///
@@ -68,46 +13,16 @@ pub mod euclid {
     /// let a = 2;
     /// let m = 13;
     ///
-    /// let a_inv = mr_kaffee_utils::euclid::mul_inverse_mod(a, m);
+    /// let a_inv = mr_kaffee_utils::euclid::mul_inverse_mod(a, m).unwrap();
     ///
-    /// assert_eq!((a_inv * a) % m, 1)
-    /// ```
+    /// assert_eq!((a_inv * a) % m, 1);
     ///
-    /// # Panics
-    /// The function panics if `a` and `m` are not co-prime
-    /// ```should_panic
-    /// let a = 15;
-    /// let m = 27;
-    /// let a_inv = mr_kaffee_utils::euclid::mul_inverse_mod(a, m);
+    /// assert_eq!(None, mr_kaffee_utils::euclid::mul_inverse_mod(15, 27));
     /// ```
-    pub fn mul_inverse_mod(a: i64, m: i64) -> i64 {
-        let (mut t, mut old_t) = (0, 1);
-        let (mut r, mut old_r) = (m, a);
-
-        while old_r != 0 {
-            let quotient = r / old_r;
-
-            (r, old_r) = (old_r, r - quotient * old_r);
-            (t, old_t) = (old_t, t - quotient * old_t);
-        }
-
-        // if GCD != 1, there is no inverse
-        assert_eq!(
-            1,
-            r,
-            "a = {} = {} * {} and m = {} = {} * {} are not co-prime",
-            a,
-            a / r,
-            r,
-            m,
-            m / r,
-            r
-        );
-
-        if t < 0 {
-            t + m
-        } else {
-            t
+    pub fn mul_inverse_mod(a: i64, m: i64) -> Option<i64> {
+        match extended_gcd(a.rem_euclid(m), m) {
+            (1, t, _) => Some(t.rem_euclid(m)),
+            _ => None,
         }
     }
 
@@ -115,15 +30,51 @@ pub mod euclid {
         const ZERO: Self;
     }
 
-    macro_rules! impl_zero {
+    pub trait One {
+        const ONE: Self;
+    }
+
+    macro_rules! impl_zero_one {
         ($($t:ty),+) => {$(
             impl Zero for $t {
                 const ZERO: Self = 0;
             }
+
+            impl One for $t {
+                const ONE: Self = 1;
+            }
         )*};
     }
 
-    impl_zero!(usize, u8, u16, u32, u64, u128, isize, i8, i16, i32, i64, i128);
+    impl_zero_one!(usize, u8, u16, u32, u64, u128, isize, i8, i16, i32, i64, i128);
+
+    pub trait Abs {
+        fn abs(self) -> Self;
+    }
+
+    macro_rules! impl_abs_signed {
+        ($($t:ty),+) => {$(
+            impl Abs for $t {
+                fn abs(self) -> Self {
+                    <$t>::abs(self)
+                }
+            }
+        )*};
+    }
+
+    impl_abs_signed!(isize, i8, i16, i32, i64, i128);
+
+    macro_rules! impl_abs_unsigned {
+        ($($t:ty),+) => {$(
+            impl Abs for $t {
+                fn abs(self) -> Self {
+                    self
+                }
+            }
+        )*};
+    }
+
+    impl_abs_unsigned!(usize, u8, u16, u32, u64, u128);
 
     /// Calculate greatest common divisor
     pub fn gcd<T>(mut a: T, mut b: T) -> T
@@ -136,18 +87,181 @@ pub mod euclid {
         a
     }
 
+    /// Calculate the non-negative greatest common divisor `g` of `a` and `b`
+    /// together with Bezout coefficients `x` and `y`, such that
+    /// `a * x + b * y = g`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mr_kaffee_utils::euclid::extended_gcd;
+    /// let (g, x, y) = extended_gcd(240, -46);
+    /// assert_eq!(2, g);
+    /// assert_eq!(g, 240 * x - 46 * y);
+    /// ```
+    pub fn extended_gcd<T>(a: T, b: T) -> (T, T, T)
+    where
+        T: Copy
+            + Ord
+            + Zero
+            + One
+            + Sub<Output = T>
+            + Mul<Output = T>
+            + Div<Output = T>
+            + Neg<Output = T>,
+    {
+        let (mut r_0, mut r_1) = (a, b);
+        let (mut x_0, mut x_1) = (T::ONE, T::ZERO);
+        let (mut y_0, mut y_1) = (T::ZERO, T::ONE);
+        while r_1 != T::ZERO {
+            let q = r_0 / r_1;
+            (r_0, r_1) = (r_1, r_0 - q * r_1);
+            (x_0, x_1) = (x_1, x_0 - q * x_1);
+            (y_0, y_1) = (y_1, y_0 - q * y_1);
+        }
+
+        if r_0 < T::ZERO {
+            (-r_0, -x_0, -y_0)
+        } else {
+            (r_0, x_0, y_0)
+        }
+    }
+
+    /// Calculate the least common multiple of all values, `1` if there are no
+    /// values and `0` if any value is zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mr_kaffee_utils::euclid::lcm;
+    /// assert_eq!(60, lcm([4, 6, 10, 15]));
+    /// assert_eq!(1, lcm(Vec::<u64>::new()));
+    /// assert_eq!(0, lcm([0, 0]));
+    /// ```
+    pub fn lcm<T, I>(values: I) -> T
+    where
+        T: Eq + Rem<T, Output = T> + Mul<Output = T> + Div<Output = T> + Zero + One + Copy,
+        I: IntoIterator<Item = T>,
+    {
+        values.into_iter().fold(T::ONE, |result, value| {
+            if result == T::ZERO || value == T::ZERO {
+                T::ZERO
+            } else {
+                result / gcd(result, value) * value
+            }
+        })
+    }
+
+    /// Solve a system of congruences `x = a_k (mod m_k)` with the
+    /// [Chinese Remainder Theorem](https://en.wikipedia.org/wiki/Chinese_remainder_theorem)
+    ///
+    /// The moduli `m_k` must be positive but do not need to be co-prime. The
+    /// result is the smallest non-negative solution `x` together with the
+    /// least common multiple `m` of all moduli, so that all solutions are
+    /// `x + n * m`. It is `None` if the congruences are incompatible.
+    ///
+    /// Intermediate results are calculated with `i128`. The least common
+    /// multiple is checked to fit into an `i64` after every step, so nothing
+    /// overflows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use mr_kaffee_utils::euclid::crt;
+    /// assert_eq!(Some((39, 60)), crt([(3, 4), (3, 6), (4, 5)]));
+    /// assert_eq!(None, crt([(3, 4), (2, 6)]));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// The function panics if `m` does not fit into an `i64`.
+    pub fn crt<I>(congruences: I) -> Option<(i64, i64)>
+    where
+        I: IntoIterator<Item = (i64, i64)>,
+    {
+        let (x, m) =
+            congruences
+                .into_iter()
+                .try_fold((0i128, 1i128), |(a_0, m_0), (a_1, m_1)| {
+                    let (a_1, m_1) = (a_1 as i128, m_1 as i128);
+                    let (g, p, _) = extended_gcd(m_0, m_1);
+                    let d = a_1 - a_0;
+                    if d % g != 0 {
+                        return None;
+                    }
+
+                    // x = a_0 + m_0 * k with m_0 * k = d (mod m_1)
+                    let m_1_g = m_1 / g;
+                    let k = (d / g).rem_euclid(m_1_g) * p.rem_euclid(m_1_g) % m_1_g;
+                    let m = m_0 * m_1_g;
+                    i64::try_from(m).expect("least common multiple of moduli overflows i64");
+                    Some(((a_0 + m_0 * k).rem_euclid(m), m))
+                })?;
+
+        Some((x as i64, m as i64))
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
 
         #[test]
         pub fn test_mul_inv_mod() {
-            assert_eq!(mul_inverse_mod(1, 10), 1);
+            assert_eq!(mul_inverse_mod(1, 10), Some(1));
+            assert_eq!(mul_inverse_mod(-3, 10), Some(3));
+            assert_eq!(mul_inverse_mod(4, 10), None);
+        }
+
+        #[test]
+        pub fn test_extended_gcd() {
+            for (a, b) in [(0i64, 7), (7, 0), (12, 18), (-12, 18), (12, -18), (17, 5)] {
+                let (g, x, y) = extended_gcd(a, b);
+                assert_eq!(gcd(a, b).abs(), g);
+                assert_eq!(g, a * x + b * y);
+            }
+        }
+
+        #[test]
+        pub fn test_lcm() {
+            assert_eq!(12, lcm([4, 6]));
+            assert_eq!(0, lcm([0, 0]));
+            assert_eq!(0, lcm([0, 5]));
+            assert_eq!(0, lcm([5, 0, 7]));
+        }
+
+        #[test]
+        pub fn test_crt() {
+            let congruences = [(2, 3), (3, 5), (2, 7)];
+            assert_eq!(Some((23, 105)), crt(congruences));
+
+            // non co-prime moduli
+            assert_eq!(Some((10, 12)), crt([(2, 4), (4, 6)]));
+            assert_eq!(None, crt([(1, 4), (4, 6)]));
+
+            // large moduli whose product overflows i64 in intermediate steps
+            let (m_0, m_1) = (4_000_000_007, 2_000_000_011);
+            let (x, m) = crt([(m_0 - 1, m_0), (m_1 - 1, m_1)]).unwrap();
+            assert_eq!((m - 1, m_0 * m_1), (x, m));
+
+            assert_eq!(Some((0, 1)), crt([]));
+        }
+
+        #[test]
+        #[should_panic(expected = "overflows i64")]
+        pub fn test_crt_overflow() {
+            // the product of all three moduli would overflow i128
+            let m = i64::MAX;
+            crt([(1, m), (2, m - 1), (3, m - 2)]);
         }
     }
 }
 
 pub mod iterators {
+    use std::{
+        collections::{hash_map::Entry, HashMap},
+        hash::Hash,
+    };
+
     pub trait FoldWith<T> {
         /// Use this to compute the initial value of a fold from the iterator
         /// that is about being fold.
@@ -239,12 +353,12 @@ pub mod iterators {
     where
         It: Iterator<Item = T>,
     {
-        fn fold_short_cuttable<S, R>(mut self, initial: R, mut step: S) -> R
+        fn fold_short_cuttable<S, R>(self, initial: R, mut step: S) -> R
         where
             S: FnMut(R, T) -> Alt<R>,
         {
             let mut acc = initial;
-            while let Some(value) = self.next() {
+            for value in self {
                 acc = match step(acc, value) {
                     Ok(upd) => upd,
                     Err(acc) => return acc,
@@ -253,7 +367,250 @@ pub mod iterators {
             acc
         }
     }
+
+    /// The shape of an eventually periodic sequence of states `x_0`,
+    /// `x_1 = f(x_0)`, `x_2 = f(x_1)`, ...
+    ///
+    /// The first repeated state is `x_prefix = x_(prefix + period)`.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct Cycle {
+        pub prefix: usize,
+        pub period: usize,
+    }
+
+    impl Cycle {
+        /// The smallest index `i` with `x_i = x_n`
+        ///
+        /// # Examples
+        /// ```
+        /// # use mr_kaffee_utils::iterators::*;
+        ///
+        /// let cycle = Cycle { prefix: 3, period: 4 };
+        /// assert_eq!(2, cycle.reduce(2));
+        /// assert_eq!(5, cycle.reduce(9));
+        /// assert_eq!(3, cycle.reduce(1_000_000_003));
+        /// ```
+        pub fn reduce(&self, n: usize) -> usize {
+            if n < self.prefix {
+                n
+            } else {
+                self.prefix + (n - self.prefix) % self.period
+            }
+        }
+
+        /// The state after `n` steps from `init`, taking at most
+        /// `prefix + period - 1` steps
+        pub fn nth<T, F>(&self, init: T, mut step: F, n: usize) -> T
+        where
+            F: FnMut(&T) -> T,
+        {
+            (0..self.reduce(n)).fold(init, |state, _| step(&state))
+        }
+    }
+
+    /// Find the cycle of the states produced by `step` from `init` with
+    /// [Brent's algorithm](https://en.wikipedia.org/wiki/Cycle_detection#Brent's_algorithm)
+    ///
+    /// Only a constant number of states is kept in memory. The function does
+    /// not return if the sequence never repeats.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_utils::iterators::*;
+    ///
+    /// // powers of two modulo 11
+    /// let cycle = brent(1, |x| 2 * x % 11);
+    /// assert_eq!(Cycle { prefix: 0, period: 10 }, cycle);
+    ///
+    /// // 0, 1, 2, 3, 4, 5, 3, ...
+    /// let cycle = brent(0, |&x| if x == 5 { 3 } else { x + 1 });
+    /// assert_eq!(Cycle { prefix: 3, period: 3 }, cycle);
+    /// assert_eq!(4, cycle.nth(0, |&x| if x == 5 { 3 } else { x + 1 }, 1_000_000));
+    /// ```
+    pub fn brent<T, F>(init: T, mut step: F) -> Cycle
+    where
+        T: Clone + PartialEq,
+        F: FnMut(&T) -> T,
+    {
+        // find the period by moving the tortoise to the hare at powers of two
+        let (mut power, mut period) = (1, 1);
+        let mut tortoise = init.clone();
+        let mut hare = step(&init);
+        while tortoise != hare {
+            if power == period {
+                tortoise = hare.clone();
+                power *= 2;
+                period = 0;
+            }
+            hare = step(&hare);
+            period += 1;
+        }
+
+        // find the prefix with the hare period steps ahead
+        let mut tortoise = init.clone();
+        let mut hare = (0..period).fold(init, |state, _| step(&state));
+        let mut prefix = 0;
+        while tortoise != hare {
+            tortoise = step(&tortoise);
+            hare = step(&hare);
+            prefix += 1;
+        }
+
+        Cycle { prefix, period }
+    }
+
+    /// Find the cycle of the states produced by `step` from `init` with
+    /// [Floyd's algorithm](https://en.wikipedia.org/wiki/Cycle_detection#Floyd's_tortoise_and_hare)
+    ///
+    /// Only a constant number of states is kept in memory. The function does
+    /// not return if the sequence never repeats. [`brent`] usually needs fewer
+    /// steps.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_utils::iterators::*;
+    ///
+    /// let cycle = floyd(0, |&x| if x == 5 { 3 } else { x + 1 });
+    /// assert_eq!(Cycle { prefix: 3, period: 3 }, cycle);
+    /// ```
+    pub fn floyd<T, F>(init: T, mut step: F) -> Cycle
+    where
+        T: Clone + PartialEq,
+        F: FnMut(&T) -> T,
+    {
+        // find a multiple of the period with the hare moving twice as fast
+        let mut tortoise = step(&init);
+        let mut hare = step(&tortoise);
+        while tortoise != hare {
+            tortoise = step(&tortoise);
+            hare = step(&hare);
+            hare = step(&hare);
+        }
+
+        // find the prefix with the hare a multiple of the period ahead
+        let mut tortoise = init;
+        let mut prefix = 0;
+        while tortoise != hare {
+            tortoise = step(&tortoise);
+            hare = step(&hare);
+            prefix += 1;
+        }
+
+        // find the period
+        let mut period = 1;
+        let mut hare = step(&tortoise);
+        while tortoise != hare {
+            hare = step(&hare);
+            period += 1;
+        }
+
+        Cycle { prefix, period }
+    }
+
+    /// The states of an eventually periodic sequence up to the first
+    /// repetition, as found by [`find_cycle`]
+    #[derive(Debug, Clone)]
+    pub struct CycleStates<T> {
+        pub cycle: Cycle,
+        pub states: Vec<T>,
+    }
+
+    impl<T> CycleStates<T> {
+        /// The state after `n` steps
+        pub fn nth(&self, n: usize) -> &T {
+            &self.states[self.cycle.reduce(n)]
+        }
+    }
+
+    /// Find the cycle of the states produced by `step` from `init` by
+    /// remembering every state in a hash map
+    ///
+    /// Every state is computed exactly once, so this is the method of choice if
+    /// steps are expensive and states are small enough to be kept. The function
+    /// does not return if the sequence never repeats.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_utils::iterators::*;
+    ///
+    /// let CycleStates { cycle, states } = find_cycle(0, |&x| if x == 5 { 3 } else { x + 1 });
+    /// assert_eq!(Cycle { prefix: 3, period: 3 }, cycle);
+    /// assert_eq!(vec![0, 1, 2, 3, 4, 5], states);
+    /// ```
+    pub fn find_cycle<T, F>(init: T, mut step: F) -> CycleStates<T>
+    where
+        T: Clone + Eq + Hash,
+        F: FnMut(&T) -> T,
+    {
+        let mut indices = HashMap::from([(init.clone(), 0)]);
+        let mut states = vec![init];
+        loop {
+            match indices.entry(step(&states[states.len() - 1])) {
+                Entry::Occupied(o) => {
+                    let prefix = *o.get();
+                    let period = states.len() - prefix;
+                    return CycleStates {
+                        cycle: Cycle { prefix, period },
+                        states,
+                    };
+                }
+                Entry::Vacant(v) => {
+                    states.push(v.key().clone());
+                    v.insert(states.len() - 1);
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        pub fn test_find_cycle() {
+            // 0, 1, ..., 6, 2, 3, ...
+            let step = |&x: &u64| if x == 6 { 2 } else { x + 1 };
+            let expected = Cycle {
+                prefix: 2,
+                period: 5,
+            };
+            assert_eq!(expected, brent(0, step));
+            assert_eq!(expected, floyd(0, step));
+            let states = find_cycle(0, step);
+            assert_eq!(expected, states.cycle);
+
+            for n in 0..30 {
+                let x = (0..n).fold(0, |x, _| step(&x));
+                assert_eq!(x, expected.nth(0, step, n));
+                assert_eq!(&x, states.nth(n));
+            }
+
+            // fixed points
+            let expected = Cycle {
+                prefix: 0,
+                period: 1,
+            };
+            assert_eq!(expected, brent(7, |&x| x));
+            assert_eq!(expected, floyd(7, |&x| x));
+            assert_eq!(expected, find_cycle(7, |&x| x).cycle);
+        }
+    }
 }
 
+pub mod answers;
+pub mod bitset;
+pub mod directions;
+pub mod graph;
 pub mod grids;
+pub mod inputs;
+pub mod intervals;
 pub mod letters;
+pub mod linear;
+pub mod memo;
+pub mod parse;
+pub mod permutations;
+pub mod polygon;
+pub mod rational;
+pub mod search;
+pub mod solution;
+pub mod vector;
@@ -0,0 +1,384 @@
+//! Exact solution of systems of linear equations with integer coefficients
+//!
+//! Systems are solved with the fraction-free
+//! [Bareiss algorithm](https://en.wikipedia.org/wiki/Bareiss_algorithm) in its
+//! Gauss-Jordan form. All intermediate values are determinants of sub-matrices,
+//! so every division is exact.
+//!
+//! These determinants grow quickly: for coefficients in the order of `1e17`,
+//! a system of six equations easily produces intermediate values beyond
+//! `1e80`. The elimination therefore works on arbitrary precision integers.
+//! Only the reduced solution needs to fit in [`Rational`], otherwise an
+//! [`LinearError::Overflow`] is reported instead of a wrong result.
+//!
+//! # Examples
+//! ```
+//! # use mr_kaffee_utils::{linear::{solve, solve_integral, LinearError}, rational::Rational};
+//! // 2x + y = 5, x - y = 1
+//! assert_eq!(Ok([2, 1]), solve_integral([[2, 1, 5], [1, -1, 1]]));
+//!
+//! // x + 2y = 1, x - y = 0 has solution x = y = 1/3
+//! assert_eq!(Ok([Rational::new(1, 3); 2]), solve([[1, 2, 1], [1, -1, 0]]));
+//! assert_eq!(Err(LinearError::NotIntegral), solve_integral([[1, 2, 1], [1, -1, 0]]));
+//!
+//! // x + y = 1, 2x + 2y = 2 has infinitely many solutions
+//! assert_eq!(Err(LinearError::Singular), solve([[1, 1, 1], [2, 2, 2]]));
+//! ```
+use crate::rational::Rational;
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt,
+    ops::{Mul, Sub},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinearError {
+    /// The system has no or infinitely many solutions
+    Singular,
+    /// The solution does not fit in a [`Rational`]
+    Overflow,
+    /// The unique solution is not integral
+    NotIntegral,
+}
+
+impl fmt::Display for LinearError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Singular => write!(f, "singular system of equations"),
+            Self::Overflow => write!(f, "solution of system of equations overflows"),
+            Self::NotIntegral => write!(f, "solution is not integral"),
+        }
+    }
+}
+
+impl Error for LinearError {}
+
+/// Solve the system of `N` linear equations in `N` unknowns given by the
+/// augmented matrix `mat = [A | b]`, i.e., `A x = b`
+///
+/// # Panics
+/// If `M != N + 1`
+pub fn solve<const N: usize, const M: usize>(
+    mat: [[i128; M]; N],
+) -> Result<[Rational; N], LinearError> {
+    assert!(M == N + 1, "M == N + 1 expected");
+
+    let mut mat = mat.map(|row| row.map(BigInt::from));
+    let mut prev = BigInt::from(1);
+    for k in 0..N {
+        // pivot
+        let p = (k..N)
+            .find(|&p| !mat[p][k].is_zero())
+            .ok_or(LinearError::Singular)?;
+        mat.swap(k, p);
+
+        // eliminate column k in all other rows
+        let row_k = mat[k].clone();
+        for (_, row) in mat.iter_mut().enumerate().filter(|&(i, _)| i != k) {
+            for j in (0..M).filter(|&j| j != k) {
+                let v = &(&row_k[k] * &row[j]) - &(&row[k] * &row_k[j]);
+                let (q, r) = v.div_rem(&prev);
+                debug_assert!(r.is_zero(), "inexact division");
+                row[j] = q;
+            }
+            row[k] = BigInt::default();
+        }
+        prev = row_k[k].clone();
+    }
+
+    // all diagonal elements equal the determinant now
+    let mut x = [Rational::default(); N];
+    for (x, row) in x.iter_mut().zip(mat.iter()) {
+        let g = row[N].gcd(&prev);
+        let num = i128::try_from(&row[N].div_rem(&g).0)?;
+        let den = i128::try_from(&prev.div_rem(&g).0)?;
+        *x = Rational::new(num, den);
+    }
+    Ok(x)
+}
+
+/// Solve a system of linear equations like [`solve`], requiring the solution
+/// to be integral
+pub fn solve_integral<const N: usize, const M: usize>(
+    mat: [[i128; M]; N],
+) -> Result<[i128; N], LinearError> {
+    let x = solve(mat)?;
+    if x.iter().any(|x| !x.is_integer()) {
+        return Err(LinearError::NotIntegral);
+    }
+    Ok(x.map(|x| x.numer()))
+}
+
+/// Minimal arbitrary precision integer, just enough for the elimination
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct BigInt {
+    neg: bool,
+    /// little endian digits without leading zeros, empty for zero
+    mag: Vec<u32>,
+}
+
+impl BigInt {
+    fn new(neg: bool, mut mag: Vec<u32>) -> Self {
+        while mag.last() == Some(&0) {
+            mag.pop();
+        }
+        Self {
+            neg: neg && !mag.is_empty(),
+            mag,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.mag.is_empty()
+    }
+
+    /// Truncated division, the remainder has the sign of `self`
+    fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let (q, r) = div_rem_mag(&self.mag, &rhs.mag);
+        (Self::new(self.neg != rhs.neg, q), Self::new(self.neg, r))
+    }
+
+    /// The non-negative greatest common divisor
+    fn gcd(&self, rhs: &Self) -> Self {
+        let (mut a, mut b) = (self.mag.clone(), rhs.mag.clone());
+        while !b.is_empty() {
+            let r = div_rem_mag(&a, &b).1;
+            (a, b) = (b, r);
+        }
+        Self::new(false, a)
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(v: i128) -> Self {
+        let mut m = v.unsigned_abs();
+        let mut mag = Vec::new();
+        while m > 0 {
+            mag.push(m as u32);
+            m >>= 32;
+        }
+        Self::new(v < 0, mag)
+    }
+}
+
+impl TryFrom<&BigInt> for i128 {
+    type Error = LinearError;
+
+    fn try_from(v: &BigInt) -> Result<Self, Self::Error> {
+        if v.mag.len() > 4 {
+            return Err(LinearError::Overflow);
+        }
+        let m = v.mag.iter().rev().fold(0u128, |m, &d| m << 32 | d as u128);
+        match v.neg {
+            true => 0i128.checked_sub_unsigned(m),
+            false => i128::try_from(m).ok(),
+        }
+        .ok_or(LinearError::Overflow)
+    }
+}
+
+impl Mul for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        BigInt::new(self.neg != rhs.neg, mul_mag(&self.mag, &rhs.mag))
+    }
+}
+
+impl Sub for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        if self.neg != rhs.neg {
+            return BigInt::new(self.neg, add_mag(&self.mag, &rhs.mag));
+        }
+        match cmp_mag(&self.mag, &rhs.mag) {
+            Ordering::Less => BigInt::new(!self.neg, sub_mag(&rhs.mag, &self.mag)),
+            _ => BigInt::new(self.neg, sub_mag(&self.mag, &rhs.mag)),
+        }
+    }
+}
+
+fn cmp_mag(a: &[u32], b: &[u32]) -> Ordering {
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut res = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0;
+    for (i, &d) in a.iter().enumerate() {
+        let s = d as u64 + b.get(i).copied().unwrap_or(0) as u64 + carry;
+        res.push(s as u32);
+        carry = s >> 32;
+    }
+    if carry > 0 {
+        res.push(carry as u32);
+    }
+    res
+}
+
+/// Calculate `a - b`, requires `a >= b`
+fn sub_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut res = Vec::with_capacity(a.len());
+    let mut borrow = false;
+    for (i, &d) in a.iter().enumerate() {
+        let (s, b1) = d.overflowing_sub(b.get(i).copied().unwrap_or(0));
+        let (s, b2) = s.overflowing_sub(borrow as u32);
+        res.push(s);
+        borrow = b1 || b2;
+    }
+    debug_assert!(!borrow, "a >= b expected");
+    while res.last() == Some(&0) {
+        res.pop();
+    }
+    res
+}
+
+fn mul_mag(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut res = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0;
+        for (j, &y) in b.iter().enumerate() {
+            let t = x as u64 * y as u64 + res[i + j] as u64 + carry;
+            res[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        res[i + b.len()] = carry as u32;
+    }
+    res
+}
+
+/// Binary long division, returns quotient and remainder
+///
+/// # Panics
+/// If `b` is zero
+fn div_rem_mag(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    assert!(!b.is_empty(), "division by zero");
+    let mut q = vec![0u32; a.len()];
+    let mut r: Vec<u32> = Vec::with_capacity(b.len() + 1);
+    for i in (0..32 * a.len()).rev() {
+        // r = 2 r + bit i of a
+        let mut carry = (a[i / 32] >> (i % 32)) & 1;
+        for d in r.iter_mut() {
+            (*d, carry) = (*d << 1 | carry, *d >> 31);
+        }
+        if carry > 0 {
+            r.push(carry);
+        }
+
+        if cmp_mag(&r, b) != Ordering::Less {
+            r = sub_mag(&r, b);
+            q[i / 32] |= 1 << (i % 32);
+        }
+    }
+    (q, r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// check `A x = b`
+    fn check<const N: usize, const M: usize>(mat: [[i128; M]; N]) {
+        let x = solve(mat).unwrap();
+        for row in mat {
+            let lhs = row[..N]
+                .iter()
+                .zip(x)
+                .fold(Rational::default(), |lhs, (&a, x)| {
+                    lhs + Rational::from(a) * x
+                });
+            assert_eq!(Rational::from(row[N]), lhs, "{:?}", mat);
+        }
+    }
+
+    #[test]
+    pub fn test_big_int() {
+        let a = BigInt::from(i128::MAX);
+        let b = BigInt::from(-12_345_678_901_234_567_890);
+        let p = &a * &b;
+        assert_eq!((a.clone(), BigInt::default()), p.div_rem(&b));
+        assert_eq!(
+            (BigInt::from(3), BigInt::from(-2)),
+            BigInt::from(-17).div_rem(&BigInt::from(-5))
+        );
+        assert_eq!(BigInt::from(-2), &BigInt::from(3) - &BigInt::from(5));
+        assert_eq!(BigInt::from(8), &BigInt::from(3) - &BigInt::from(-5));
+        assert_eq!(BigInt::default(), &b - &b);
+        assert_eq!(BigInt::from(9), BigInt::from(-27).gcd(&BigInt::from(36)));
+        assert_eq!(Ok(i128::MIN), i128::try_from(&BigInt::from(i128::MIN)));
+        assert_eq!(Err(LinearError::Overflow), i128::try_from(&p));
+    }
+
+    #[test]
+    pub fn test_solve() {
+        check([[3, 5]]);
+        check([[2, 1, -1, 8], [-3, -1, 2, -11], [-2, 1, 2, -3]]);
+        assert_eq!(
+            Ok([2, 3, -1]),
+            solve_integral([[2, 1, -1, 8], [-3, -1, 2, -11], [-2, 1, 2, -3]])
+        );
+
+        // requires pivoting
+        check([[0, 1, 2], [1, 0, 3]]);
+        check([[0, 0, 1, 1], [0, 1, 0, 2], [1, 0, 0, 3]]);
+        assert_eq!(
+            Ok([3, 2, 1]),
+            solve_integral([[0, 0, 1, 1], [0, 1, 0, 2], [1, 0, 0, 3]])
+        );
+
+        // negative determinant
+        assert_eq!(Ok([Rational::new(1, 2)]), solve([[-2, -1]]));
+
+        // pseudo-random systems
+        let mut seed = 17i128;
+        let mut rand = || {
+            seed = (seed * 1_103_515_245 + 12_345) % (1 << 31);
+            seed % 41 - 20
+        };
+        for _ in 0..100 {
+            let mat: [[i128; 5]; 4] = std::array::from_fn(|_| std::array::from_fn(|_| rand()));
+            if solve(mat) != Err(LinearError::Singular) {
+                check(mat);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_large_intermediates() {
+        // x = (c, -c, 2c), intermediate products exceed i128
+        let c = 1 << 40;
+        assert_eq!(
+            Ok([c, -c, 2 * c]),
+            solve_integral([
+                [c + 1, c, 0, c],
+                [c, c + 1, 1, c],
+                [0, 1, c + 1, 2 * c * c + c]
+            ])
+        );
+    }
+
+    #[test]
+    pub fn test_errors() {
+        assert_eq!(Err(LinearError::Singular), solve([[0, 1]]));
+        assert_eq!(
+            Err(LinearError::Singular),
+            solve([[1, 2, 3, 1], [2, 4, 6, 2], [1, 0, 1, 0]])
+        );
+        // inconsistent
+        assert_eq!(Err(LinearError::Singular), solve([[1, 1, 1], [1, 1, 2]]));
+
+        assert_eq!(Err(LinearError::NotIntegral), solve_integral([[2, 1]]));
+
+        // x = -1 / (big^2 - 1)
+        let big = 1 << 100;
+        assert_eq!(
+            Err(LinearError::Overflow),
+            solve([[big, 1, 0], [1, big, 1]])
+        );
+    }
+}
@@ -0,0 +1,244 @@
+//! Memoization for recursive functions
+//!
+//! A [`Memo`] is a hash map from arguments to results that keeps track of hits
+//! and misses. It is passed explicitly through the recursion, so its keys may
+//! borrow from the data being processed, e.g., slices of the input. Whether a
+//! memo is shared across invocations is up to the caller: keep using the same
+//! instance or [`Memo::clear`] it in between.
+//!
+//! # Examples
+//! ```
+//! # use mr_kaffee_utils::memo::Memo;
+//! fn ways(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+//!     memo.memoize(n, |memo| match n {
+//!         0 | 1 => 1,
+//!         n => ways(n - 1, memo) + ways(n - 2, memo),
+//!     })
+//! }
+//!
+//! let mut memo = Memo::new();
+//! assert_eq!(89, ways(10, &mut memo));
+//! assert_eq!(11, memo.stats().len);
+//! assert_eq!(8, memo.stats().hits);
+//! ```
+use std::{collections::HashMap, fmt, hash::Hash};
+
+/// Statistics of a [`Memo`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoStats {
+    /// number of lookups that found a stored result
+    pub hits: usize,
+    /// number of lookups that did not find a stored result
+    pub misses: usize,
+    /// number of stored results
+    pub len: usize,
+}
+
+impl MemoStats {
+    /// The fraction of lookups that were hits, zero if there were no lookups
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            n => self.hits as f64 / n as f64,
+        }
+    }
+}
+
+impl fmt::Display for MemoStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate), {} entries",
+            self.hits,
+            self.misses,
+            100.0 * self.hit_rate(),
+            self.len
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    map: HashMap<K, V>,
+    max_len: usize,
+    hits: usize,
+    misses: usize,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            map: HashMap::new(),
+            max_len: usize::MAX,
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<K, V> Memo<K, V>
+where
+    K: Hash + Eq,
+    V: Clone,
+{
+    /// An unbounded memo
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An unbounded memo with space for `capacity` entries preallocated
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            map: HashMap::with_capacity(capacity),
+            ..Self::default()
+        }
+    }
+
+    /// A memo that stores at most `max_len` entries
+    ///
+    /// Once it is full, further results are not stored anymore. Stored results
+    /// are never evicted.
+    pub fn bounded(max_len: usize) -> Self {
+        Self {
+            map: HashMap::with_capacity(max_len.min(1 << 16)),
+            max_len,
+            ..Self::default()
+        }
+    }
+
+    /// A memo that never stores anything
+    ///
+    /// Use this to run a memoized function without memoization. Lookups are
+    /// counted as misses.
+    pub fn disabled() -> Self {
+        Self::bounded(0)
+    }
+
+    /// Look up the result stored for `key`
+    pub fn get(&mut self, key: &K) -> Option<V> {
+        let value = match self.max_len {
+            0 => None,
+            _ => self.map.get(key).cloned(),
+        };
+        match value {
+            Some(_) => self.hits += 1,
+            None => self.misses += 1,
+        }
+        value
+    }
+
+    /// Store `value` for `key` unless the memo is full, and return `value`
+    pub fn insert(&mut self, key: K, value: V) -> V {
+        if self.map.len() < self.max_len {
+            self.map.insert(key, value.clone());
+        }
+        value
+    }
+
+    /// Return the result stored for `key` or calculate it with `f`, which
+    /// receives the memo for recursive calls, and store it
+    pub fn memoize<F>(&mut self, key: K, f: F) -> V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        if let Some(value) = self.get(&key) {
+            return value;
+        }
+        let value = f(self);
+        self.insert(key, value)
+    }
+
+    /// Remove all stored results, keeping statistics and allocated space
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn stats(&self) -> MemoStats {
+        MemoStats {
+            hits: self.hits,
+            misses: self.misses,
+            len: self.map.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Number of ways to split `text` into words from `words`
+    fn splits<'a>(text: &'a [u8], words: &[&[u8]], memo: &mut Memo<&'a [u8], u64>) -> u64 {
+        if text.is_empty() {
+            return 1;
+        }
+        memo.memoize(text, |memo| {
+            words
+                .iter()
+                .filter(|w| text.starts_with(w))
+                .map(|w| splits(&text[w.len()..], words, memo))
+                .sum()
+        })
+    }
+
+    #[test]
+    pub fn test_memo() {
+        let mut memo = Memo::new();
+        assert_eq!(None, memo.get(&1));
+        assert_eq!(2, memo.insert(1, 2));
+        assert_eq!(Some(2), memo.get(&1));
+        assert_eq!(
+            MemoStats {
+                hits: 1,
+                misses: 1,
+                len: 1
+            },
+            memo.stats()
+        );
+        assert_eq!(0.5, memo.stats().hit_rate());
+
+        memo.clear();
+        assert!(memo.is_empty());
+        assert_eq!(None, memo.get(&1));
+        assert_eq!(2, memo.stats().misses);
+    }
+
+    #[test]
+    pub fn test_bounded() {
+        let mut memo = Memo::bounded(2);
+        for k in 0..4 {
+            memo.insert(k, k);
+        }
+        assert_eq!(2, memo.len());
+
+        let mut memo = Memo::disabled();
+        memo.insert(1, 1);
+        assert_eq!(None, memo.get(&1));
+        assert_eq!(0, memo.stats().hits);
+        assert_eq!(1, memo.stats().misses);
+    }
+
+    #[test]
+    pub fn test_slice_keys() {
+        let words: &[&[u8]] = &[b"a", b"ab", b"b", b"ba"];
+        let text = b"abababab";
+
+        let mut memo = Memo::new();
+        assert_eq!(34, splits(text, words, &mut memo));
+        let stats = memo.stats();
+        assert_eq!(text.len(), stats.len);
+        assert!(stats.hits > 0);
+
+        let mut memo = Memo::disabled();
+        assert_eq!(34, splits(text, words, &mut memo));
+        assert_eq!(0, memo.stats().hits);
+        assert!(memo.stats().misses > stats.misses);
+    }
+}
@@ -0,0 +1,211 @@
+//! Error type and helpers for fallible parsing of puzzle inputs
+//!
+//! Parsers typically split the input into lines and tokens, which are all
+//! sub-slices of the input. The position of a token within the input is thus
+//! known and used to report the line and column where parsing failed.
+use std::{error::Error, fmt, str::FromStr};
+
+/// Error returned if a puzzle input cannot be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line number, starting at 1
+    pub line: usize,
+    /// Column number in characters, starting at 1
+    pub column: usize,
+    /// Description of what was expected at that position
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, expected: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            expected: expected.into(),
+        }
+    }
+
+    /// Error at byte offset `offset` in `input`
+    pub fn at_offset(input: &[u8], offset: usize, expected: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset]
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |pos| pos + 1);
+        let line = input[..line_start].iter().filter(|&&b| b == b'\n').count() + 1;
+        // count UTF-8 start bytes to get the column in characters
+        let column = input[line_start..offset]
+            .iter()
+            .filter(|&&b| b & 0xc0 != 0x80)
+            .count()
+            + 1;
+        Self::new(line, column, expected)
+    }
+
+    /// Error at the start of `part`, which is a sub-slice of `input`
+    pub fn at(input: &str, part: &str, expected: impl Into<String>) -> Self {
+        Self::at_offset(
+            input.as_bytes(),
+            offset_of(input.as_bytes(), part.as_bytes()),
+            expected,
+        )
+    }
+
+    /// Error right after the end of `part`, which is a sub-slice of `input`
+    pub fn after(input: &str, part: &str, expected: impl Into<String>) -> Self {
+        Self::at_offset(
+            input.as_bytes(),
+            offset_of(input.as_bytes(), part.as_bytes()) + part.len(),
+            expected,
+        )
+    }
+
+    /// Translate an error with line and column relative to `part` into one
+    /// relative to `input`, where `part` is a sub-slice of `input`
+    pub fn within(self, input: &str, part: &str) -> Self {
+        let start = Self::at(input, part, "");
+        let column = if self.line == 1 {
+            start.column + self.column - 1
+        } else {
+            self.column
+        };
+        Self::new(start.line + self.line - 1, column, self.expected)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Byte offset of `part` in `input`, clamped to the length of `input` if `part`
+/// is not a sub-slice of `input`
+pub fn offset_of(input: &[u8], part: &[u8]) -> usize {
+    let offset = (part.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+    debug_assert!(offset <= input.len(), "Part is not a sub-slice of input");
+    offset.min(input.len())
+}
+
+/// Parse `token`, which is a sub-slice of `input`, reporting `expected` at the
+/// token's position if it cannot be parsed
+pub fn parse_token<T: FromStr>(input: &str, token: &str, expected: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(input, token, expected))
+}
+
+/// Check that `input` is a non-empty, rectangular grid made of symbols for
+/// which `is_symbol` holds, with lines separated by `'\n'` and an optional
+/// trailing newline
+///
+/// Returns the width and height of the grid.
+pub fn check_grid<F>(
+    input: &[u8],
+    is_symbol: F,
+    expected: &str,
+) -> Result<(usize, usize), ParseError>
+where
+    F: Fn(u8) -> bool,
+{
+    let data = input.strip_suffix(b"\n").unwrap_or(input);
+    let w = data.iter().position(|&b| b == b'\n').unwrap_or(data.len());
+    if w == 0 {
+        return Err(ParseError::at_offset(input, 0, expected));
+    }
+
+    let mut h = 0;
+    for line in data.split(|&b| b == b'\n') {
+        let offset = offset_of(input, line);
+        if let Some(col) = line.iter().position(|&b| !is_symbol(b)) {
+            return Err(ParseError::at_offset(input, offset + col, expected));
+        }
+        if line.len() != w {
+            return Err(ParseError::at_offset(
+                input,
+                offset + line.len().min(w),
+                format!("line of length {}", w),
+            ));
+        }
+        h += 1;
+    }
+
+    Ok((w, h))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "first line\nsecond äine\n";
+
+    #[test]
+    pub fn test_at() {
+        let line = INPUT.lines().nth(1).unwrap();
+        assert_eq!(
+            ParseError::new(2, 1, "something"),
+            ParseError::at(INPUT, line, "something")
+        );
+        assert_eq!(
+            ParseError::new(2, 9, "'i'"),
+            ParseError::at(INPUT, &line[9..], "'i'")
+        );
+        assert_eq!(
+            ParseError::new(2, 12, "more"),
+            ParseError::after(INPUT, line, "more")
+        );
+        assert_eq!(
+            "line 2, column 12: expected more",
+            ParseError::after(INPUT, line, "more").to_string()
+        );
+    }
+
+    #[test]
+    pub fn test_within() {
+        let line = INPUT.lines().nth(1).unwrap();
+        let part = &line[7..];
+        assert_eq!(
+            ParseError::new(2, 10, "'n'"),
+            ParseError::new(1, 3, "'n'").within(INPUT, part)
+        );
+        assert_eq!(
+            ParseError::new(3, 2, "x"),
+            ParseError::new(2, 2, "x").within(INPUT, line)
+        );
+    }
+
+    #[test]
+    pub fn test_parse_token() {
+        let input = "1 x 3";
+        assert_eq!(Ok(1), parse_token::<u8>(input, &input[..1], "number"));
+        assert_eq!(
+            Err(ParseError::new(1, 3, "number")),
+            parse_token::<u8>(input, &input[2..3], "number")
+        );
+    }
+
+    #[test]
+    pub fn test_check_grid() {
+        let is_symbol = |b| b == b'.' || b == b'#';
+        assert_eq!(
+            Ok((3, 2)),
+            check_grid(b"..#\n#..\n", is_symbol, "'.' or '#'")
+        );
+        assert_eq!(Ok((3, 2)), check_grid(b"..#\n#..", is_symbol, "'.' or '#'"));
+        assert_eq!(
+            Err(ParseError::new(2, 2, "'.' or '#'")),
+            check_grid(b"..#\n#x.\n", is_symbol, "'.' or '#'")
+        );
+        assert_eq!(
+            Err(ParseError::new(2, 3, "line of length 3")),
+            check_grid(b"..#\n#.\n", is_symbol, "'.' or '#'")
+        );
+        assert!(check_grid(b"", is_symbol, "'.' or '#'").is_err());
+    }
+}
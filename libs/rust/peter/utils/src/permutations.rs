@@ -0,0 +1,440 @@
+//! Permutations and combinations
+//!
+//! [`Permutations`] is a regular iterator which yields a new vector for every
+//! permutation. All other types are [`LendingIterator`]s, which yield slices
+//! borrowed from the iterator and do not allocate after construction. Use them
+//! with `while let Some(items) = it.next() { ... }`.
+
+/// An iterator whose items may borrow from the iterator itself
+pub trait LendingIterator {
+    type Item<'a>
+    where
+        Self: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>>;
+
+    /// Count the remaining items, consuming the iterator
+    fn count(mut self) -> usize
+    where
+        Self: Sized,
+    {
+        let mut count = 0;
+        while self.next().is_some() {
+            count += 1;
+        }
+        count
+    }
+}
+
+/// An iterator over all permutations using Heap's algorithm in a non-recursive form.
+///
+/// See https://en.wikipedia.org/wiki/Heap%27s_algorithm
+///
+/// Every permutation is returned as a new vector. Use [`HeapPermutations`] to
+/// avoid the allocations.
+pub struct Permutations<T: Copy> {
+    heap: HeapPermutations<T>,
+}
+
+impl<T: Copy, D: IntoIterator<Item = T>> From<D> for Permutations<T> {
+    fn from(value: D) -> Self {
+        Self {
+            heap: HeapPermutations::from(value),
+        }
+    }
+}
+
+impl<T: Copy> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.heap.next().map(|items| items.to_vec())
+    }
+}
+
+/// A lending iterator over all permutations in the order of Heap's algorithm
+///
+/// Consecutive permutations differ by a single swap.
+pub struct HeapPermutations<T> {
+    items: Vec<T>,
+    c: Vec<usize>,
+    k: usize,
+    count: usize,
+}
+
+impl<T, D: IntoIterator<Item = T>> From<D> for HeapPermutations<T> {
+    fn from(value: D) -> Self {
+        let items = Vec::from_iter(value);
+        let c = vec![0; items.len()];
+        Self {
+            items,
+            c,
+            k: 0,
+            count: 0,
+        }
+    }
+}
+
+impl<T> LendingIterator for HeapPermutations<T> {
+    type Item<'a>
+        = &'a [T]
+    where
+        T: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.count == 0 {
+            self.count = 1;
+            return Some(&self.items);
+        }
+
+        while self.k < self.items.len() {
+            if self.c[self.k] < self.k {
+                if self.k & 1 == 0 {
+                    self.items.swap(0, self.k);
+                } else {
+                    self.items.swap(self.c[self.k], self.k);
+                }
+                self.c[self.k] += 1;
+                self.k = 0;
+                self.count += 1;
+                return Some(&self.items);
+            } else {
+                self.c[self.k] = 0;
+                self.k += 1;
+            }
+        }
+        None
+    }
+}
+
+/// Rearrange `items` into the lexicographically next permutation
+///
+/// Return `false` and leave the items sorted in ascending order if they were
+/// sorted in descending order, i.e., there is no next permutation.
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_utils::permutations::next_permutation;
+/// let mut items = [1, 3, 2];
+/// assert!(next_permutation(&mut items));
+/// assert_eq!([2, 1, 3], items);
+///
+/// let mut items = [3, 2, 1];
+/// assert!(!next_permutation(&mut items));
+/// assert_eq!([1, 2, 3], items);
+/// ```
+pub fn next_permutation<T: Ord>(items: &mut [T]) -> bool {
+    // find the longest non-increasing suffix
+    let Some(pivot) = (1..items.len()).rev().find(|&k| items[k - 1] < items[k]) else {
+        items.reverse();
+        return false;
+    };
+
+    // swap the element before the suffix with its successor in the suffix
+    let successor = (pivot..items.len())
+        .rev()
+        .find(|&k| items[k] > items[pivot - 1])
+        .unwrap();
+    items.swap(pivot - 1, successor);
+
+    items[pivot..].reverse();
+    true
+}
+
+/// A lending iterator over all permutations in lexicographic order
+///
+/// If there are equal items, every distinct permutation is returned exactly
+/// once, i.e., this iterates over the permutations of a multiset.
+pub struct Lexicographic<T> {
+    items: Vec<T>,
+    started: bool,
+    done: bool,
+}
+
+impl<T: Ord, D: IntoIterator<Item = T>> From<D> for Lexicographic<T> {
+    fn from(value: D) -> Self {
+        let mut items = Vec::from_iter(value);
+        items.sort_unstable();
+        Self {
+            items,
+            started: false,
+            done: false,
+        }
+    }
+}
+
+impl<T: Ord> LendingIterator for Lexicographic<T> {
+    type Item<'a>
+        = &'a [T]
+    where
+        T: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.started {
+            self.done = self.done || !next_permutation(&mut self.items);
+        }
+        self.started = true;
+        (!self.done).then_some(&self.items)
+    }
+}
+
+/// Iterate over the distinct permutations of a multiset in lexicographic
+/// order
+///
+/// # Examples
+/// ```
+/// # use mr_kaffee_utils::permutations::*;
+/// assert_eq!(3, multiset_permutations([1, 2, 1]).count());
+/// ```
+pub fn multiset_permutations<T: Ord, D: IntoIterator<Item = T>>(items: D) -> Lexicographic<T> {
+    Lexicographic::from(items)
+}
+
+/// A lending iterator over all ordered selections of `k` items in
+/// lexicographic order
+///
+/// If there are equal items, every distinct selection is returned exactly once.
+pub struct KPermutations<T> {
+    items: Vec<T>,
+    k: usize,
+    started: bool,
+    done: bool,
+}
+
+impl<T: Ord> KPermutations<T> {
+    pub fn new<D: IntoIterator<Item = T>>(items: D, k: usize) -> Self {
+        let mut items = Vec::from_iter(items);
+        items.sort_unstable();
+        let done = k > items.len();
+        Self {
+            items,
+            k,
+            started: false,
+            done,
+        }
+    }
+}
+
+impl<T: Ord> LendingIterator for KPermutations<T> {
+    type Item<'a>
+        = &'a [T]
+    where
+        T: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.started && !self.done {
+            // with the unselected items in descending order, the next
+            // permutation of all items changes the selected ones
+            self.items[self.k..].reverse();
+            self.done = !next_permutation(&mut self.items);
+        }
+        self.started = true;
+        if self.done {
+            None
+        } else {
+            Some(&self.items[..self.k])
+        }
+    }
+}
+
+/// A lending iterator over all selections of `k` items, ignoring order, in
+/// lexicographic order of the items' positions
+///
+/// Items are not compared, so equal items at different positions yield
+/// equal combinations.
+pub struct Combinations<T> {
+    items: Vec<T>,
+    indices: Vec<usize>,
+    current: Vec<T>,
+    replacement: bool,
+    started: bool,
+    done: bool,
+}
+
+impl<T: Clone> Combinations<T> {
+    /// Combinations without replacement, i.e., every item is selected at most
+    /// once
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_utils::permutations::*;
+    /// let mut it = Combinations::new("abc".chars(), 2);
+    /// assert_eq!(Some(&['a', 'b'][..]), it.next());
+    /// assert_eq!(Some(&['a', 'c'][..]), it.next());
+    /// assert_eq!(Some(&['b', 'c'][..]), it.next());
+    /// assert_eq!(None, it.next());
+    /// ```
+    pub fn new<D: IntoIterator<Item = T>>(items: D, k: usize) -> Self {
+        Self::create(Vec::from_iter(items), k, false)
+    }
+
+    /// Combinations with replacement, i.e., every item may be selected any
+    /// number of times
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_utils::permutations::*;
+    /// let mut it = Combinations::with_replacement("ab".chars(), 2);
+    /// assert_eq!(Some(&['a', 'a'][..]), it.next());
+    /// assert_eq!(Some(&['a', 'b'][..]), it.next());
+    /// assert_eq!(Some(&['b', 'b'][..]), it.next());
+    /// assert_eq!(None, it.next());
+    /// ```
+    pub fn with_replacement<D: IntoIterator<Item = T>>(items: D, k: usize) -> Self {
+        Self::create(Vec::from_iter(items), k, true)
+    }
+
+    fn create(items: Vec<T>, k: usize, replacement: bool) -> Self {
+        let (indices, done) = if replacement {
+            (vec![0; k], k > 0 && items.is_empty())
+        } else {
+            ((0..k).collect(), k > items.len())
+        };
+        let current = if done {
+            Vec::new()
+        } else {
+            indices.iter().map(|&idx| items[idx].clone()).collect()
+        };
+        Self {
+            items,
+            indices,
+            current,
+            replacement,
+            started: false,
+            done,
+        }
+    }
+
+    /// Advance the indices, return `false` if there are no more combinations
+    fn advance(&mut self) -> bool {
+        let (n, k) = (self.items.len(), self.indices.len());
+        let step = if self.replacement { 0 } else { 1 };
+
+        // find the right-most index that can be incremented
+        let Some(i) = (0..k)
+            .rev()
+            .find(|&i| self.indices[i] < n - 1 - step * (k - 1 - i))
+        else {
+            return false;
+        };
+
+        self.indices[i] += 1;
+        for j in i + 1..k {
+            self.indices[j] = self.indices[j - 1] + step;
+        }
+        for j in i..k {
+            self.current[j] = self.items[self.indices[j]].clone();
+        }
+        true
+    }
+}
+
+impl<T: Clone> LendingIterator for Combinations<T> {
+    type Item<'a>
+        = &'a [T]
+    where
+        T: 'a;
+
+    fn next(&mut self) -> Option<Self::Item<'_>> {
+        if self.started && !self.done {
+            self.done = !self.advance();
+        }
+        self.started = true;
+        (!self.done).then_some(&self.current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn collect<I>(mut it: I) -> Vec<Vec<u8>>
+    where
+        I: for<'a> LendingIterator<Item<'a> = &'a [u8]>,
+    {
+        let mut all = Vec::new();
+        while let Some(items) = it.next() {
+            all.push(items.to_vec());
+        }
+        all
+    }
+
+    #[test]
+    pub fn test_permutations() {
+        let all = Permutations::from([1, 2, 3, 4]).collect::<Vec<_>>();
+        assert_eq!(24, all.len());
+        assert_eq!(24, all.iter().collect::<HashSet<_>>().len());
+        assert_eq!(all, collect(HeapPermutations::from([1, 2, 3, 4])));
+
+        assert_eq!(vec![Vec::<u8>::new()], collect(HeapPermutations::from([])));
+    }
+
+    #[test]
+    pub fn test_lexicographic() {
+        let all = collect(Lexicographic::from([3, 1, 2]));
+        assert_eq!(
+            vec![
+                vec![1, 2, 3],
+                vec![1, 3, 2],
+                vec![2, 1, 3],
+                vec![2, 3, 1],
+                vec![3, 1, 2],
+                vec![3, 2, 1]
+            ],
+            all
+        );
+
+        let all = collect(multiset_permutations([1, 1, 2, 2]));
+        assert_eq!(6, all.len());
+        assert_eq!(6, all.iter().collect::<HashSet<_>>().len());
+        assert!(all.windows(2).all(|w| w[0] < w[1]));
+
+        assert_eq!(vec![Vec::<u8>::new()], collect(Lexicographic::from([])));
+    }
+
+    #[test]
+    pub fn test_k_permutations() {
+        let all = collect(KPermutations::new([1, 2, 3, 4], 2));
+        assert_eq!(12, all.len());
+        assert_eq!(12, all.iter().collect::<HashSet<_>>().len());
+        assert!(all.windows(2).all(|w| w[0] < w[1]));
+
+        // with duplicates: 11, 12, 21, 22
+        assert_eq!(4, KPermutations::new([1, 1, 2, 2], 2).count());
+
+        assert_eq!(24, KPermutations::new([1, 2, 3, 4], 4).count());
+        assert_eq!(1, KPermutations::new([1, 2, 3, 4], 0).count());
+        assert_eq!(0, KPermutations::new([1, 2, 3, 4], 5).count());
+    }
+
+    #[test]
+    pub fn test_combinations() {
+        let all = collect(Combinations::new([1, 2, 3, 4, 5], 3));
+        assert_eq!(10, all.len());
+        assert!(all.windows(2).all(|w| w[0] < w[1]));
+        assert!(all.iter().all(|c| c.windows(2).all(|w| w[0] < w[1])));
+
+        assert_eq!(1, Combinations::new([1, 2], 0).count());
+        assert_eq!(0, Combinations::new([1, 2], 3).count());
+
+        let all = collect(Combinations::with_replacement([1, 2, 3], 2));
+        assert_eq!(
+            vec![
+                vec![1, 1],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 2],
+                vec![2, 3],
+                vec![3, 3]
+            ],
+            all
+        );
+        assert_eq!(
+            35,
+            Combinations::with_replacement([1, 2, 3, 4, 5], 3).count()
+        );
+        assert_eq!(1, Combinations::with_replacement([1, 2], 0).count());
+        assert_eq!(0, Combinations::<u8>::with_replacement([], 2).count());
+    }
+}
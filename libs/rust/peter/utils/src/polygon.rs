@@ -0,0 +1,240 @@
+//! Simple polygons with vertices on the integer lattice
+//!
+//! Areas are calculated with the
+//! [Shoelace formula](https://en.wikipedia.org/wiki/Shoelace_formula), lattice
+//! points are counted with [Pick's theorem](https://en.wikipedia.org/wiki/Pick%27s_theorem).
+//! Both require the polygon not to intersect itself, which can be checked with
+//! [`Polygon::is_self_intersecting`].
+use crate::{directions::Direction, euclid::gcd};
+
+pub type Vertex = (isize, isize);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    vertices: Vec<Vertex>,
+}
+
+impl Polygon {
+    /// Create a polygon from its vertices
+    ///
+    /// The polygon is closed implicitly, i.e., there is an edge from the last
+    /// to the first vertex. Consecutive duplicate vertices are dropped.
+    pub fn new<I: IntoIterator<Item = Vertex>>(vertices: I) -> Self {
+        let mut vertices = Vec::from_iter(vertices);
+        vertices.dedup();
+        if vertices.len() > 1 && vertices.first() == vertices.last() {
+            vertices.pop();
+        }
+        Self { vertices }
+    }
+
+    /// Create a polygon by walking from `start`, taking the given number of
+    /// steps in the given directions
+    ///
+    /// The steps are supposed to lead back to `start`.
+    ///
+    /// # Examples
+    /// ```
+    /// # use mr_kaffee_utils::{directions::Direction::*, polygon::Polygon};
+    /// let square = Polygon::from_steps((0, 0), [(East, 2), (South, 2), (West, 2), (North, 2)]);
+    /// assert_eq!(&[(0, 0), (2, 0), (2, 2), (0, 2)], square.vertices());
+    /// assert_eq!(9, square.lattice_points());
+    /// ```
+    pub fn from_steps<I>(start: Vertex, steps: I) -> Self
+    where
+        I: IntoIterator<Item = (Direction, isize)>,
+    {
+        let mut pos = start;
+        Self::new(
+            [start]
+                .into_iter()
+                .chain(steps.into_iter().map(|(d, n)| {
+                    pos = d.steps(pos, n);
+                    pos
+                }))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    pub fn vertices(&self) -> &[Vertex] {
+        &self.vertices
+    }
+
+    /// Iterate over the edges, including the one closing the polygon
+    pub fn edges(&self) -> impl Iterator<Item = (Vertex, Vertex)> + '_ {
+        let n = self.vertices.len();
+        (0..n).map(move |k| (self.vertices[k], self.vertices[(k + 1) % n]))
+    }
+
+    /// Twice the signed area, which is an integer for lattice polygons
+    ///
+    /// The sign is positive if the vertices are ordered counter-clockwise with
+    /// the y-axis pointing up (clockwise with rows growing downwards).
+    pub fn twice_signed_area(&self) -> isize {
+        self.edges()
+            .map(|((x0, y0), (x1, y1))| x0 * y1 - x1 * y0)
+            .sum()
+    }
+
+    /// The area enclosed by the polygon
+    pub fn area(&self) -> f64 {
+        self.twice_signed_area().abs() as f64 / 2.0
+    }
+
+    /// The length of all edges
+    pub fn perimeter(&self) -> f64 {
+        self.edges()
+            .map(|((x0, y0), (x1, y1))| (((x1 - x0).pow(2) + (y1 - y0).pow(2)) as f64).sqrt())
+            .sum()
+    }
+
+    /// The number of lattice points on edges
+    ///
+    /// For polygons with horizontal and vertical edges only, this is the
+    /// perimeter.
+    pub fn boundary_points(&self) -> isize {
+        self.edges()
+            .map(|((x0, y0), (x1, y1))| gcd((x1 - x0).abs(), (y1 - y0).abs()))
+            .sum()
+    }
+
+    /// The number of lattice points strictly inside the polygon
+    pub fn interior_points(&self) -> isize {
+        // Pick's theorem: A = I + B / 2 - 1
+        (self.twice_signed_area().abs() - self.boundary_points() + 2) / 2
+    }
+
+    /// The number of lattice points inside or on the boundary of the polygon
+    ///
+    /// If every lattice point stands for a unit square, this is the area
+    /// covered by the polygon including its boundary.
+    pub fn lattice_points(&self) -> isize {
+        self.interior_points() + self.boundary_points()
+    }
+
+    /// Whether any two edges intersect other than adjacent edges in their
+    /// common vertex
+    pub fn is_self_intersecting(&self) -> bool {
+        let edges = self.edges().collect::<Vec<_>>();
+        let n = edges.len();
+        (0..n).any(|i| {
+            (i + 1..n).any(|j| {
+                let (a, b) = (edges[i], edges[j]);
+                if j == i + 1 {
+                    overlap_at_common_vertex(a.0, a.1, b.1)
+                } else if i == 0 && j == n - 1 {
+                    overlap_at_common_vertex(a.1, a.0, b.0)
+                } else {
+                    segments_intersect(a, b)
+                }
+            })
+        })
+    }
+}
+
+/// The z-component of the cross product of `b - a` and `c - a`
+fn cross(a: Vertex, b: Vertex, c: Vertex) -> isize {
+    (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0)
+}
+
+/// Whether the segments `v..a` and `v..b` overlap in more than `v`
+fn overlap_at_common_vertex(a: Vertex, v: Vertex, b: Vertex) -> bool {
+    cross(v, a, b) == 0 && (a.0 - v.0) * (b.0 - v.0) + (a.1 - v.1) * (b.1 - v.1) > 0
+}
+
+/// Whether `p`, known to be collinear with segment `a..b`, is on the segment
+fn on_segment((a, b): (Vertex, Vertex), p: Vertex) -> bool {
+    a.0.min(b.0) <= p.0 && p.0 <= a.0.max(b.0) && a.1.min(b.1) <= p.1 && p.1 <= a.1.max(b.1)
+}
+
+fn segments_intersect(s: (Vertex, Vertex), t: (Vertex, Vertex)) -> bool {
+    let d1 = cross(t.0, t.1, s.0).signum();
+    let d2 = cross(t.0, t.1, s.1).signum();
+    let d3 = cross(s.0, s.1, t.0).signum();
+    let d4 = cross(s.0, s.1, t.1).signum();
+
+    (d1 * d2 < 0 && d3 * d4 < 0)
+        || (d1 == 0 && on_segment(t, s.0))
+        || (d2 == 0 && on_segment(t, s.1))
+        || (d3 == 0 && on_segment(s, t.0))
+        || (d4 == 0 && on_segment(s, t.1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_area() {
+        // rows growing downwards, walking clockwise
+        let p = Polygon::new([(0, 0), (4, 0), (4, 3), (0, 3), (0, 0)]);
+        assert_eq!(4, p.vertices().len());
+        assert_eq!(24, p.twice_signed_area());
+        assert_eq!(12.0, p.area());
+        assert_eq!(14.0, p.perimeter());
+        assert_eq!(14, p.boundary_points());
+        assert_eq!(6, p.interior_points());
+        assert_eq!(20, p.lattice_points());
+
+        // reverse orientation
+        let q = Polygon::new(p.vertices().iter().rev().copied());
+        assert_eq!(-24, q.twice_signed_area());
+        assert_eq!(6, q.interior_points());
+
+        // triangle with diagonal edge
+        let t = Polygon::new([(0, 0), (4, 0), (0, 2)]);
+        assert_eq!(8, t.twice_signed_area());
+        assert_eq!(4 + 2 + 2, t.boundary_points());
+        assert_eq!(1, t.interior_points());
+        assert!((t.perimeter() - (6.0 + 20f64.sqrt())).abs() < 1e-9);
+    }
+
+    #[test]
+    pub fn test_from_steps() {
+        use Direction::*;
+
+        // an L-shape
+        let p = Polygon::from_steps(
+            (0, 0),
+            [
+                (East, 2),
+                (South, 1),
+                (East, 1),
+                (South, 1),
+                (West, 3),
+                (North, 2),
+            ],
+        );
+        assert_eq!(6, p.vertices().len());
+        assert_eq!(10, p.boundary_points());
+        assert_eq!(1, p.interior_points());
+    }
+
+    #[test]
+    pub fn test_self_intersecting() {
+        let square = Polygon::new([(0, 0), (2, 0), (2, 2), (0, 2)]);
+        assert!(!square.is_self_intersecting());
+
+        let bow_tie = Polygon::new([(0, 0), (2, 2), (2, 0), (0, 2)]);
+        assert!(bow_tie.is_self_intersecting());
+
+        // touching itself in a vertex
+        let touching = Polygon::new([
+            (0, 0),
+            (2, 0),
+            (2, 2),
+            (4, 2),
+            (4, 4),
+            (2, 4),
+            (2, 2),
+            (0, 2),
+        ]);
+        assert!(touching.is_self_intersecting());
+
+        // walking back on the same line
+        let spike = Polygon::new([(0, 0), (3, 0), (1, 0), (1, 2)]);
+        assert!(spike.is_self_intersecting());
+        let spike = Polygon::new([(1, 0), (0, 0), (3, 0), (3, 2)]);
+        assert!(spike.is_self_intersecting());
+    }
+}
@@ -0,0 +1,269 @@
+//! Exact fractions with `i128` numerator and denominator
+//!
+//! A [`Rational`] is always normalized: the denominator is positive and
+//! co-prime to the numerator. Arithmetic reduces before multiplying to keep
+//! intermediate values small, but it still panics on overflow in debug builds
+//! like the primitive integer types.
+//!
+//! # Examples
+//! ```
+//! # use mr_kaffee_utils::rational::Rational;
+//! let a = Rational::new(1, 6);
+//! let b = Rational::new(-3, 4);
+//! assert_eq!(Rational::new(-7, 12), a + b);
+//! assert_eq!(Rational::new(-1, 8), a * b);
+//! assert_eq!("-2/9", (a / b).to_string());
+//! assert_eq!(Some(3), Rational::new(6, 2).to_integer());
+//! ```
+use crate::euclid::{gcd, Abs, One, Zero};
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    num: i128,
+    den: i128,
+}
+
+impl Rational {
+    /// The fraction `num / den`
+    ///
+    /// # Panics
+    /// If `den` is zero
+    pub fn new(num: i128, den: i128) -> Self {
+        assert!(den != 0, "zero denominator");
+        let g = gcd(num, den).abs();
+        let s = den.signum();
+        Self {
+            num: s * num / g,
+            den: s * den / g,
+        }
+    }
+
+    pub const fn from_integer(n: i128) -> Self {
+        Self { num: n, den: 1 }
+    }
+
+    pub fn numer(&self) -> i128 {
+        self.num
+    }
+
+    pub fn denom(&self) -> i128 {
+        self.den
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    pub fn to_integer(&self) -> Option<i128> {
+        self.is_integer().then_some(self.num)
+    }
+
+    /// The largest integer less than or equal to `self`
+    pub fn floor(&self) -> i128 {
+        self.num.div_euclid(self.den)
+    }
+
+    /// The smallest integer greater than or equal to `self`
+    pub fn ceil(&self) -> i128 {
+        -(-self.num).div_euclid(self.den)
+    }
+
+    pub fn signum(&self) -> i128 {
+        self.num.signum()
+    }
+
+    /// The multiplicative inverse
+    ///
+    /// # Panics
+    /// If `self` is zero
+    pub fn recip(&self) -> Self {
+        Self::new(self.den, self.num)
+    }
+}
+
+impl Zero for Rational {
+    const ZERO: Self = Self::from_integer(0);
+}
+
+impl One for Rational {
+    const ONE: Self = Self::from_integer(1);
+}
+
+impl Abs for Rational {
+    fn abs(self) -> Self {
+        Self {
+            num: self.num.abs(),
+            den: self.den,
+        }
+    }
+}
+
+impl Default for Rational {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),+) => {$(
+        impl From<$t> for Rational {
+            fn from(n: $t) -> Self {
+                Self::from_integer(n as _)
+            }
+        }
+    )+};
+}
+
+impl_from_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl Add for Rational {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let g = gcd(self.den, rhs.den);
+        Self::new(
+            self.num * (rhs.den / g) + rhs.num * (self.den / g),
+            self.den / g * rhs.den,
+        )
+    }
+}
+
+impl Sub for Rational {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for Rational {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let g1 = gcd(self.num, rhs.den).abs().max(1);
+        let g2 = gcd(rhs.num, self.den).abs().max(1);
+        Self {
+            num: (self.num / g1) * (rhs.num / g2),
+            den: (self.den / g2) * (rhs.den / g1),
+        }
+    }
+}
+
+impl Div for Rational {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.recip()
+    }
+}
+
+impl Neg for Rational {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+macro_rules! impl_assign_ops {
+    ($($tr:ident, $f:ident, $op:tt);+) => {$(
+        impl $tr for Rational {
+            fn $f(&mut self, rhs: Self) {
+                *self = *self $op rhs;
+            }
+        }
+    )+};
+}
+
+impl_assign_ops!(AddAssign, add_assign, +; SubAssign, sub_assign, -; MulAssign, mul_assign, *; DivAssign, div_assign, /);
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (*self - *other).num.cmp(&0)
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.den == 1 {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_normalize() {
+        assert_eq!(Rational::new(-1, 2), Rational::new(3, -6));
+        assert_eq!(
+            (0, 1),
+            (Rational::new(0, -5).numer(), Rational::new(0, -5).denom())
+        );
+        assert_eq!(Some(-4), Rational::new(8, -2).to_integer());
+        assert_eq!(None, Rational::new(1, 2).to_integer());
+    }
+
+    #[test]
+    pub fn test_arithmetic() {
+        let a = Rational::new(2, 3);
+        let b = Rational::new(-5, 6);
+        assert_eq!(Rational::new(-1, 6), a + b);
+        assert_eq!(Rational::new(3, 2), a - b);
+        assert_eq!(Rational::new(-5, 9), a * b);
+        assert_eq!(Rational::new(-4, 5), a / b);
+        assert_eq!(Rational::new(5, 6), -b);
+        assert_eq!(Rational::new(-6, 5), b.recip());
+
+        let mut c = a;
+        c += b;
+        c *= Rational::from(6);
+        c -= Rational::ONE;
+        c /= Rational::new(1, 2);
+        assert_eq!(Rational::from(-4), c);
+    }
+
+    #[test]
+    pub fn test_order_and_rounding() {
+        let mut values = [Rational::new(1, 3), Rational::new(-1, 2), Rational::ZERO];
+        values.sort();
+        assert_eq!(
+            [Rational::new(-1, 2), Rational::ZERO, Rational::new(1, 3)],
+            values
+        );
+
+        assert_eq!(
+            (-1, 0),
+            (Rational::new(-1, 2).floor(), Rational::new(-1, 2).ceil())
+        );
+        assert_eq!(
+            (2, 3),
+            (Rational::new(7, 3).floor(), Rational::new(7, 3).ceil())
+        );
+        assert_eq!(
+            (2, 2),
+            (Rational::from(2).floor(), Rational::from(2).ceil())
+        );
+        assert_eq!("7/3", Rational::new(7, 3).to_string());
+        assert_eq!("-2", Rational::from(-2).to_string());
+    }
+}
@@ -0,0 +1,444 @@
+//! Shortest path search: breadth-first search, Dijkstra and A*
+//!
+//! The graph is given implicitly by a successor function, which returns the
+//! nodes adjacent to a node (with the cost to move there for weighted
+//! searches). Searches start from any number of start nodes and end at the
+//! first node for which the target predicate holds. They return the cost to
+//! reach that node together with the path from a start node to it, both ends
+//! included. [`bfs_steps`] and [`dijkstra_costs`] search exhaustively
+//! instead and return the cost to reach every node.
+//!
+//! Costs are any ordered type that can be added with [`Default`] as zero.
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Marker for nodes without parent, i.e., start nodes
+const NO_PARENT: usize = usize::MAX;
+
+/// Explored nodes with their parents' indices
+struct Arena<N> {
+    nodes: Vec<(N, usize)>,
+    indices: HashMap<N, usize>,
+}
+
+impl<N: Clone + Eq + Hash> Arena<N> {
+    fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            indices: HashMap::new(),
+        }
+    }
+
+    /// Insert a node unless it is already known, return its index and whether
+    /// it was inserted
+    fn insert(&mut self, node: N, parent: usize) -> (usize, bool) {
+        match self.indices.entry(node) {
+            Entry::Occupied(o) => (*o.get(), false),
+            Entry::Vacant(v) => {
+                let idx = self.nodes.len();
+                self.nodes.push((v.key().clone(), parent));
+                v.insert(idx);
+                (idx, true)
+            }
+        }
+    }
+
+    fn path(&self, mut idx: usize) -> Vec<N> {
+        let mut path = Vec::new();
+        while idx != NO_PARENT {
+            let (node, parent) = &self.nodes[idx];
+            path.push(node.clone());
+            idx = *parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Breadth-first search, returning the number of steps and the path to the
+/// first target found
+pub fn bfs<N, IS, FS, IT, FT>(
+    starts: IS,
+    mut successors: FS,
+    mut is_target: FT,
+) -> Option<(usize, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    IS: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> IT,
+    IT: IntoIterator<Item = N>,
+    FT: FnMut(&N) -> bool,
+{
+    let mut arena = Arena::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let (idx, true) = arena.insert(start, NO_PARENT) {
+            queue.push_back((0, idx));
+        }
+    }
+
+    while let Some((steps, idx)) = queue.pop_front() {
+        let node = arena.nodes[idx].0.clone();
+        if is_target(&node) {
+            return Some((steps, arena.path(idx)));
+        }
+
+        for adj in successors(&node) {
+            if let (adj, true) = arena.insert(adj, idx) {
+                queue.push_back((steps + 1, adj));
+            }
+        }
+    }
+
+    None
+}
+
+/// All nodes reachable from the start nodes, including the start nodes
+pub fn reachable<N, IS, FS, IT>(starts: IS, mut successors: FS) -> HashSet<N>
+where
+    N: Clone + Eq + Hash,
+    IS: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> IT,
+    IT: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut queue = Vec::new();
+    for start in starts {
+        if seen.insert(start.clone()) {
+            queue.push(start);
+        }
+    }
+
+    while let Some(node) = queue.pop() {
+        for adj in successors(&node) {
+            if seen.insert(adj.clone()) {
+                queue.push(adj);
+            }
+        }
+    }
+
+    seen
+}
+
+/// Breadth-first search, returning the number of steps to every node that is
+/// reachable from the start nodes in at most `max_steps` steps
+pub fn bfs_steps<N, IS, FS, IT>(
+    starts: IS,
+    mut successors: FS,
+    max_steps: usize,
+) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    IS: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> IT,
+    IT: IntoIterator<Item = N>,
+{
+    let mut steps = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let Entry::Vacant(v) = steps.entry(start) {
+            queue.push_back((0, v.key().clone()));
+            v.insert(0);
+        }
+    }
+
+    while let Some((s, node)) = queue.pop_front() {
+        if s >= max_steps {
+            // all nodes left in the queue are at least as far away
+            break;
+        }
+
+        for adj in successors(&node) {
+            if let Entry::Vacant(v) = steps.entry(adj) {
+                queue.push_back((s + 1, v.key().clone()));
+                v.insert(s + 1);
+            }
+        }
+    }
+
+    steps
+}
+
+/// Dijkstra's algorithm, returning the cost and the path to the cheapest
+/// target
+pub fn dijkstra<N, C, IS, FS, IT, FT>(
+    starts: IS,
+    successors: FS,
+    is_target: FT,
+) -> Option<(C, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    IS: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> IT,
+    IT: IntoIterator<Item = (N, C)>,
+    FT: FnMut(&N) -> bool,
+{
+    astar(starts, successors, |_| C::default(), is_target)
+}
+
+/// A* search, returning the cost and the path to the cheapest target
+///
+/// The `heuristic` must never over-estimate the cost to reach a target from a
+/// node. Otherwise, the result is not guaranteed to be optimal.
+pub fn astar<N, C, IS, FS, IT, FH, FT>(
+    starts: IS,
+    mut successors: FS,
+    mut heuristic: FH,
+    mut is_target: FT,
+) -> Option<(C, Vec<N>)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    IS: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> IT,
+    IT: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FT: FnMut(&N) -> bool,
+{
+    let mut arena = Arena::new();
+    let mut costs = Vec::new();
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        if let (idx, true) = arena.insert(start, NO_PARENT) {
+            costs.push(C::default());
+            heap.push(Reverse((heuristic(&arena.nodes[idx].0), C::default(), idx)));
+        }
+    }
+
+    while let Some(Reverse((_, cost, idx))) = heap.pop() {
+        if cost > costs[idx] {
+            // there is a cheaper way to this node
+            continue;
+        }
+
+        let node = arena.nodes[idx].0.clone();
+        if is_target(&node) {
+            return Some((cost, arena.path(idx)));
+        }
+
+        for (adj, weight) in successors(&node) {
+            let cost = cost + weight;
+            let (adj, inserted) = arena.insert(adj, idx);
+            if inserted {
+                costs.push(cost);
+            } else if cost < costs[adj] {
+                costs[adj] = cost;
+                arena.nodes[adj].1 = idx;
+            } else {
+                continue;
+            }
+            heap.push(Reverse((cost + heuristic(&arena.nodes[adj].0), cost, adj)));
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's algorithm, returning the cost of every node that can be reached
+/// at a cost of at most `max_cost`
+///
+/// Every start node comes with its initial cost.
+pub fn dijkstra_costs<N, C, IS, FS, IT>(
+    starts: IS,
+    mut successors: FS,
+    max_cost: C,
+) -> HashMap<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C>,
+    IS: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> IT,
+    IT: IntoIterator<Item = (N, C)>,
+{
+    let mut arena = Arena::new();
+    let mut costs = Vec::new();
+    let mut heap = BinaryHeap::new();
+    let starts = starts
+        .into_iter()
+        .map(|(start, cost)| (start, cost, NO_PARENT));
+    for (node, cost, parent) in starts {
+        if let Some(idx) = relax(&mut arena, &mut costs, node, cost, parent, max_cost) {
+            heap.push(Reverse((cost, idx)));
+        }
+    }
+
+    while let Some(Reverse((cost, idx))) = heap.pop() {
+        if cost > costs[idx] {
+            // there is a cheaper way to this node
+            continue;
+        }
+
+        let node = arena.nodes[idx].0.clone();
+        for (adj, weight) in successors(&node) {
+            let cost = cost + weight;
+            if let Some(adj) = relax(&mut arena, &mut costs, adj, cost, idx, max_cost) {
+                heap.push(Reverse((cost, adj)));
+            }
+        }
+    }
+
+    arena
+        .nodes
+        .into_iter()
+        .map(|(node, _)| node)
+        .zip(costs)
+        .collect()
+}
+
+/// Record `cost` for `node` if it does not exceed `max_cost` and improves on
+/// the cost known so far, return the node's index if so
+fn relax<N: Clone + Eq + Hash, C: Copy + Ord>(
+    arena: &mut Arena<N>,
+    costs: &mut Vec<C>,
+    node: N,
+    cost: C,
+    parent: usize,
+    max_cost: C,
+) -> Option<usize> {
+    if cost > max_cost {
+        return None;
+    }
+
+    let (idx, inserted) = arena.insert(node, parent);
+    if inserted {
+        costs.push(cost);
+    } else if cost < costs[idx] {
+        costs[idx] = cost;
+    } else {
+        return None;
+    }
+    Some(idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Maze with walls '#', start 'S' and target 'T'
+    const MAZE: &[&[u8]] = &[
+        b"S..#....",
+        b".#.#.##.",
+        b".#......",
+        b".####.#.",
+        b"......#T",
+    ];
+
+    fn adjacents((x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        [(1, 0), (0, 1), (-1, 0), (0, -1)]
+            .into_iter()
+            .map(move |(dx, dy)| (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy)))
+            .filter(|&(x, y)| y < MAZE.len() && x < MAZE[y].len() && MAZE[y][x] != b'#')
+    }
+
+    fn is_target(&(x, y): &(usize, usize)) -> bool {
+        MAZE[y][x] == b'T'
+    }
+
+    fn check_path(path: &[(usize, usize)]) {
+        assert_eq!(Some(&(0, 0)), path.first());
+        assert!(is_target(path.last().unwrap()));
+        for w in path.windows(2) {
+            assert!(adjacents(w[0]).any(|adj| adj == w[1]));
+        }
+    }
+
+    #[test]
+    pub fn test_bfs() {
+        let (steps, path) = bfs([(0, 0)], |&pos| adjacents(pos), is_target).unwrap();
+        assert_eq!(11, steps);
+        assert_eq!(steps + 1, path.len());
+        check_path(&path);
+
+        assert_eq!(None, bfs([(0, 0)], |&pos| adjacents(pos), |_| false));
+        assert_eq!(Some((0, vec![(0, 0)])), bfs([(0, 0)], |_| [], |_| true));
+    }
+
+    #[test]
+    pub fn test_reachable() {
+        let cells = MAZE
+            .iter()
+            .flat_map(|row| row.iter())
+            .filter(|&&b| b != b'#');
+        assert_eq!(
+            cells.count(),
+            reachable([(0, 0)], |&pos| adjacents(pos)).len()
+        );
+    }
+
+    #[test]
+    pub fn test_bfs_steps() {
+        let steps = bfs_steps([(0, 0)], |&pos| adjacents(pos), usize::MAX);
+        assert_eq!(
+            reachable([(0, 0)], |&pos| adjacents(pos)).len(),
+            steps.len()
+        );
+        assert_eq!(Some(&11), steps.get(&(7, 4)));
+        assert_eq!(Some(&4), steps.get(&(2, 2)));
+
+        let steps = bfs_steps([(0, 0)], |&pos| adjacents(pos), 2);
+        assert_eq!(5, steps.len());
+        assert!(steps.values().all(|&s| s <= 2));
+    }
+
+    #[test]
+    pub fn test_dijkstra_costs() {
+        let successors = |&(x, y): &(usize, usize)| {
+            adjacents((x, y)).map(move |(x1, y1)| ((x1, y1), if y1 > y { 5 } else { 1 }))
+        };
+        let costs = dijkstra_costs([((0, 0), 0)], successors, usize::MAX);
+        assert_eq!(Some(&27), costs.get(&(7, 4)));
+        assert_eq!(
+            dijkstra([(0, 0)], successors, |&pos| pos == (5, 3)).map(|(cost, _)| cost),
+            costs.get(&(5, 3)).copied()
+        );
+
+        // start costs and cost limit
+        let costs = dijkstra_costs([((0, 0), 3), ((7, 4), 0)], successors, 3);
+        assert_eq!(
+            HashMap::from([
+                ((0, 0), 3),
+                ((7, 4), 0),
+                ((7, 3), 1),
+                ((7, 2), 2),
+                ((7, 1), 3),
+                ((6, 2), 3)
+            ]),
+            costs
+        );
+    }
+
+    #[test]
+    pub fn test_dijkstra() {
+        // going down is expensive
+        let successors = |&(x, y): &(usize, usize)| {
+            adjacents((x, y)).map(move |(x1, y1)| ((x1, y1), if y1 > y { 5 } else { 1 }))
+        };
+        let (cost, path) = dijkstra([(0, 0)], successors, is_target).unwrap();
+        assert_eq!(27, cost);
+        check_path(&path);
+        assert_eq!(
+            cost,
+            path.windows(2)
+                .map(|w| if w[1].1 > w[0].1 { 5 } else { 1 })
+                .sum::<usize>()
+        );
+    }
+
+    #[test]
+    pub fn test_astar() {
+        let successors = |&pos: &(usize, usize)| adjacents(pos).map(|adj| (adj, 1));
+        let manhattan = |&(x, y): &(usize, usize)| 7 - x + 4 - y;
+        let (cost, path) = astar([(0, 0)], successors, manhattan, is_target).unwrap();
+        assert_eq!(11, cost);
+        check_path(&path);
+
+        // multiple starts
+        let (cost, path) = astar([(0, 0), (7, 0)], successors, manhattan, is_target).unwrap();
+        assert_eq!((4, Some(&(7, 0))), (cost, path.first()));
+    }
+}
@@ -0,0 +1,121 @@
+//! Common interface implemented by the solutions of all days
+//!
+//! The trait allows to write tooling such as runners, benchmarks or answer
+//! checkers generically instead of per day.
+use crate::{inputs, parse::ParseError};
+use std::{fmt::Display, io, path::Path};
+
+pub trait Solution {
+    /// Identifier of the puzzle in the form `yyyy/dd`
+    const IDENTIFIER: &'static str;
+
+    /// The parsed puzzle input, which may borrow from the raw input
+    type Input<'a>;
+
+    /// Type of the answer to the first star
+    type Answer1: Display;
+
+    /// Type of the answer to the second star, use
+    /// [`std::convert::Infallible`] if there is no second star
+    type Answer2: Display;
+
+    /// The day of the puzzle as given by the last part of the identifier
+    fn day() -> u8 {
+        Self::IDENTIFIER
+            .rsplit('/')
+            .next()
+            .and_then(|day| day.parse().ok())
+            .unwrap_or_else(|| panic!("Bad identifier: '{}'", Self::IDENTIFIER))
+    }
+
+    /// Read the raw puzzle input from `path` or from the default location if
+    /// no path is given, see [`inputs::read_input`]
+    fn read_input(path: Option<&Path>) -> io::Result<String> {
+        inputs::read_input(Self::day(), path)
+    }
+
+    /// Parse the raw puzzle input
+    fn parse(input: &str) -> Self::Input<'_>;
+
+    /// Parse the raw puzzle input, reporting malformed input as error instead
+    /// of panicking
+    ///
+    /// The default implementation delegates to [`Solution::parse`] and is
+    /// meant for solutions whose parsing cannot fail.
+    fn try_parse(input: &str) -> Result<Self::Input<'_>, ParseError> {
+        Ok(Self::parse(input))
+    }
+
+    /// Solve the first star
+    fn star_1(input: &Self::Input<'_>) -> Self::Answer1;
+
+    /// Solve the second star, returns `None` if there is no second star
+    fn star_2(_input: &Self::Input<'_>) -> Option<Self::Answer2> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+
+    struct Words;
+
+    impl Solution for Words {
+        const IDENTIFIER: &'static str = "2023/07";
+
+        type Input<'a> = Vec<&'a str>;
+        type Answer1 = usize;
+        type Answer2 = String;
+
+        fn parse(input: &str) -> Self::Input<'_> {
+            input.split_ascii_whitespace().collect()
+        }
+
+        fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+            input.len()
+        }
+
+        fn star_2(input: &Self::Input<'_>) -> Option<Self::Answer2> {
+            input
+                .iter()
+                .max_by_key(|word| word.len())
+                .map(|w| w.to_string())
+        }
+    }
+
+    struct OneStar;
+
+    impl Solution for OneStar {
+        const IDENTIFIER: &'static str = "2023/25";
+
+        type Input<'a> = &'a str;
+        type Answer1 = usize;
+        type Answer2 = Infallible;
+
+        fn parse(input: &str) -> Self::Input<'_> {
+            input
+        }
+
+        fn star_1(input: &Self::Input<'_>) -> Self::Answer1 {
+            input.len()
+        }
+    }
+
+    #[test]
+    pub fn test_solution() {
+        assert_eq!(7, Words::day());
+        let input = Words::parse("a quickly brown fox");
+        assert_eq!(4, Words::star_1(&input));
+        assert_eq!(Some("quickly".to_string()), Words::star_2(&input));
+    }
+
+    #[test]
+    pub fn test_solution_one_star() {
+        assert_eq!(25, OneStar::day());
+        let input = OneStar::parse("abc");
+        assert_eq!(3, OneStar::star_1(&input));
+        assert_eq!(None, OneStar::star_2(&input));
+    }
+}
@@ -0,0 +1,415 @@
+//! Vectors and lines in two, three or more dimensions
+//!
+//! A [`Vector`] is a fixed size array of coordinates with component-wise
+//! arithmetic. It is used for points (position vectors) as well as for
+//! directions and velocities. Coordinates are typically integers or
+//! [`Rational`]s, so that all calculations are exact.
+//!
+//! A [`Line`] is given by a point and a direction vector. Intersections are
+//! returned as exact rational line parameters.
+//!
+//! # Examples
+//! ```
+//! # use mr_kaffee_utils::{euclid::One, rational::Rational, vector::{Line, Vec2D, Vec3D}};
+//! let a = Vec3D::from((1, -2, 3));
+//! let b = Vec3D::from((4, 0, -1));
+//! assert_eq!(Vec3D::from((5, -2, 2)), a + b);
+//! assert_eq!(1, a.dot(b));
+//! assert_eq!(Vec3D::from((2, 13, 8)), a.cross(b));
+//! assert_eq!(9, (a - b).manhattan());
+//! assert_eq!(4, (a - b).chebyshev());
+//!
+//! // (0, 0) + t (2, 1) and (3, 0) + t (-1, 1) intersect at t = 1
+//! let l1 = Line::new(Vec2D::from((0, 0)), Vec2D::from((2, 1)));
+//! let l2 = Line::new(Vec2D::from((3, 0)), Vec2D::from((-1, 1)));
+//! assert_eq!(Some((Rational::ONE, Rational::ONE)), l1.intersect(&l2));
+//! assert_eq!(Vec2D::from((2, 1)), l1.at(1));
+//! ```
+use crate::{
+    euclid::{gcd, Abs, Zero},
+    rational::Rational,
+};
+use std::{
+    iter::Sum,
+    ops::{
+        Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Rem, Sub, SubAssign,
+    },
+};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub struct Vector<T, const D: usize>(pub [T; D]);
+
+pub type Vec2D<T> = Vector<T, 2>;
+pub type Vec3D<T> = Vector<T, 3>;
+
+impl<T, const D: usize> Vector<T, D> {
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> Vector<U, D> {
+        Vector(self.0.map(f))
+    }
+}
+
+impl<T: Copy, const D: usize> Vector<T, D> {
+    pub fn x(&self) -> T {
+        self.0[0]
+    }
+
+    pub fn y(&self) -> T {
+        self.0[1]
+    }
+
+    pub fn z(&self) -> T {
+        self.0[2]
+    }
+}
+
+impl<T, const D: usize> Vector<T, D>
+where
+    T: Copy + Zero + Add<Output = T> + Mul<Output = T>,
+{
+    pub fn dot(self, rhs: Self) -> T {
+        self.0
+            .into_iter()
+            .zip(rhs.0)
+            .fold(T::ZERO, |dot, (a, b)| dot + a * b)
+    }
+}
+
+impl<T: Copy + Mul<Output = T> + Sub<Output = T>> Vec2D<T> {
+    /// The z-component of the cross product of the vectors extended to 3D
+    pub fn perp_dot(self, rhs: Self) -> T {
+        self.0[0] * rhs.0[1] - self.0[1] * rhs.0[0]
+    }
+}
+
+impl<T: Copy + Mul<Output = T> + Sub<Output = T>> Vec3D<T> {
+    pub fn cross(self, rhs: Self) -> Self {
+        Self([
+            self.0[1] * rhs.0[2] - self.0[2] * rhs.0[1],
+            self.0[2] * rhs.0[0] - self.0[0] * rhs.0[2],
+            self.0[0] * rhs.0[1] - self.0[1] * rhs.0[0],
+        ])
+    }
+}
+
+impl<T: Copy + Zero + Abs + Add<Output = T>, const D: usize> Vector<T, D> {
+    /// The sum of the absolute values of the coordinates
+    pub fn manhattan(self) -> T {
+        self.0.into_iter().fold(T::ZERO, |n, c| n + c.abs())
+    }
+}
+
+impl<T: Copy + Zero + Abs + Ord, const D: usize> Vector<T, D> {
+    /// The maximum of the absolute values of the coordinates
+    pub fn chebyshev(self) -> T {
+        self.0.into_iter().map(Abs::abs).max().unwrap_or(T::ZERO)
+    }
+}
+
+impl<T, const D: usize> Vector<T, D>
+where
+    T: Copy + Eq + Zero + Abs + Rem<Output = T> + DivAssign,
+{
+    /// Divide by the greatest common divisor of the coordinates, so that the
+    /// result is the shortest integer vector with the same direction
+    pub fn shrink(mut self) -> Self {
+        let g = self.0.into_iter().fold(T::ZERO, gcd).abs();
+        if g != T::ZERO {
+            self /= g;
+        }
+        self
+    }
+}
+
+impl<const D: usize> Vector<Rational, D> {
+    /// The integer vector, if all coordinates are integers
+    pub fn to_integer(self) -> Option<Vector<i128, D>> {
+        self.0
+            .iter()
+            .all(Rational::is_integer)
+            .then(|| self.map(|c| c.numer()))
+    }
+}
+
+impl<T: Zero, const D: usize> Zero for Vector<T, D> {
+    const ZERO: Self = Self([T::ZERO; D]);
+}
+
+impl<T, const D: usize> From<[T; D]> for Vector<T, D> {
+    fn from(value: [T; D]) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> From<(T, T)> for Vec2D<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self([x, y])
+    }
+}
+
+impl<T> From<(T, T, T)> for Vec3D<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self([x, y, z])
+    }
+}
+
+impl<T, const D: usize> Index<usize> for Vector<T, D> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<T, const D: usize> IndexMut<usize> for Vector<T, D> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<T: AddAssign, const D: usize> AddAssign for Vector<T, D> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (lhs, rhs) in self.0.iter_mut().zip(rhs.0) {
+            *lhs += rhs;
+        }
+    }
+}
+
+impl<T: AddAssign, const D: usize> Add for Vector<T, D> {
+    type Output = Self;
+
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += rhs;
+        self
+    }
+}
+
+impl<T: SubAssign, const D: usize> SubAssign for Vector<T, D> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (lhs, rhs) in self.0.iter_mut().zip(rhs.0) {
+            *lhs -= rhs;
+        }
+    }
+}
+
+impl<T: SubAssign, const D: usize> Sub for Vector<T, D> {
+    type Output = Self;
+
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= rhs;
+        self
+    }
+}
+
+impl<T: Neg<Output = T>, const D: usize> Neg for Vector<T, D> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.map(Neg::neg)
+    }
+}
+
+impl<T: MulAssign + Copy, const D: usize> MulAssign<T> for Vector<T, D> {
+    fn mul_assign(&mut self, rhs: T) {
+        for lhs in self.0.iter_mut() {
+            *lhs *= rhs;
+        }
+    }
+}
+
+impl<T: MulAssign + Copy, const D: usize> Mul<T> for Vector<T, D> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: T) -> Self::Output {
+        self *= rhs;
+        self
+    }
+}
+
+impl<T: DivAssign + Copy, const D: usize> DivAssign<T> for Vector<T, D> {
+    fn div_assign(&mut self, rhs: T) {
+        for lhs in self.0.iter_mut() {
+            *lhs /= rhs;
+        }
+    }
+}
+
+impl<T: DivAssign + Copy, const D: usize> Div<T> for Vector<T, D> {
+    type Output = Self;
+
+    fn div(mut self, rhs: T) -> Self::Output {
+        self /= rhs;
+        self
+    }
+}
+
+impl<T: Zero + AddAssign, const D: usize> Sum for Vector<T, D> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+/// The line `p + t v`
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Line<T, const D: usize> {
+    pub p: Vector<T, D>,
+    pub v: Vector<T, D>,
+}
+
+impl<T, const D: usize> Line<T, D> {
+    pub fn new(p: Vector<T, D>, v: Vector<T, D>) -> Self {
+        Self { p, v }
+    }
+}
+
+impl<T: Copy + AddAssign + MulAssign, const D: usize> Line<T, D> {
+    /// The point `p + t v`
+    pub fn at(&self, t: T) -> Vector<T, D> {
+        self.p + self.v * t
+    }
+}
+
+impl<T: Copy + Into<i128>, const D: usize> Line<T, D> {
+    fn to_i128(self) -> (Vector<i128, D>, Vector<i128, D>) {
+        (self.p.map(Into::into), self.v.map(Into::into))
+    }
+
+    /// The point `p + t v` for a rational `t`
+    pub fn at_rational(&self, t: Rational) -> Vector<Rational, D> {
+        let (p, v) = self.to_i128();
+        p.map(Rational::from) + v.map(Rational::from) * t
+    }
+
+    /// Check whether the direction vectors are parallel
+    ///
+    /// Lines with a zero direction vector are parallel to any line.
+    pub fn is_parallel(&self, other: &Self) -> bool {
+        let (_, va) = self.to_i128();
+        let (_, vb) = other.to_i128();
+        (0..D).all(|k| (k + 1..D).all(|l| va[k] * vb[l] == va[l] * vb[k]))
+    }
+
+    /// The parameters `(t_a, t_b)` such that `self.at(t_a) == other.at(t_b)`
+    ///
+    /// The result is `None` if there is no unique intersection, i.e., if the
+    /// lines are parallel (even if they coincide) or skew.
+    pub fn intersect(&self, other: &Self) -> Option<(Rational, Rational)> {
+        self.intersect_unreduced(other)
+            .map(|(num_a, num_b, den)| (Rational::new(num_a, den), Rational::new(num_b, den)))
+    }
+
+    /// Like [`Line::intersect`], but the parameters are returned as numerators
+    /// `(num_a, num_b)` with a common positive denominator `den`, i.e.,
+    /// `t_a = num_a / den` and `t_b = num_b / den`
+    ///
+    /// The fractions are not reduced, which saves the comparatively expensive
+    /// greatest common divisor calculations if only signs or bounds of the
+    /// parameters are needed.
+    pub fn intersect_unreduced(&self, other: &Self) -> Option<(i128, i128, i128)> {
+        let (pa, va) = self.to_i128();
+        let (pb, vb) = other.to_i128();
+        let d = pa - pb;
+
+        // d + t_a va - t_b vb = 0, solved with Cramer's rule in the first two
+        // dimensions where the lines are not parallel
+        let (num_a, num_b, den) = (0..D)
+            .flat_map(|k| (k + 1..D).map(move |l| (k, l)))
+            .map(|(k, l)| {
+                (
+                    vb[l] * d[k] - vb[k] * d[l],
+                    va[l] * d[k] - va[k] * d[l],
+                    vb[k] * va[l] - va[k] * vb[l],
+                )
+            })
+            .find(|&(_, _, den)| den != 0)?;
+
+        // lines in three or more dimensions may still be skew
+        let s = den.signum();
+        (0..D)
+            .all(|k| d[k] * den + va[k] * num_a == vb[k] * num_b)
+            .then_some((s * num_a, s * num_b, s * den))
+    }
+
+    /// Like [`Line::intersect`], but only if the intersection is at non-negative
+    /// parameters for both lines, i.e., if the rays starting at `p` intersect
+    pub fn intersect_rays(&self, other: &Self) -> Option<(Rational, Rational)> {
+        self.intersect(other)
+            .filter(|&(t_a, t_b)| t_a >= Rational::ZERO && t_b >= Rational::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::euclid::One;
+
+    #[test]
+    pub fn test_vector() {
+        let a = Vec3D::from((3, -6, 9));
+        assert_eq!(Vec3D::from((1, -2, 3)), a.shrink());
+        assert_eq!(Vec3D::<i64>::ZERO, Vec3D::ZERO.shrink());
+        assert_eq!(Vec3D::from((-3, 6, -9)), -a);
+        assert_eq!(Vec3D::from((1, -2, 3)), a / 3);
+        assert_eq!((3, -6, 9), (a.x(), a.y(), a.z()));
+        assert_eq!(18, a.manhattan());
+        assert_eq!(9, a.chebyshev());
+        assert_eq!(0, a.dot(a.cross(Vec3D::from((1, 1, 1)))));
+
+        let mut b = a;
+        b[2] -= 9;
+        b += Vec3D::from((1, 1, 1));
+        assert_eq!(Vec3D::from((4, -5, 1)), b);
+        assert_eq!(Vec3D::from((7, -11, 10)), [a, b].into_iter().sum());
+
+        assert_eq!(-2, Vec2D::from((1, 2)).perp_dot(Vec2D::from((2, 2))));
+        assert_eq!(5usize, Vec2D::from((2usize, 3)).manhattan());
+    }
+
+    #[test]
+    pub fn test_rational_vector() {
+        let a = Vec2D::from((1, 3)).map(Rational::from) * Rational::new(1, 2);
+        assert_eq!(Vec2D::from((Rational::new(1, 2), Rational::new(3, 2))), a);
+        assert_eq!(Rational::from(2), a.manhattan());
+        assert_eq!(Rational::new(3, 2), a.chebyshev());
+        assert_eq!(None, a.to_integer());
+        assert_eq!(
+            Some(Vec2D::from((1, 3))),
+            (a * Rational::from(2)).to_integer()
+        );
+    }
+
+    #[test]
+    pub fn test_intersect() {
+        // 3D lines through (1, 1, 1)
+        let l1 = Line::new(Vec3D::from((0, 0, 0)), Vec3D::from((2, 2, 2)));
+        let l2 = Line::new(Vec3D::from((1, 0, 1)), Vec3D::from((0, 3, 0)));
+        let (t1, t2) = l1.intersect(&l2).unwrap();
+        assert_eq!((Rational::new(1, 2), Rational::new(1, 3)), (t1, t2));
+        assert_eq!(l1.at_rational(t1), l2.at_rational(t2));
+        assert_eq!(Some((3, 2, 6)), l1.intersect_unreduced(&l2));
+        assert_eq!(
+            Some(Vec3D::from((1, 1, 1))),
+            l1.at_rational(t1).to_integer()
+        );
+
+        // skew
+        let l3 = Line::new(Vec3D::from((1, 0, 2)), Vec3D::from((0, 3, 0)));
+        assert_eq!(None, l1.intersect(&l3));
+        assert!(!l1.is_parallel(&l3));
+
+        // parallel
+        let l4 = Line::new(Vec3D::from((1, 0, 0)), Vec3D::from((-1, -1, -1)));
+        assert_eq!(None, l1.intersect(&l4));
+        assert!(l1.is_parallel(&l4));
+
+        // rays
+        let r1 = Line::new(Vec2D::from((0i64, 0)), Vec2D::from((1, 0)));
+        let r2 = Line::new(Vec2D::from((2i64, -1)), Vec2D::from((0, 1)));
+        let r3 = Line::new(Vec2D::from((-2i64, -1)), Vec2D::from((0, 1)));
+        assert_eq!(
+            Some((Rational::from(2), Rational::ONE)),
+            r1.intersect_rays(&r2)
+        );
+        assert_eq!(None, r1.intersect_rays(&r3));
+        assert!(r1.intersect(&r3).is_some());
+    }
+}